@@ -0,0 +1,43 @@
+use crate::core::models::backup::{BackupInfo, BackupReason, RestorePreview, RestoreResult, RetentionPolicy};
+use crate::services::backup::BackupService;
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn create_backup(campaign_id: String) -> DmResult<BackupInfo> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    BackupService::create_backup(&campaign_uuid, BackupReason::Manual)
+}
+
+#[tauri::command]
+pub async fn list_backups(campaign_id: Option<String>) -> DmResult<Vec<BackupInfo>> {
+    let campaign_uuid = campaign_id.map(|id| validate_uuid(&id, "Campaign")).transpose()?;
+    BackupService::list_backups(campaign_uuid.as_ref())
+}
+
+#[tauri::command]
+pub async fn preview_restore(campaign_id: String, backup_id: String) -> DmResult<RestorePreview> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    BackupService::preview_restore(&campaign_uuid, &backup_id)
+}
+
+#[tauri::command]
+pub async fn restore_backup(campaign_id: String, backup_id: String) -> DmResult<RestoreResult> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    BackupService::restore_backup(&campaign_uuid, &backup_id)
+}
+
+#[tauri::command]
+pub async fn prune_backups(campaign_id: String) -> DmResult<Vec<BackupInfo>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    BackupService::prune(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn get_backup_retention() -> DmResult<RetentionPolicy> {
+    BackupService::retention_policy()
+}
+
+#[tauri::command]
+pub async fn set_backup_retention(policy: RetentionPolicy) -> DmResult<RetentionPolicy> {
+    BackupService::set_retention_policy(policy)
+}
@@ -0,0 +1,103 @@
+use crate::core::models::backup::BackupInfo;
+use crate::core::models::campaign::{Campaign, CreateCampaignRequest, UpdateCampaignRequest};
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::storage::{StorageBackend, StorageConversion};
+use crate::utils::error::{DmResult, validate_uuid, validate_non_empty, validate_range};
+
+#[tauri::command]
+pub async fn create_campaign(req: CreateCampaignRequest) -> DmResult<Campaign> {
+    validate_non_empty(&req.name, "Campaign name")?;
+    if let Some(level) = req.party_level {
+        validate_range(level, 1, 20, "Party level")?;
+    }
+
+    let campaign = Campaign::new(req);
+    CampaignStorageService::create_campaign(campaign)
+}
+
+#[tauri::command]
+pub async fn get_campaign(campaign_id: String) -> DmResult<Option<Campaign>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::get_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn list_campaigns(include_archived: Option<bool>) -> DmResult<Vec<Campaign>> {
+    CampaignStorageService::list_campaigns(include_archived.unwrap_or(false))
+}
+
+#[tauri::command]
+pub async fn update_campaign(
+    campaign_id: String,
+    req: UpdateCampaignRequest,
+) -> DmResult<Campaign> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    if let Some(name) = &req.name {
+        validate_non_empty(name, "Campaign name")?;
+    }
+    if let Some(level) = req.party_level {
+        validate_range(level, 1, 20, "Party level")?;
+    }
+
+    // Moving on to a new session snapshots the campaign
+    let (campaign, _) = CampaignStorageService::modify_campaign_with_session_backup(&campaign_uuid, |campaign| {
+        campaign.update(req);
+        Ok(())
+    })?;
+    Ok(campaign)
+}
+
+#[tauri::command]
+pub async fn delete_campaign(campaign_id: String) -> DmResult<String> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let backup_path = CampaignStorageService::delete_campaign(&campaign_uuid)?;
+    Ok(backup_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn archive_campaign(campaign_id: String) -> DmResult<Campaign> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::archive_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn restore_campaign(campaign_id: String) -> DmResult<Campaign> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::restore_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn duplicate_campaign(campaign_id: String, new_name: Option<String>) -> DmResult<Campaign> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::duplicate_campaign(&campaign_uuid, new_name)
+}
+
+#[tauri::command]
+pub async fn set_active_campaign(campaign_id: String) -> DmResult<Campaign> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::set_active_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn get_active_campaign() -> DmResult<Option<Campaign>> {
+    CampaignStorageService::get_active_campaign()
+}
+
+#[tauri::command]
+pub async fn get_campaign_storage_backend(campaign_id: String) -> DmResult<StorageBackend> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::ensure_exists(&campaign_uuid)?;
+    StorageBackend::of(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn convert_campaign_storage(campaign_id: String, backend: StorageBackend) -> DmResult<StorageConversion> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::convert_storage(&campaign_uuid, backend)
+}
+
+#[tauri::command]
+pub async fn start_session(campaign_id: String, session_number: Option<u32>) -> DmResult<BackupInfo> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    CampaignStorageService::start_session(&campaign_uuid, session_number)
+}
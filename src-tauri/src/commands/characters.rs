@@ -3,8 +3,12 @@ use crate::core::models::character::{
     CreateCharacterRequest, 
     UpdateCharacterRequest,
     AddAchievementRequest,
-    UpdateRelationshipRequest
+    UpdateRelationshipRequest,
+    NpcRelationshipFilter,
+    CampaignNpcRelationships,
 };
+use crate::core::models::backup::BackupReason;
+use crate::services::backup::BackupService;
 use crate::services::character_storage::CharacterStorageService;
 use crate::utils::error::{DmAssistantError, DmResult, validate_uuid};
 use uuid::Uuid;
@@ -21,10 +25,7 @@ pub async fn create_character(req: CreateCharacterRequest) -> DmResult<PlayerCha
 #[tauri::command]
 pub async fn get_character(character_id: String) -> DmResult<Option<PlayerCharacter>> {
     let id = validate_uuid(&character_id, "Character")?;
-    
-    // We need campaign_id to load from file, but for now we'll return an error
-    // This will be fixed when we implement proper campaign management
-    Err(DmAssistantError::internal("get_character requires campaign context - use get_character_with_campaign instead"))
+    CharacterStorageService::find_character(&id)
 }
 
 #[tauri::command]
@@ -56,10 +57,12 @@ pub async fn update_character(
     req: UpdateCharacterRequest,
 ) -> DmResult<PlayerCharacter> {
     let id = validate_uuid(&character_id, "Character")?;
+    let campaign_id = CharacterStorageService::require_campaign_of(&id)?;
     
-    // Get current character to find campaign_id
-    // For now, we'll need campaign_id as parameter
-    Err(DmAssistantError::internal("update_character requires campaign context - use update_character_with_campaign instead"))
+    CharacterStorageService::modify_character(&campaign_id, &id, |character| {
+        character.update(req);
+        Ok(())
+    })
 }
 
 #[tauri::command]
@@ -161,11 +164,18 @@ pub async fn remove_character_relationship(
 }
 
 #[tauri::command]
-pub async fn get_characters_by_npc_relationship(npc_id: String) -> DmResult<Vec<PlayerCharacter>> {
+pub async fn get_characters_by_npc_relationship(
+    npc_id: String,
+    filter: Option<NpcRelationshipFilter>,
+) -> DmResult<Vec<CampaignNpcRelationships>> {
     let npc_uuid = validate_uuid(&npc_id, "NPC")?;
+    if let Some(NpcRelationshipFilter { interacted_after: Some(after), interacted_before: Some(before), .. }) = &filter {
+        if after > before {
+            return Err(DmAssistantError::validation("Interaction range start must be before its end"));
+        }
+    }
     
-    // This requires cross-campaign search, which is not implemented yet
-    CharacterStorageService::get_characters_by_npc_relationship(&npc_uuid)
+    CharacterStorageService::get_characters_by_npc_relationship(&npc_uuid, &filter.unwrap_or_default())
 }
 
 #[tauri::command]
@@ -206,11 +216,10 @@ pub async fn toggle_character_active(
 pub async fn save_characters_to_file(campaign_id: String) -> DmResult<String> {
     let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
     
-    // Create backup before saving
-    let backup_path = CharacterStorageService::backup_characters(&campaign_uuid)
-        .unwrap_or_else(|_| std::path::PathBuf::from("backup failed"));
+    let saved = CharacterStorageService::resave_characters(&campaign_uuid)?;
+    let backup = BackupService::create_backup(&campaign_uuid, BackupReason::Manual)?;
     
-    Ok(format!("Characters saved successfully. Backup created at: {}", backup_path.display()))
+    Ok(format!("{} characters saved successfully. Backup created at: {}", saved, backup.path))
 }
 
 #[tauri::command]
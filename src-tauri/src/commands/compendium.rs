@@ -0,0 +1,32 @@
+use crate::core::models::compendium::{
+    CompendiumEntry,
+    CompendiumKind,
+    CompendiumListing,
+    CompendiumQuery,
+    ContentPack,
+    CopiedEntity,
+    CopyCompendiumEntryRequest,
+};
+use crate::services::compendium::CompendiumService;
+use crate::utils::error::DmResult;
+
+#[tauri::command]
+pub async fn list_content_packs() -> DmResult<Vec<ContentPack>> {
+    Ok(CompendiumService::list_packs())
+}
+
+/// Compendium entries, along with the homebrew of the campaign if one is given
+#[tauri::command]
+pub async fn search_compendium(query: CompendiumQuery) -> DmResult<Vec<CompendiumListing>> {
+    CompendiumService::search(&query)
+}
+
+#[tauri::command]
+pub async fn get_compendium_entry(pack: String, kind: CompendiumKind, key: String) -> DmResult<CompendiumEntry> {
+    CompendiumService::get_entry(&pack, kind, &key)
+}
+
+#[tauri::command]
+pub async fn copy_compendium_entry(req: CopyCompendiumEntryRequest) -> DmResult<CopiedEntity> {
+    CompendiumService::copy_to_campaign(req)
+}
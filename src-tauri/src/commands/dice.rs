@@ -0,0 +1,34 @@
+use crate::core::models::common::{DiceStatistics, RollDiceRequest, RollHistoryFilter, RollRecord, RollVerification};
+use crate::core::rules::dice_stats;
+use crate::services::dice_roller::{DiceRoller, RollHistoryService};
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn roll_dice(campaign_id: String, req: RollDiceRequest) -> DmResult<RollRecord> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+
+    let mut roller = DiceRoller::new();
+    RollHistoryService::roll(&mut roller, &campaign_uuid, req)
+}
+
+#[tauri::command]
+pub async fn get_roll_history(
+    campaign_id: String,
+    filter: Option<RollHistoryFilter>,
+) -> DmResult<Vec<RollRecord>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    RollHistoryService::get_history(&campaign_uuid, &filter.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn verify_roll(campaign_id: String, roll_id: String) -> DmResult<RollVerification> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let roll_uuid = validate_uuid(&roll_id, "Roll")?;
+
+    RollHistoryService::verify_roll(&campaign_uuid, &roll_uuid)
+}
+
+#[tauri::command]
+pub async fn dice_statistics(expression: String, target: Option<i64>) -> DmResult<DiceStatistics> {
+    dice_stats::statistics(&expression, target)
+}
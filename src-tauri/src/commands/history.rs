@@ -0,0 +1,21 @@
+use crate::core::models::history::{HistoryList, HistorySummary};
+use crate::services::history::HistoryService;
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn undo(campaign_id: String) -> DmResult<Option<HistorySummary>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    HistoryService::undo(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn redo(campaign_id: String) -> DmResult<Option<HistorySummary>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    HistoryService::redo(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn list_history(campaign_id: String) -> DmResult<HistoryList> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    HistoryService::list_history(&campaign_uuid)
+}
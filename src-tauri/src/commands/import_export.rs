@@ -0,0 +1,46 @@
+use crate::core::models::archive::{
+    ArchiveManifest, BestiaryFormat, BestiaryImportResult, ExportResult, ImportMode, ImportResult,
+};
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::import_export::ImportExportService;
+use crate::utils::error::{DmResult, validate_uuid, validate_non_empty};
+use std::path::{Path, PathBuf};
+
+#[tauri::command]
+pub async fn export_campaign(campaign_id: String, destination_path: Option<String>) -> DmResult<ExportResult> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let destination = match destination_path {
+        Some(path) => {
+            validate_non_empty(&path, "Destination path")?;
+            PathBuf::from(path)
+        }
+        None => {
+            let campaign = CampaignStorageService::require_campaign(&campaign_uuid)?;
+            ImportExportService::exports_dir()?.join(ImportExportService::archive_file_name(&campaign))
+        }
+    };
+    ImportExportService::export_campaign(&campaign_uuid, &destination)
+}
+
+#[tauri::command]
+pub async fn inspect_campaign_archive(archive_path: String) -> DmResult<ArchiveManifest> {
+    validate_non_empty(&archive_path, "Archive path")?;
+    ImportExportService::inspect_archive(Path::new(&archive_path))
+}
+
+#[tauri::command]
+pub async fn import_campaign(archive_path: String, mode: ImportMode) -> DmResult<ImportResult> {
+    validate_non_empty(&archive_path, "Archive path")?;
+    ImportExportService::import_campaign(Path::new(&archive_path), mode)
+}
+
+#[tauri::command]
+pub async fn import_bestiary(
+    campaign_id: String,
+    file_path: String,
+    format: Option<BestiaryFormat>,
+) -> DmResult<BestiaryImportResult> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    validate_non_empty(&file_path, "Bestiary path")?;
+    ImportExportService::import_bestiary(&campaign_uuid, Path::new(&file_path), format)
+}
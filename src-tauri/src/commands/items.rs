@@ -0,0 +1,47 @@
+use crate::core::models::item::{
+    Item,
+    CreateItemRequest,
+    UpdateItemRequest,
+};
+use crate::services::item_storage::ItemStorageService;
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn create_item(req: CreateItemRequest) -> DmResult<Item> {
+    let item = Item::new(req);
+    ItemStorageService::create_item(item)
+}
+
+#[tauri::command]
+pub async fn get_item(campaign_id: String, item_id: String) -> DmResult<Option<Item>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let item_uuid = validate_uuid(&item_id, "Item")?;
+
+    ItemStorageService::get_item(&campaign_uuid, &item_uuid)
+}
+
+#[tauri::command]
+pub async fn get_items_by_campaign(campaign_id: String) -> DmResult<Vec<Item>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    ItemStorageService::get_items_by_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn update_item(
+    campaign_id: String,
+    item_id: String,
+    req: UpdateItemRequest,
+) -> DmResult<Item> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let item_uuid = validate_uuid(&item_id, "Item")?;
+
+    ItemStorageService::update_item(&campaign_uuid, &item_uuid, req)
+}
+
+#[tauri::command]
+pub async fn delete_item(campaign_id: String, item_id: String) -> DmResult<bool> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let item_uuid = validate_uuid(&item_id, "Item")?;
+
+    ItemStorageService::delete_item(&campaign_uuid, &item_uuid)
+}
@@ -1,11 +1,17 @@
 pub mod campaigns;
+pub mod backups;
+pub mod import_export;
+pub mod history;
 pub mod characters;
 pub mod npcs;
+pub mod relationships;
 pub mod environments;
 pub mod items;
 pub mod inventory;
 pub mod quests;
 pub mod monsters;
+pub mod spells;
+pub mod compendium;
 pub mod encounters;
 pub mod combat;
 pub mod maps;
@@ -0,0 +1,69 @@
+use crate::core::models::monster::{
+    Monster,
+    MonsterStats,
+    StatBlock,
+    CreateMonsterRequest,
+    UpdateMonsterRequest,
+    ScaleMonsterRequest,
+    ScaledMonster,
+};
+use crate::core::rules::{ability_scores, challenge_rating};
+use crate::core::rules::challenge_rating::CrCalculation;
+use crate::services::monster_storage::MonsterStorageService;
+use crate::services::templates::TemplateService;
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn create_monster(req: CreateMonsterRequest) -> DmResult<Monster> {
+    let monster = Monster::new(req);
+    MonsterStorageService::create_monster(monster)
+}
+
+#[tauri::command]
+pub async fn get_monster(monster_id: String) -> DmResult<Option<Monster>> {
+    let monster_uuid = validate_uuid(&monster_id, "Monster")?;
+    MonsterStorageService::find_monster(&monster_uuid)
+}
+
+#[tauri::command]
+pub async fn get_monsters_by_campaign(campaign_id: String) -> DmResult<Vec<Monster>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    MonsterStorageService::get_monsters_by_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn update_monster(
+    campaign_id: String,
+    monster_id: String,
+    req: UpdateMonsterRequest,
+) -> DmResult<Monster> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let monster_uuid = validate_uuid(&monster_id, "Monster")?;
+
+    MonsterStorageService::update_monster(&campaign_uuid, &monster_uuid, req)
+}
+
+#[tauri::command]
+pub async fn delete_monster(campaign_id: String, monster_id: String) -> DmResult<bool> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let monster_uuid = validate_uuid(&monster_id, "Monster")?;
+
+    MonsterStorageService::delete_monster(&campaign_uuid, &monster_uuid)
+}
+
+/// Derived values of a stat block, saved or still being edited
+#[tauri::command]
+pub async fn get_monster_stats(stat_block: StatBlock) -> DmResult<MonsterStats> {
+    Ok(ability_scores::monster_stats(&stat_block))
+}
+
+#[tauri::command]
+pub async fn calculate_challenge_rating(monster: Monster) -> DmResult<CrCalculation> {
+    challenge_rating::calculate(&monster.stat_block)
+}
+
+/// New monster made from another one with templates, not saved yet
+#[tauri::command]
+pub async fn scale_monster(req: ScaleMonsterRequest) -> DmResult<ScaledMonster> {
+    TemplateService::scale_monster(req)
+}
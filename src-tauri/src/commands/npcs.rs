@@ -0,0 +1,72 @@
+use crate::core::models::npc::{
+    Npc,
+    NpcInteraction,
+    CreateNpcRequest,
+    UpdateNpcRequest,
+    LogInteractionRequest,
+    NpcDeletionReport,
+};
+use crate::services::npc_storage::NpcStorageService;
+use crate::utils::error::{DmResult, validate_uuid, validate_non_empty};
+
+#[tauri::command]
+pub async fn create_npc(req: CreateNpcRequest) -> DmResult<Npc> {
+    validate_non_empty(&req.name, "NPC name")?;
+    
+    let npc = Npc::new(req);
+    NpcStorageService::create_npc(npc)
+}
+
+#[tauri::command]
+pub async fn get_npc(npc_id: String) -> DmResult<Option<Npc>> {
+    let npc_uuid = validate_uuid(&npc_id, "NPC")?;
+    NpcStorageService::find_npc(&npc_uuid)
+}
+
+#[tauri::command]
+pub async fn get_npcs_by_campaign(campaign_id: String) -> DmResult<Vec<Npc>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    NpcStorageService::get_npcs_by_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn update_npc(
+    campaign_id: String,
+    npc_id: String,
+    req: UpdateNpcRequest,
+) -> DmResult<Npc> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let npc_uuid = validate_uuid(&npc_id, "NPC")?;
+    if let Some(name) = &req.name {
+        validate_non_empty(name, "NPC name")?;
+    }
+    
+    NpcStorageService::modify_npc(&campaign_uuid, &npc_uuid, |npc| {
+        npc.update(req);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn delete_npc(
+    campaign_id: String,
+    npc_id: String,
+    remove_relationships: Option<bool>,
+) -> DmResult<NpcDeletionReport> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let npc_uuid = validate_uuid(&npc_id, "NPC")?;
+    
+    NpcStorageService::delete_npc(&campaign_uuid, &npc_uuid, remove_relationships.unwrap_or(false))
+}
+
+#[tauri::command]
+pub async fn log_npc_interaction(
+    campaign_id: String,
+    npc_id: String,
+    req: LogInteractionRequest,
+) -> DmResult<NpcInteraction> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let npc_uuid = validate_uuid(&npc_id, "NPC")?;
+    
+    NpcStorageService::log_interaction(&campaign_uuid, &npc_uuid, req)
+}
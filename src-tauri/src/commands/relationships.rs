@@ -0,0 +1,77 @@
+use crate::core::models::relationship::{
+    GraphNode,
+    RelationshipEdge,
+    EdgeDirection,
+    CreateEdgeRequest,
+    UpdateEdgeRequest,
+    GraphNeighborhood,
+    GraphExport,
+};
+use crate::services::relationship_graph::RelationshipGraphService;
+use crate::utils::error::{DmResult, validate_uuid, validate_range};
+
+#[tauri::command]
+pub async fn get_relationship_graph(campaign_id: String) -> DmResult<GraphExport> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    RelationshipGraphService::export_json(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn add_relationship_edge(
+    campaign_id: String,
+    req: CreateEdgeRequest,
+) -> DmResult<RelationshipEdge> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    RelationshipGraphService::add_edge(&campaign_uuid, req)
+}
+
+#[tauri::command]
+pub async fn update_relationship_edge(
+    campaign_id: String,
+    edge_id: String,
+    req: UpdateEdgeRequest,
+) -> DmResult<RelationshipEdge> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let edge_uuid = validate_uuid(&edge_id, "Relationship")?;
+    RelationshipGraphService::update_edge(&campaign_uuid, &edge_uuid, req)
+}
+
+#[tauri::command]
+pub async fn remove_relationship_edge(campaign_id: String, edge_id: String) -> DmResult<()> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let edge_uuid = validate_uuid(&edge_id, "Relationship")?;
+    RelationshipGraphService::remove_edge(&campaign_uuid, &edge_uuid)
+}
+
+#[tauri::command]
+pub async fn get_node_neighborhood(
+    campaign_id: String,
+    node: GraphNode,
+    depth: Option<usize>,
+    direction: Option<EdgeDirection>,
+) -> DmResult<GraphNeighborhood> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let depth = depth.unwrap_or(1);
+    validate_range(depth, 1, 6, "Depth")?;
+    
+    let graph = RelationshipGraphService::load_graph(&campaign_uuid)?;
+    Ok(graph.neighborhood(&node, depth, direction.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn find_relationship_path(
+    campaign_id: String,
+    from: GraphNode,
+    to: GraphNode,
+    direction: Option<EdgeDirection>,
+) -> DmResult<Option<Vec<RelationshipEdge>>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let graph = RelationshipGraphService::load_graph(&campaign_uuid)?;
+    Ok(graph.find_path(&from, &to, direction.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn export_relationship_graph_dot(campaign_id: String) -> DmResult<String> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    RelationshipGraphService::export_dot(&campaign_uuid)
+}
@@ -0,0 +1,47 @@
+use crate::core::models::spell::{
+    Spell,
+    CreateSpellRequest,
+    UpdateSpellRequest,
+};
+use crate::services::spell_storage::SpellStorageService;
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn create_spell(req: CreateSpellRequest) -> DmResult<Spell> {
+    let spell = Spell::new(req);
+    SpellStorageService::create_spell(spell)
+}
+
+#[tauri::command]
+pub async fn get_spell(campaign_id: String, spell_id: String) -> DmResult<Option<Spell>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let spell_uuid = validate_uuid(&spell_id, "Spell")?;
+
+    SpellStorageService::get_spell(&campaign_uuid, &spell_uuid)
+}
+
+#[tauri::command]
+pub async fn get_spells_by_campaign(campaign_id: String) -> DmResult<Vec<Spell>> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    SpellStorageService::get_spells_by_campaign(&campaign_uuid)
+}
+
+#[tauri::command]
+pub async fn update_spell(
+    campaign_id: String,
+    spell_id: String,
+    req: UpdateSpellRequest,
+) -> DmResult<Spell> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let spell_uuid = validate_uuid(&spell_id, "Spell")?;
+
+    SpellStorageService::update_spell(&campaign_uuid, &spell_uuid, req)
+}
+
+#[tauri::command]
+pub async fn delete_spell(campaign_id: String, spell_id: String) -> DmResult<bool> {
+    let campaign_uuid = validate_uuid(&campaign_id, "Campaign")?;
+    let spell_uuid = validate_uuid(&spell_id, "Spell")?;
+
+    SpellStorageService::delete_spell(&campaign_uuid, &spell_uuid)
+}
@@ -0,0 +1,32 @@
+use crate::core::models::common::EntityLocation;
+use crate::services::entity_index::EntityIndexService;
+use crate::services::migrations::{MigrationReport, MigrationService};
+use crate::services::storage::{CampaignStore, StoreStats};
+use crate::utils::error::{DmResult, validate_uuid};
+
+#[tauri::command]
+pub async fn rebuild_entity_index() -> DmResult<usize> {
+    EntityIndexService::rebuild()
+}
+
+#[tauri::command]
+pub async fn resolve_entity(entity_id: String) -> DmResult<Option<EntityLocation>> {
+    let id = validate_uuid(&entity_id, "Entity")?;
+    EntityIndexService::lookup(&id)
+}
+
+#[tauri::command]
+pub async fn migrate_campaigns() -> DmResult<MigrationReport> {
+    MigrationService::migrate_all()
+}
+
+#[tauri::command]
+pub async fn get_storage_stats() -> DmResult<StoreStats> {
+    Ok(CampaignStore::global().stats())
+}
+
+#[tauri::command]
+pub async fn clear_storage_cache() -> DmResult<()> {
+    CampaignStore::global().clear();
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use crate::core::models::backup::BackupInfo;
+use crate::core::models::campaign::Campaign;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Manifest of a portable campaign archive (`.dmcampaign`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    pub format: String, // Always "dmcampaign"
+    pub format_version: u32,
+    pub app_version: String, // Version of the app that exported the archive
+    #[serde(alias = "schema_version")] // Key of the archives exported before schema version 2
+    pub schema_version: u32,
+    pub campaign_id: Uuid,
+    pub campaign_name: String,
+    pub exported_at: DateTime<Utc>,
+    pub files: Vec<ArchiveFile>,
+}
+
+/// File stored in a campaign archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveFile {
+    pub path: String, // Relative to the campaign directory
+    pub size: u64,
+    pub sha256: String, // Hex digest of the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_path: Option<String>, // For assets referenced from outside the campaign directory
+}
+
+/// Where an archive is imported
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum ImportMode {
+    /// As a new campaign, with new IDs for the campaign and every entity
+    #[serde(rename_all = "camelCase")]
+    CreateNew { name: Option<String> },
+    /// Into an existing campaign
+    #[serde(rename_all = "camelCase")]
+    Merge {
+        campaign_id: Uuid,
+        #[serde(default)]
+        conflicts: ConflictResolution,
+    },
+}
+
+/// What to do when a merged entity already exists in the campaign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictResolution {
+    KeepExisting,
+    Replace,
+    #[default]
+    KeepNewer, // Whichever was updated last
+}
+
+/// Outcome of exporting a campaign
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub path: String,
+    pub size_bytes: u64,
+    pub manifest: ArchiveManifest,
+}
+
+/// Outcome of importing an archive
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub campaign: Campaign,
+    pub added: usize, // Entities and relationship edges
+    pub updated: usize,
+    pub skipped: usize, // Conflicts resolved in favour of the campaign
+    pub assets: usize,
+    pub safety_backup: Option<BackupInfo>, // Campaign before a merge
+}
+
+/// Tool a bestiary was exported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BestiaryFormat {
+    #[serde(rename = "5etools")]
+    FiveEtools, // `{"monster": [...]}`, with `{@tag}` markup in its text
+    Open5e, // Monsters of the Open5e API (v1), e.g. `{"results": [...]}`
+}
+
+/// Data of an imported monster that has no place in the stat block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmappedField {
+    pub field: String, // As named in the source, e.g. "hp", "action[Fire Breath]"
+    pub value: String,
+}
+
+/// Outcome of importing one monster of a bestiary
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedMonster {
+    pub name: String,
+    pub monster_id: Option<Uuid>, // None if it couldn't be imported
+    pub error: Option<String>,
+    pub unmapped: Vec<UnmappedField>,
+}
+
+/// Outcome of importing a bestiary
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BestiaryImportResult {
+    pub format: BestiaryFormat,
+    pub imported: usize,
+    pub failed: usize,
+    pub monsters: Vec<ImportedMonster>,
+}
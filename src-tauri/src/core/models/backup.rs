@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Why a backup was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupReason {
+    Manual,
+    SessionStart,
+    BeforeRestore,
+    BeforeDelete,
+    BeforeConversion,
+    BeforeImport,
+}
+
+/// Backup archive of a campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub id: String, // Archive name without extension, unique within the campaign
+    pub campaign_id: Uuid,
+    pub campaign_name: String,
+    pub reason: BackupReason,
+    pub session_number: Option<u32>, // Campaign session when the backup was taken
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64, // Size of the compressed archive
+    pub file_count: usize,
+    pub path: String,
+}
+
+/// Which backups of a campaign are kept when a new one is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionPolicy {
+    pub keep_last: usize, // Most recent backups, whatever their age
+    pub daily_days: u32, // Newest backup of each of the last N days
+    pub weekly_weeks: u32, // Newest backup of each of the last N weeks
+}
+
+/// How a file or entity would change if a backup were restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RestoreChange {
+    Added, // In the backup, not in the campaign
+    Removed, // In the campaign, not in the backup
+    Modified,
+}
+
+/// File of the campaign directory changed by a restore
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRestoreChange {
+    pub path: String, // Relative to the campaign directory
+    pub change: RestoreChange,
+}
+
+/// Entity changed by a restore
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityRestoreChange {
+    pub collection: String, // e.g. "characters", "npcs"
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub change: RestoreChange,
+}
+
+/// What restoring a backup would change
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePreview {
+    pub backup: BackupInfo,
+    pub files: Vec<FileRestoreChange>,
+    pub entities: Vec<EntityRestoreChange>, // Only for JSON collections
+    pub unchanged_files: usize,
+}
+
+/// Outcome of restoring a backup
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResult {
+    pub changes: RestorePreview,
+    pub safety_backup: Option<BackupInfo>, // State of the campaign right before the restore
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last: 10,
+            daily_days: 7,
+            weekly_weeks: 4,
+        }
+    }
+}
+
+impl BackupReason {
+    /// Tag used in archive names
+    pub fn tag(&self) -> &'static str {
+        match self {
+            BackupReason::Manual => "manual",
+            BackupReason::SessionStart => "session-start",
+            BackupReason::BeforeRestore => "before-restore",
+            BackupReason::BeforeDelete => "before-delete",
+            BackupReason::BeforeConversion => "before-conversion",
+            BackupReason::BeforeImport => "before-import",
+        }
+    }
+}
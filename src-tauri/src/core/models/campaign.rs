@@ -0,0 +1,95 @@
+use crate::core::models::common::SCHEMA_VERSION;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Campaign managed by the DM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Campaign {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub setting: String, // World/setting, e.g. "Forgotten Realms" or homebrew
+    pub ruleset: Ruleset,
+    pub current_session: u32, // Number of the current (or next) session
+    pub in_game_date: Option<String>, // Free-form, settings use their own calendars
+    pub party_level: u8,
+    #[serde(default)]
+    pub is_archived: bool, // Archived campaigns are read-only and hidden from the active list
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Rules the campaign is played with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Ruleset {
+    Dnd5e2014,
+    Dnd5e2024,
+    Custom(String),
+}
+
+/// Request to create a new campaign
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCampaignRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub setting: Option<String>,
+    pub ruleset: Option<Ruleset>,
+    pub party_level: Option<u8>,
+    pub in_game_date: Option<String>,
+}
+
+/// Request to update campaign
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCampaignRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub setting: Option<String>,
+    pub ruleset: Option<Ruleset>,
+    pub current_session: Option<u32>,
+    pub in_game_date: Option<String>,
+    pub party_level: Option<u8>,
+}
+
+impl Campaign {
+    /// Create a new campaign
+    pub fn new(req: CreateCampaignRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            id: Uuid::new_v4(),
+            name: req.name,
+            description: req.description.unwrap_or_default(),
+            setting: req.setting.unwrap_or_default(),
+            ruleset: req.ruleset.unwrap_or(Ruleset::Dnd5e2014),
+            current_session: 1,
+            in_game_date: req.in_game_date,
+            party_level: req.party_level.unwrap_or(1),
+            is_archived: false,
+            archived_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update campaign data
+    pub fn update(&mut self, req: UpdateCampaignRequest) {
+        if let Some(name) = req.name { self.name = name; }
+        if let Some(description) = req.description { self.description = description; }
+        if let Some(setting) = req.setting { self.setting = setting; }
+        if let Some(ruleset) = req.ruleset { self.ruleset = ruleset; }
+        if let Some(current_session) = req.current_session { self.current_session = current_session; }
+        if let Some(in_game_date) = req.in_game_date { self.in_game_date = Some(in_game_date); }
+        if let Some(party_level) = req.party_level { self.party_level = party_level; }
+
+        self.updated_at = Utc::now();
+    }
+}
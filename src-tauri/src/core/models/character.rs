@@ -61,7 +61,7 @@ pub struct CharacterRelationship {
 }
 
 /// Simple relationship states
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RelationshipType {
     Neutral,
@@ -125,6 +125,32 @@ pub struct UpdateRelationshipRequest {
     pub notes: Option<String>,
 }
 
+/// Filters for searching characters by NPC relationship
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NpcRelationshipFilter {
+    pub relationship_types: Option<Vec<RelationshipType>>, // Any of these types
+    pub interacted_after: Option<DateTime<Utc>>, // Inclusive
+    pub interacted_before: Option<DateTime<Utc>>, // Inclusive
+}
+
+/// Character with its relationship to the searched NPC
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NpcRelationshipMatch {
+    pub character: PlayerCharacter,
+    pub relationship: CharacterRelationship,
+}
+
+/// Characters of a campaign related to the searched NPC
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignNpcRelationships {
+    pub campaign_id: Uuid,
+    pub campaign_name: Option<String>, // None if the campaign has no manifest
+    pub matches: Vec<NpcRelationshipMatch>,
+}
+
 impl PlayerCharacter {
     /// Create a new character
     pub fn new(req: CreateCharacterRequest) -> Self {
@@ -220,6 +246,32 @@ impl PlayerCharacter {
     }
 }
 
+impl NpcRelationshipFilter {
+    /// Check if a relationship matches the filter.
+    /// Relationships without a recorded interaction never match a date range.
+    pub fn matches(&self, relationship: &CharacterRelationship) -> bool {
+        if let Some(types) = &self.relationship_types {
+            if !types.is_empty() && !types.contains(&relationship.relationship_type) {
+                return false;
+            }
+        }
+
+        if self.interacted_after.is_some() || self.interacted_before.is_some() {
+            let Some(last_interaction) = relationship.last_interaction else {
+                return false;
+            };
+            if self.interacted_after.is_some_and(|after| last_interaction < after) {
+                return false;
+            }
+            if self.interacted_before.is_some_and(|before| last_interaction > before) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 impl Achievement {
     /// Check if achievement is linked to a quest
     pub fn is_quest_related(&self) -> bool {
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Schema version of the files written by this version of the app, stored
+/// as `schema_version` in each of them (`schemaVersion` in the files with
+/// camelCase keys). Files without it are version 0.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Result of evaluating a dice expression, with every individual die
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiceRoll {
+    pub expression: String,
+    pub total: i64,
+    pub terms: Vec<DiceTermRoll>, // One entry per dice term, in order of appearance
+    pub breakdown: String, // Human readable form, e.g. "4d6kh3[6, 5, 3, ~1~] + 2"
+}
+
+/// Dice rolled for a single `NdM` term of an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiceTermRoll {
+    pub notation: String,
+    pub sides: u32,
+    pub dice: Vec<DieResult>,
+    pub subtotal: i64, // Sum of kept dice
+}
+
+/// Outcome of a single die, including rerolls and explosions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DieResult {
+    pub value: i64, // Final value after explosions and min/max clamps
+    pub faces: Vec<u32>, // Faces that make up the value (more than one if exploded)
+    pub rerolled: Vec<u32>, // Faces discarded by reroll modifiers
+    pub exploded: bool,
+    pub clamped: bool,
+    pub kept: bool,
+}
+
+impl DiceRoll {
+    /// Get every die rolled by the expression
+    pub fn all_dice(&self) -> impl Iterator<Item = &DieResult> {
+        self.terms.iter().flat_map(|t| t.dice.iter())
+    }
+
+    /// Get the kept dice of the expression
+    pub fn kept_dice(&self) -> impl Iterator<Item = &DieResult> {
+        self.all_dice().filter(|d| d.kept)
+    }
+}
+
+impl DieResult {
+    /// First face rolled for this die, before any explosion
+    pub fn natural(&self) -> u32 {
+        self.faces.first().copied().unwrap_or(0)
+    }
+}
+
+/// Roll recorded in a campaign's roll history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollRecord {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub expression: String,
+    #[serde(with = "seed_string")]
+    pub seed: u64, // Seed of the RNG that produced the result, stored as a string for JS
+    pub result: DiceRoll,
+    pub roller: Option<String>, // Who rolled: "DM", a player or character name
+    pub session_number: Option<u32>,
+    pub label: Option<String>, // What the roll was for, e.g. "Perception check"
+    pub rolled_at: DateTime<Utc>,
+}
+
+/// Request to roll dice
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollDiceRequest {
+    pub expression: String,
+    pub roller: Option<String>,
+    pub session_number: Option<u32>,
+    pub label: Option<String>,
+}
+
+/// Filters for the roll history
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollHistoryFilter {
+    pub roller: Option<String>,
+    pub session_number: Option<u32>,
+    pub expression: Option<String>, // Case-insensitive substring match
+    pub limit: Option<usize>,
+}
+
+/// Outcome of replaying a recorded roll from its seed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollVerification {
+    pub roll_id: Uuid,
+    pub verified: bool, // True if the replay produced exactly the recorded dice
+    pub recorded: DiceRoll,
+    pub replayed: DiceRoll,
+}
+
+impl RollHistoryFilter {
+    /// Check if a record matches every filter that is set
+    pub fn matches(&self, record: &RollRecord) -> bool {
+        if let Some(roller) = &self.roller {
+            let matches_roller = record.roller.as_ref()
+                .map(|r| r.eq_ignore_ascii_case(roller.trim()))
+                .unwrap_or(false);
+            if !matches_roller {
+                return false;
+            }
+        }
+        if let Some(session) = self.session_number {
+            if record.session_number != Some(session) {
+                return false;
+            }
+        }
+        if let Some(expression) = &self.expression {
+            let needle = normalize_expression(expression);
+            if !normalize_expression(&record.expression).contains(&needle) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Normalize a dice expression for comparisons (lowercase, no whitespace)
+pub fn normalize_expression(expression: &str) -> String {
+    expression.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Serialize u64 seeds as strings, since JavaScript numbers can't hold them exactly
+mod seed_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&seed.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Seed {
+            Number(u64),
+            Text(String),
+        }
+
+        match Seed::deserialize(deserializer)? {
+            Seed::Number(seed) => Ok(seed),
+            Seed::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Exact probability distribution of a dice expression
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiceStatistics {
+    pub expression: String,
+    pub mean: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    pub min: i64,
+    pub max: i64,
+    pub pmf: Vec<ValueProbability>, // P(total = value), sorted by value
+    pub at_least: Vec<ValueProbability>, // P(total >= value), sorted by value
+    pub target: Option<i64>,
+    pub target_probability: Option<f64>, // P(total >= target) if a target was given
+}
+
+/// Probability associated with a total
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ValueProbability {
+    pub value: i64,
+    pub probability: f64,
+}
+
+/// Kind of entity stored in a campaign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EntityKind {
+    Character,
+    Npc,
+    Monster,
+    Spell,
+    Item,
+}
+
+/// Where an entity lives, as recorded in the global entity index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityLocation {
+    pub campaign_id: Uuid,
+    pub kind: EntityKind,
+}
+
+/// One of the six abilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Ability {
+    Strength,
+    Dexterity,
+    Constitution,
+    Intelligence,
+    Wisdom,
+    Charisma,
+}
+
+/// Scores of the six abilities, 1 to 30
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbilityScores {
+    pub strength: u8,
+    pub dexterity: u8,
+    pub constitution: u8,
+    pub intelligence: u8,
+    pub wisdom: u8,
+    pub charisma: u8,
+}
+
+/// Skill, with the ability it's based on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Skill {
+    Acrobatics,
+    AnimalHandling,
+    Arcana,
+    Athletics,
+    Deception,
+    History,
+    Insight,
+    Intimidation,
+    Investigation,
+    Medicine,
+    Nature,
+    Perception,
+    Performance,
+    Persuasion,
+    Religion,
+    SleightOfHand,
+    Stealth,
+    Survival,
+}
+
+impl Ability {
+    pub const ALL: [Ability; 6] = [
+        Ability::Strength,
+        Ability::Dexterity,
+        Ability::Constitution,
+        Ability::Intelligence,
+        Ability::Wisdom,
+        Ability::Charisma,
+    ];
+}
+
+impl Default for AbilityScores {
+    fn default() -> Self {
+        Self { strength: 10, dexterity: 10, constitution: 10, intelligence: 10, wisdom: 10, charisma: 10 }
+    }
+}
+
+impl AbilityScores {
+    /// Score of an ability
+    pub fn get(&self, ability: Ability) -> u8 {
+        match ability {
+            Ability::Strength => self.strength,
+            Ability::Dexterity => self.dexterity,
+            Ability::Constitution => self.constitution,
+            Ability::Intelligence => self.intelligence,
+            Ability::Wisdom => self.wisdom,
+            Ability::Charisma => self.charisma,
+        }
+    }
+}
+
+impl Skill {
+    pub const ALL: [Skill; 18] = [
+        Skill::Acrobatics,
+        Skill::AnimalHandling,
+        Skill::Arcana,
+        Skill::Athletics,
+        Skill::Deception,
+        Skill::History,
+        Skill::Insight,
+        Skill::Intimidation,
+        Skill::Investigation,
+        Skill::Medicine,
+        Skill::Nature,
+        Skill::Perception,
+        Skill::Performance,
+        Skill::Persuasion,
+        Skill::Religion,
+        Skill::SleightOfHand,
+        Skill::Stealth,
+        Skill::Survival,
+    ];
+
+    /// Ability the skill is based on
+    pub fn ability(&self) -> Ability {
+        match self {
+            Skill::Athletics => Ability::Strength,
+            Skill::Acrobatics | Skill::SleightOfHand | Skill::Stealth => Ability::Dexterity,
+            Skill::Arcana | Skill::History | Skill::Investigation | Skill::Nature | Skill::Religion => Ability::Intelligence,
+            Skill::AnimalHandling | Skill::Insight | Skill::Medicine | Skill::Perception | Skill::Survival => Ability::Wisdom,
+            Skill::Deception | Skill::Intimidation | Skill::Performance | Skill::Persuasion => Ability::Charisma,
+        }
+    }
+}
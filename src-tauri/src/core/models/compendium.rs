@@ -0,0 +1,101 @@
+use crate::core::models::item::{Item, ItemDetails};
+use crate::core::models::monster::{Monster, StatBlock};
+use crate::core::models::spell::{Spell, SpellDetails};
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Compendium entry a homebrew entity was copied from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSource {
+    pub pack: String, // e.g. "srd-5.1"
+    pub key: String, // Key of the entry in the pack, e.g. "goblin"
+    pub copied_at: DateTime<Utc>,
+}
+
+/// Kind of content found in the compendium
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompendiumKind {
+    Monster,
+    Spell,
+    Item,
+}
+
+/// Where a listed entry lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContentOrigin {
+    Compendium, // Read-only, compiled into the app
+    Homebrew, // Entity of the campaign, editable
+}
+
+/// Content pack compiled into the app
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentPack {
+    pub key: String, // e.g. "srd-5.1"
+    pub name: String,
+    pub license: String,
+    pub attribution: String, // Notice the license requires to be shown
+    pub monsters: usize,
+    pub spells: usize,
+    pub items: usize,
+}
+
+/// Compendium entry or homebrew entity, as listed when browsing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompendiumListing {
+    pub kind: CompendiumKind,
+    pub origin: ContentOrigin,
+    pub key: String, // Key in the pack for compendium entries, ID for homebrew
+    pub name: String,
+    pub summary: String, // e.g. "Small humanoid, CR 1/4", "3rd-level evocation", "Ring, rare"
+    pub source: Option<ContentSource>, // Set for homebrew copied from the compendium
+}
+
+/// Search of the compendium, along with the homebrew of a campaign
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompendiumQuery {
+    pub campaign_id: Option<Uuid>, // Homebrew is listed too if set
+    pub kind: Option<CompendiumKind>,
+    pub text: Option<String>, // Matched against names, case-insensitive
+}
+
+/// Full content of a compendium entry
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "entry", rename_all = "camelCase")]
+pub enum CompendiumEntry {
+    Monster(Box<StatBlock>),
+    Spell(SpellDetails),
+    Item(ItemDetails),
+}
+
+/// Homebrew entity created from a compendium entry
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "entity", rename_all = "camelCase")]
+pub enum CopiedEntity {
+    Monster(Box<Monster>),
+    Spell(Spell),
+    Item(Item),
+}
+
+/// Request to copy a compendium entry into a campaign
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyCompendiumEntryRequest {
+    pub campaign_id: Uuid,
+    pub pack: String, // e.g. "srd-5.1"
+    pub kind: CompendiumKind,
+    pub key: String,
+    pub name: Option<String>, // Rename the copy, e.g. "Goblin Chief"
+}
+
+impl ContentSource {
+    pub fn new(pack: &str, key: &str) -> Self {
+        Self { pack: pack.to_string(), key: key.to_string(), copied_at: Utc::now() }
+    }
+}
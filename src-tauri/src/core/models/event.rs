@@ -0,0 +1,83 @@
+use crate::core::models::history::ChangeOperation;
+use crate::utils::json_diff::ValueDiff;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// What happened to an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeType {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Event sent to the frontend when an entity changes, as `entity_created`,
+/// `entity_updated` or `entity_deleted`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityEvent {
+    pub change_type: ChangeType,
+    pub kind: String, // Collection of the entity, e.g. "characters", "npcs", "relationships"
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub diff: ValueDiff, // From null for created entities, to null for deleted ones
+}
+
+/// Why a campaign was replaced as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReloadReason {
+    Restored, // From a backup
+    Imported, // As a new campaign, from an archive
+    StorageConverted,
+}
+
+/// Event sent to the frontend as `campaign_reloaded` when every file of a
+/// campaign may have changed at once. Its data is loaded again rather than
+/// patched entity by entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignReloadedEvent {
+    pub campaign_id: Uuid,
+    pub reason: ReloadReason,
+}
+
+impl ChangeType {
+    /// Name of the Tauri event
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ChangeType::Created => "entity_created",
+            ChangeType::Updated => "entity_updated",
+            ChangeType::Deleted => "entity_deleted",
+        }
+    }
+}
+
+impl EntityEvent {
+    pub fn new(campaign_id: Uuid, kind: &str, id: Uuid, operation: &ChangeOperation) -> Self {
+        let (change_type, diff) = match operation {
+            ChangeOperation::Create { state } => (
+                ChangeType::Created,
+                ValueDiff::Replace { before: Value::Null, after: state.clone() },
+            ),
+            ChangeOperation::Update { diff } => (ChangeType::Updated, diff.clone()),
+            ChangeOperation::Delete { state } => (
+                ChangeType::Deleted,
+                ValueDiff::Replace { before: state.clone(), after: Value::Null },
+            ),
+        };
+        Self { change_type, kind: kind.to_string(), id, campaign_id, diff }
+    }
+
+    /// Name of the Tauri event
+    pub fn event_name(&self) -> &'static str {
+        self.change_type.event_name()
+    }
+}
+
+impl CampaignReloadedEvent {
+    /// Name of the Tauri event
+    pub const EVENT_NAME: &'static str = "campaign_reloaded";
+}
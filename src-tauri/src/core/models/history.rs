@@ -0,0 +1,101 @@
+use crate::utils::json_diff::{diff, ValueDiff};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// What a change did to an entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChangeOperation {
+    Create { state: Value },
+    Update { diff: ValueDiff }, // Only the changed fields, to keep the history small
+    Delete { state: Value },
+}
+
+/// Change to one entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityChange {
+    pub collection: String, // e.g. "characters", "npcs", "relationships"
+    pub entity_id: Uuid,
+    pub operation: ChangeOperation,
+}
+
+/// User action in the history: undone and redone as a whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub id: Uuid,
+    pub label: String, // e.g. "Delete Character 'Brann'"
+    pub changes: Vec<EntityChange>, // In the order they were made
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// History entry without the changes themselves
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySummary {
+    pub id: Uuid,
+    pub label: String,
+    pub change_count: usize,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Undo and redo stacks of a campaign, most recent first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryList {
+    pub undo: Vec<HistorySummary>,
+    pub redo: Vec<HistorySummary>,
+}
+
+impl EntityChange {
+    /// Change of an entity between two states (None when it doesn't exist).
+    /// Returns None if nothing changed.
+    pub fn between(collection: &str, entity_id: Uuid, before: Option<Value>, after: Option<Value>) -> Option<Self> {
+        let operation = match (before, after) {
+            (None, Some(state)) => ChangeOperation::Create { state },
+            (Some(state), None) => ChangeOperation::Delete { state },
+            (Some(before), Some(after)) => ChangeOperation::Update { diff: diff(&before, &after)? },
+            (None, None) => return None,
+        };
+        Some(Self { collection: collection.to_string(), entity_id, operation })
+    }
+
+    /// Description for the history, e.g. "Update Character 'Brann'".
+    /// `name` is the entity's current name, if it has one.
+    pub fn describe(&self, label: &str, name: Option<&str>) -> String {
+        let action = match self.operation {
+            ChangeOperation::Create { .. } => "Create",
+            ChangeOperation::Update { .. } => "Update",
+            ChangeOperation::Delete { .. } => "Delete",
+        };
+        match name {
+            Some(name) => format!("{} {} '{}'", action, label, name),
+            None => format!("{} {}", action, label),
+        }
+    }
+}
+
+impl ChangeOperation {
+    /// Operation undoing this one
+    pub fn reversed(&self) -> ChangeOperation {
+        match self {
+            ChangeOperation::Create { state } => ChangeOperation::Delete { state: state.clone() },
+            ChangeOperation::Update { diff } => ChangeOperation::Update { diff: diff.reversed() },
+            ChangeOperation::Delete { state } => ChangeOperation::Create { state: state.clone() },
+        }
+    }
+}
+
+impl From<&HistoryEntry> for HistorySummary {
+    fn from(entry: &HistoryEntry) -> Self {
+        Self {
+            id: entry.id,
+            label: entry.label.clone(),
+            change_count: entry.changes.len(),
+            recorded_at: entry.recorded_at,
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use crate::core::models::compendium::ContentSource;
+use crate::core::models::monster::Damage;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use uuid::Uuid;
+
+/// Item of a campaign: mundane equipment or magic item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub details: ItemDetails,
+    #[serde(default)]
+    pub source: Option<ContentSource>, // Compendium entry it was copied from
+    pub notes: String, // DM private notes
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Rules text of an item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDetails {
+    pub name: String,
+    pub category: ItemCategory,
+    #[serde(default)]
+    pub rarity: Option<Rarity>, // None for mundane items
+    #[serde(default)]
+    pub requires_attunement: bool,
+    #[serde(default)]
+    pub attunement_by: Option<String>, // e.g. "by a spellcaster"
+    #[serde(default)]
+    pub cost: Option<Cost>,
+    #[serde(default)]
+    pub weight: Option<f64>, // Pounds
+    #[serde(default)]
+    pub weapon: Option<WeaponStats>,
+    #[serde(default)]
+    pub armor: Option<ArmorStats>,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemCategory {
+    Weapon,
+    Armor,
+    Shield,
+    Ammunition,
+    AdventuringGear,
+    Tool,
+    Mount,
+    Vehicle,
+    TradeGood,
+    Potion,
+    Ring,
+    Rod,
+    Scroll,
+    Staff,
+    Wand,
+    WondrousItem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    VeryRare,
+    Legendary,
+    Artifact,
+}
+
+/// Price of an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cost {
+    pub amount: u32,
+    pub coin: Coin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Coin {
+    Cp,
+    Sp,
+    Ep,
+    Gp,
+    Pp,
+}
+
+/// Weapon statistics (PHB p.149)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponStats {
+    pub martial: bool, // Simple weapon otherwise
+    pub ranged: bool, // Melee weapon otherwise
+    pub damage: Option<Damage>, // None for the net
+    #[serde(default)]
+    pub versatile: Option<String>, // Dice used with two hands
+    #[serde(default)]
+    pub range: Option<u32>, // Normal range in feet, for ranged and thrown weapons
+    #[serde(default)]
+    pub long_range: Option<u32>,
+    #[serde(default)]
+    pub properties: Vec<WeaponProperty>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WeaponProperty {
+    Ammunition,
+    Finesse,
+    Heavy,
+    Light,
+    Loading,
+    Reach,
+    Special,
+    Thrown,
+    TwoHanded,
+    Versatile,
+}
+
+/// Armor statistics (PHB p.145)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmorStats {
+    pub armor_class: u8, // Base AC, or the bonus of a shield
+    pub max_dex_bonus: Option<u8>, // None if the full Dexterity modifier applies
+    pub adds_dex: bool, // False for heavy armor and shields
+    #[serde(default)]
+    pub strength: Option<u8>, // Strength score needed to move at full speed
+    #[serde(default)]
+    pub stealth_disadvantage: bool,
+}
+
+/// Request to create a new item
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateItemRequest {
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub details: ItemDetails,
+    pub notes: Option<String>,
+}
+
+/// Request to update an item: the details are replaced as a whole
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateItemRequest {
+    pub details: Option<ItemDetails>,
+    pub notes: Option<String>,
+}
+
+impl fmt::Display for ItemCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ItemCategory::Weapon => "Weapon",
+            ItemCategory::Armor => "Armor",
+            ItemCategory::Shield => "Shield",
+            ItemCategory::Ammunition => "Ammunition",
+            ItemCategory::AdventuringGear => "Adventuring gear",
+            ItemCategory::Tool => "Tool",
+            ItemCategory::Mount => "Mount",
+            ItemCategory::Vehicle => "Vehicle",
+            ItemCategory::TradeGood => "Trade good",
+            ItemCategory::Potion => "Potion",
+            ItemCategory::Ring => "Ring",
+            ItemCategory::Rod => "Rod",
+            ItemCategory::Scroll => "Scroll",
+            ItemCategory::Staff => "Staff",
+            ItemCategory::Wand => "Wand",
+            ItemCategory::WondrousItem => "Wondrous item",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Rarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::VeryRare => "very rare",
+            Rarity::Legendary => "legendary",
+            Rarity::Artifact => "artifact",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coin = match self.coin {
+            Coin::Cp => "cp",
+            Coin::Sp => "sp",
+            Coin::Ep => "ep",
+            Coin::Gp => "gp",
+            Coin::Pp => "pp",
+        };
+        write!(f, "{} {}", self.amount, coin)
+    }
+}
+
+impl ItemDetails {
+    /// Category and rarity as printed, e.g. "Ring, rare (requires attunement)" or "Weapon, 15 gp"
+    pub fn summary(&self) -> String {
+        match (self.rarity, self.cost) {
+            (Some(rarity), _) => {
+                let attunement = match (self.requires_attunement, &self.attunement_by) {
+                    (true, Some(by)) => format!(" (requires attunement {})", by),
+                    (true, None) => " (requires attunement)".to_string(),
+                    (false, _) => String::new(),
+                };
+                format!("{}, {}{}", self.category, rarity, attunement)
+            }
+            (None, Some(cost)) => format!("{}, {}", self.category, cost),
+            (None, None) => self.category.to_string(),
+        }
+    }
+}
+
+impl Item {
+    /// Create a new item
+    pub fn new(req: CreateItemRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            campaign_id: req.campaign_id,
+            details: req.details,
+            source: None,
+            notes: req.notes.unwrap_or_default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update item data
+    pub fn update(&mut self, req: UpdateItemRequest) {
+        if let Some(details) = req.details { self.details = details; }
+        if let Some(notes) = req.notes { self.notes = notes; }
+
+        self.updated_at = Utc::now();
+    }
+}
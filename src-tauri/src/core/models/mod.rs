@@ -1,12 +1,19 @@
 pub mod campaign;
 pub mod character;
 pub mod npc;
+pub mod relationship;
+pub mod backup;
+pub mod archive;
+pub mod history;
+pub mod event;
 pub mod environment;
 pub mod map;
 pub mod item;
 pub mod inventory;
 pub mod quest;
 pub mod monster;
+pub mod spell;
+pub mod compendium;
 pub mod participant;
 pub mod encounter;
 pub mod combat;
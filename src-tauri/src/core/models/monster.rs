@@ -0,0 +1,551 @@
+use crate::core::models::common::{Ability, AbilityScores, Skill};
+use crate::core::models::compendium::ContentSource;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Monster of a campaign's bestiary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Monster {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub stat_block: StatBlock,
+    #[serde(default)]
+    pub source: Option<ContentSource>, // Compendium entry it was copied from
+    pub notes: String, // DM private notes
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 5e stat block. Fields missing when deserializing take their default,
+/// so a stat block can be built up from a name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StatBlock {
+    pub name: String,
+    pub size: Size,
+    pub creature_type: CreatureType,
+    pub subtypes: Vec<String>, // e.g. "goblinoid", "shapechanger"
+    pub alignment: String, // e.g. "chaotic evil", "unaligned", "any alignment"
+    pub armor_class: ArmorClass,
+    pub hit_points: HitPoints,
+    pub speed: Speed,
+    pub ability_scores: AbilityScores,
+    pub saving_throws: BTreeMap<Ability, i32>, // Total bonus of proficient saves, as printed
+    pub skills: BTreeMap<Skill, i32>, // Total bonus of proficient skills, as printed
+    pub damage_vulnerabilities: Vec<DamageDefense>,
+    pub damage_resistances: Vec<DamageDefense>,
+    pub damage_immunities: Vec<DamageDefense>,
+    pub condition_immunities: Vec<Condition>,
+    pub senses: Senses,
+    pub languages: Vec<String>,
+    pub telepathy: Option<u32>, // Range in feet
+    pub challenge_rating: ChallengeRating,
+    pub traits: Vec<MonsterFeature>,
+    pub actions: Vec<MonsterAction>,
+    pub bonus_actions: Vec<MonsterAction>,
+    pub reactions: Vec<MonsterAction>,
+    pub legendary_actions: Option<LegendaryActions>,
+    pub lair_actions: Option<LairActions>,
+    pub spellcasting: Vec<Spellcasting>, // e.g. "Spellcasting" and "Innate Spellcasting"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Size {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+    Gargantuan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CreatureType {
+    Aberration,
+    Beast,
+    Celestial,
+    Construct,
+    Dragon,
+    Elemental,
+    Fey,
+    Fiend,
+    Giant,
+    #[default]
+    Humanoid,
+    Monstrosity,
+    Ooze,
+    Plant,
+    Undead,
+}
+
+/// Armor class, with what it comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmorClass {
+    pub value: u8,
+    pub source: Option<String>, // e.g. "natural armor", "chain mail, shield"
+}
+
+/// Hit points: the average, and the dice they're rolled with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HitPoints {
+    pub average: u32,
+    pub formula: Option<String>, // Dice expression, e.g. "2d8+2"
+}
+
+/// Speeds in feet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Speed {
+    pub walk: u32,
+    pub burrow: Option<u32>,
+    pub climb: Option<u32>,
+    pub fly: Option<u32>,
+    pub swim: Option<u32>,
+    pub hover: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DamageType {
+    Acid,
+    Bludgeoning,
+    Cold,
+    Fire,
+    Force,
+    Lightning,
+    Necrotic,
+    Piercing,
+    Poison,
+    Psychic,
+    Radiant,
+    Slashing,
+    Thunder,
+}
+
+/// Vulnerability, resistance or immunity to a damage type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageDefense {
+    pub damage_type: DamageType,
+    pub condition: Option<String>, // e.g. "from nonmagical attacks"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Condition {
+    Blinded,
+    Charmed,
+    Deafened,
+    Exhaustion,
+    Frightened,
+    Grappled,
+    Incapacitated,
+    Invisible,
+    Paralyzed,
+    Petrified,
+    Poisoned,
+    Prone,
+    Restrained,
+    Stunned,
+    Unconscious,
+}
+
+/// Special senses, with their range in feet. Passive Perception is derived
+/// from the ability scores and skills.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Senses {
+    pub blindsight: Option<u32>,
+    pub darkvision: Option<u32>,
+    pub tremorsense: Option<u32>,
+    pub truesight: Option<u32>,
+}
+
+/// Challenge rating: 0, 1/8, 1/4, 1/2 or a whole number up to 30.
+/// Serialized as a string such as "1/4" or "5"; numbers are accepted too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ChallengeRating {
+    eighths: u16,
+}
+
+/// Special trait, e.g. "Pack Tactics"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonsterFeature {
+    pub name: String,
+    pub description: String,
+    pub usage: Option<ActionUsage>,
+}
+
+/// Action, bonus action or reaction. The attack, saving throw and damage
+/// are structured so they can be rolled; the description is the full text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonsterAction {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub attack: Option<Attack>,
+    #[serde(default)]
+    pub saving_throw: Option<SavingThrowEffect>,
+    #[serde(default)]
+    pub damage: Vec<Damage>, // Damage on a hit, or on a failed save
+    #[serde(default)]
+    pub usage: Option<ActionUsage>,
+}
+
+/// Attack roll of an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attack {
+    pub kind: AttackKind,
+    pub to_hit: i32,
+    pub reach: Option<u32>, // Melee reach in feet
+    pub range: Option<u32>, // Normal range in feet
+    pub long_range: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AttackKind {
+    MeleeWeapon,
+    RangedWeapon,
+    MeleeOrRangedWeapon,
+    MeleeSpell,
+    RangedSpell,
+}
+
+/// Saving throw forced by an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavingThrowEffect {
+    pub ability: Ability,
+    pub dc: u8,
+    #[serde(default)]
+    pub half_on_success: bool,
+}
+
+/// Damage dealt by an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Damage {
+    pub dice: String, // Dice expression, e.g. "2d6+3"
+    pub damage_type: DamageType,
+}
+
+/// Limit on how often a feature or action can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ActionUsage {
+    Recharge { min: u8 }, // Recharges on a d6 roll of `min` or more
+    PerDay { uses: u8 },
+    RechargeAfterRest,
+}
+
+/// Legendary actions, taken at the end of other creatures' turns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendaryActions {
+    pub per_round: u8,
+    pub description: Option<String>,
+    pub actions: Vec<LegendaryAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendaryAction {
+    #[serde(flatten)]
+    pub action: MonsterAction,
+    pub cost: u8, // Legendary actions spent
+}
+
+/// Lair actions, taken on initiative count 20 (losing ties)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LairActions {
+    pub initiative: u8,
+    pub description: Option<String>,
+    pub actions: Vec<MonsterFeature>,
+}
+
+/// Spellcasting trait
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spellcasting {
+    pub name: String, // e.g. "Spellcasting", "Innate Spellcasting"
+    pub ability: Ability,
+    pub save_dc: Option<u8>,
+    pub attack_bonus: Option<i32>,
+    pub caster_level: Option<u8>,
+    pub description: Option<String>,
+    pub spells: Vec<SpellList>,
+}
+
+/// Spells available with the same frequency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellList {
+    pub frequency: SpellFrequency,
+    pub spells: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SpellFrequency {
+    AtWill,
+    PerDay { uses: u8 },
+    Cantrip,
+    Level { level: u8, slots: u8 },
+}
+
+/// Derived values of a stat block, computed by the rules
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonsterStats {
+    pub ability_modifiers: BTreeMap<Ability, i32>,
+    pub proficiency_bonus: i32,
+    pub saving_throws: BTreeMap<Ability, i32>, // Every save, proficient or not
+    pub skills: BTreeMap<Skill, i32>, // Every skill, proficient or not
+    pub initiative: i32,
+    pub passive_perception: i32,
+    pub experience_points: u32,
+    pub hit_dice_average: Option<u32>, // Average of the hit points formula
+}
+
+/// Request to create a new monster
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMonsterRequest {
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub stat_block: StatBlock,
+    pub notes: Option<String>,
+}
+
+/// Request to update a monster: the stat block is replaced as a whole
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMonsterRequest {
+    pub stat_block: Option<StatBlock>,
+    pub notes: Option<String>,
+}
+
+/// Transformation of a stat block. Templates compose: each one applies to
+/// the result of the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MonsterTemplate {
+    /// Move to another challenge rating, scaling HP, AC, attack bonuses,
+    /// save DCs and damage along the DMG table (DMG p.274)
+    Retarget { challenge_rating: ChallengeRating },
+    /// Tougher and more accurate, with one Legendary Resistance
+    Elite,
+    /// Dies from any damage and deals fixed damage, to field in numbers
+    Minion,
+    /// Risen as undead: poison and necrotic defenses, darkvision
+    UndeadVariant,
+    /// Young creature grown to its adult form: one size larger, stronger,
+    /// seven challenge ratings higher (as a young dragon to an adult one)
+    Adult,
+}
+
+/// Request to scale a monster
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleMonsterRequest {
+    pub monster: Monster,
+    pub templates: Vec<MonsterTemplate>, // Applied in order
+}
+
+/// Value changed by a template
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateChange {
+    pub template: String, // e.g. "Elite", "CR 5"
+    pub field: String, // e.g. "Hit points", "Scimitar attack bonus"
+    pub before: String,
+    pub after: String,
+}
+
+/// New monster made from another one, with what was changed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaledMonster {
+    pub monster: Monster,
+    pub changelog: Vec<TemplateChange>,
+}
+
+impl Default for StatBlock {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            size: Size::default(),
+            creature_type: CreatureType::default(),
+            subtypes: Vec::new(),
+            alignment: "unaligned".to_string(),
+            armor_class: ArmorClass { value: 10, source: None },
+            hit_points: HitPoints { average: 1, formula: None },
+            speed: Speed::default(),
+            ability_scores: AbilityScores::default(),
+            saving_throws: BTreeMap::new(),
+            skills: BTreeMap::new(),
+            damage_vulnerabilities: Vec::new(),
+            damage_resistances: Vec::new(),
+            damage_immunities: Vec::new(),
+            condition_immunities: Vec::new(),
+            senses: Senses::default(),
+            languages: Vec::new(),
+            telepathy: None,
+            challenge_rating: ChallengeRating::default(),
+            traits: Vec::new(),
+            actions: Vec::new(),
+            bonus_actions: Vec::new(),
+            reactions: Vec::new(),
+            legendary_actions: None,
+            lair_actions: None,
+            spellcasting: Vec::new(),
+        }
+    }
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Self { walk: 30, burrow: None, climb: None, fly: None, swim: None, hover: false }
+    }
+}
+
+/// Experience points awarded for a monster of each challenge rating, in the order of `ChallengeRating::all()`
+const EXPERIENCE_POINTS: [u32; 34] = [
+    10, 25, 50, 100, 200, 450, 700, 1_100, 1_800, 2_300, 2_900, 3_900, 5_000, 5_900, 7_200, 8_400, 10_000,
+    11_500, 13_000, 15_000, 18_000, 20_000, 22_000, 25_000, 33_000, 41_000, 50_000, 62_000, 75_000,
+    90_000, 105_000, 120_000, 135_000, 155_000,
+];
+
+impl ChallengeRating {
+    /// Every challenge rating, lowest first
+    pub fn all() -> impl Iterator<Item = ChallengeRating> {
+        [0, 1, 2, 4].into_iter()
+            .chain((1..=30).map(|cr| cr * 8))
+            .map(|eighths| ChallengeRating { eighths })
+    }
+
+    /// Whole challenge rating from 1 to 30
+    pub fn whole(cr: u8) -> Option<Self> {
+        (1..=30).contains(&cr).then_some(ChallengeRating { eighths: cr as u16 * 8 })
+    }
+
+    /// Challenge rating with the exact value, e.g. 0.25 for 1/4
+    pub fn from_value(value: f64) -> Option<Self> {
+        Self::all().find(|cr| cr.value() == value)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.eighths as f64 / 8.0
+    }
+
+    /// Position in `all()`, 0 for CR 0 and 33 for CR 30
+    pub fn index(&self) -> usize {
+        Self::all().position(|cr| cr == *self).unwrap_or(0)
+    }
+
+    /// Challenge rating at a position of `all()`, clamped to the valid range
+    pub fn from_index(index: usize) -> Self {
+        Self::all().nth(index).unwrap_or(ChallengeRating { eighths: 240 })
+    }
+
+    /// Experience points for defeating a monster of this challenge rating
+    pub fn experience_points(&self) -> u32 {
+        EXPERIENCE_POINTS[self.index()]
+    }
+
+    /// Proficiency bonus of a monster: +2 up to CR 4, then one more every 4 CRs
+    pub fn proficiency_bonus(&self) -> i32 {
+        let whole = self.value().ceil() as i32;
+        2 + (whole - 1).max(0) / 4
+    }
+}
+
+impl fmt::Display for ChallengeRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.eighths {
+            1 => write!(f, "1/8"),
+            2 => write!(f, "1/4"),
+            4 => write!(f, "1/2"),
+            eighths => write!(f, "{}", eighths / 8),
+        }
+    }
+}
+
+impl FromStr for ChallengeRating {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = match s.trim().split_once('/') {
+            Some((numerator, denominator)) => {
+                let numerator: f64 = numerator.trim().parse().map_err(|_| format!("Invalid challenge rating '{}'", s))?;
+                let denominator: f64 = denominator.trim().parse().map_err(|_| format!("Invalid challenge rating '{}'", s))?;
+                numerator / denominator
+            }
+            None => s.trim().parse().map_err(|_| format!("Invalid challenge rating '{}'", s))?,
+        };
+        Self::from_value(value).ok_or_else(|| format!("Invalid challenge rating '{}'", s))
+    }
+}
+
+impl Serialize for ChallengeRating {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChallengeRating {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Number(f64),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            Raw::Number(value) => Self::from_value(value)
+                .ok_or_else(|| serde::de::Error::custom(format!("Invalid challenge rating {}", value))),
+        }
+    }
+}
+
+impl Monster {
+    /// Create a new monster
+    pub fn new(req: CreateMonsterRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            campaign_id: req.campaign_id,
+            stat_block: req.stat_block,
+            source: None,
+            notes: req.notes.unwrap_or_default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update monster data
+    pub fn update(&mut self, req: UpdateMonsterRequest) {
+        if let Some(stat_block) = req.stat_block { self.stat_block = stat_block; }
+        if let Some(notes) = req.notes { self.notes = notes; }
+
+        self.updated_at = Utc::now();
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Non-player character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Npc {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub name: String,
+    pub race: String,
+    pub role: String, // e.g. "Innkeeper", "Cult leader"
+    pub location_id: Option<Uuid>, // Link to the environment where the NPC is usually found
+    pub faction: Option<String>,
+    pub attitude: NpcAttitude, // Attitude towards the party
+    pub voice: String, // How to play the NPC: accent, tone, catchphrases
+    pub mannerisms: String,
+    pub secrets: Vec<String>, // DM only
+    pub status: NpcStatus,
+    pub portrait_path: Option<String>,
+    pub notes: String, // DM private notes
+    pub interactions: Vec<NpcInteraction>, // Append-only log, oldest first
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Attitude of an NPC towards the party (DMG p.244)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NpcAttitude {
+    Friendly,
+    #[default]
+    Indifferent,
+    Hostile,
+}
+
+/// Whether the NPC is still around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NpcStatus {
+    #[default]
+    Alive,
+    Dead,
+    Missing,
+    Unknown,
+}
+
+/// A meeting between the NPC and some characters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NpcInteraction {
+    pub id: Uuid,
+    pub character_ids: Vec<Uuid>, // Characters who met the NPC
+    pub session_number: Option<u32>,
+    pub summary: String,
+    pub attitude_after: Option<NpcAttitude>, // Set if the interaction changed the NPC's attitude
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Request to create a new NPC
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNpcRequest {
+    pub campaign_id: Uuid,
+    pub name: String,
+    pub race: Option<String>,
+    pub role: Option<String>,
+    pub location_id: Option<Uuid>,
+    pub faction: Option<String>,
+    pub attitude: Option<NpcAttitude>,
+    pub voice: Option<String>,
+    pub mannerisms: Option<String>,
+    pub secrets: Option<Vec<String>>,
+    pub portrait_path: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Request to update NPC
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNpcRequest {
+    pub name: Option<String>,
+    pub race: Option<String>,
+    pub role: Option<String>,
+    pub location_id: Option<Uuid>,
+    pub faction: Option<String>,
+    pub attitude: Option<NpcAttitude>,
+    pub voice: Option<String>,
+    pub mannerisms: Option<String>,
+    pub secrets: Option<Vec<String>>,
+    pub status: Option<NpcStatus>,
+    pub portrait_path: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Request to log an interaction with an NPC
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInteractionRequest {
+    pub character_ids: Vec<Uuid>,
+    pub session_number: Option<u32>,
+    pub summary: String,
+    pub attitude_after: Option<NpcAttitude>,
+}
+
+/// Character relationship left pointing to a deleted NPC
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanglingRelationship {
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub relationship_id: Uuid,
+}
+
+/// Outcome of deleting an NPC
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NpcDeletionReport {
+    pub npc_id: Uuid,
+    pub deleted: bool,
+    pub relationships: Vec<DanglingRelationship>, // Relationships that pointed to the NPC
+    pub relationships_removed: bool, // Whether they were cleaned up or left dangling
+}
+
+impl Npc {
+    /// Create a new NPC
+    pub fn new(req: CreateNpcRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            campaign_id: req.campaign_id,
+            name: req.name,
+            race: req.race.unwrap_or_default(),
+            role: req.role.unwrap_or_default(),
+            location_id: req.location_id,
+            faction: req.faction.filter(|f| !f.trim().is_empty()),
+            attitude: req.attitude.unwrap_or_default(),
+            voice: req.voice.unwrap_or_default(),
+            mannerisms: req.mannerisms.unwrap_or_default(),
+            secrets: req.secrets.unwrap_or_default(),
+            status: NpcStatus::Alive,
+            portrait_path: req.portrait_path,
+            notes: req.notes.unwrap_or_default(),
+            interactions: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update NPC data (the interaction log is only appended to)
+    pub fn update(&mut self, req: UpdateNpcRequest) {
+        if let Some(name) = req.name { self.name = name; }
+        if let Some(race) = req.race { self.race = race; }
+        if let Some(role) = req.role { self.role = role; }
+        if let Some(location_id) = req.location_id { self.location_id = Some(location_id); }
+        if let Some(faction) = req.faction {
+            self.faction = Some(faction).filter(|f| !f.trim().is_empty());
+        }
+        if let Some(attitude) = req.attitude { self.attitude = attitude; }
+        if let Some(voice) = req.voice { self.voice = voice; }
+        if let Some(mannerisms) = req.mannerisms { self.mannerisms = mannerisms; }
+        if let Some(secrets) = req.secrets { self.secrets = secrets; }
+        if let Some(status) = req.status { self.status = status; }
+        if let Some(portrait_path) = req.portrait_path { self.portrait_path = Some(portrait_path); }
+        if let Some(notes) = req.notes { self.notes = notes; }
+
+        self.updated_at = Utc::now();
+    }
+
+    /// Append an interaction to the log
+    pub fn log_interaction(&mut self, req: LogInteractionRequest) -> NpcInteraction {
+        let interaction = NpcInteraction {
+            id: Uuid::new_v4(),
+            character_ids: req.character_ids,
+            session_number: req.session_number,
+            summary: req.summary,
+            attitude_after: req.attitude_after,
+            occurred_at: Utc::now(),
+        };
+
+        if let Some(attitude) = interaction.attitude_after {
+            self.attitude = attitude;
+        }
+        self.interactions.push(interaction.clone());
+        self.updated_at = Utc::now();
+
+        interaction
+    }
+
+    /// Get interactions involving a character
+    pub fn interactions_with(&self, character_id: &Uuid) -> Vec<&NpcInteraction> {
+        self.interactions.iter()
+            .filter(|i| i.character_ids.contains(character_id))
+            .collect()
+    }
+
+    /// Check if the NPC is alive
+    pub fn is_alive(&self) -> bool {
+        self.status == NpcStatus::Alive
+    }
+}
@@ -0,0 +1,133 @@
+use crate::core::models::character::RelationshipType;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Node of the relationship graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "camelCase")]
+pub enum GraphNode {
+    Character(Uuid),
+    Npc(Uuid),
+    Faction(String), // Factions are identified by name, as in `Npc.faction`
+}
+
+/// Typed, directed relationship between two nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipEdge {
+    pub id: Uuid,
+    pub from: GraphNode,
+    pub to: GraphNode,
+    pub relationship_type: RelationshipType,
+    pub mutual: bool, // The relationship holds in both directions
+    pub notes: String,
+    pub last_interaction: Option<DateTime<Utc>>,
+    pub from_character: bool, // Mirrored from `PlayerCharacter.relationships`, edited from the character
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Which edges to follow from a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EdgeDirection {
+    Outgoing,
+    Incoming,
+    #[default]
+    Both,
+}
+
+/// Request to create a relationship edge
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateEdgeRequest {
+    pub from: GraphNode,
+    pub to: GraphNode,
+    pub relationship_type: RelationshipType,
+    pub mutual: Option<bool>,
+    pub notes: Option<String>,
+}
+
+/// Request to update a relationship edge
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateEdgeRequest {
+    pub relationship_type: Option<RelationshipType>,
+    pub mutual: Option<bool>,
+    pub notes: Option<String>,
+    pub last_interaction: Option<DateTime<Utc>>,
+}
+
+/// Nodes and edges around a node
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNeighborhood {
+    pub center: GraphNode,
+    pub nodes: Vec<GraphNode>, // Reached nodes, excluding the center
+    pub edges: Vec<RelationshipEdge>,
+}
+
+/// Node with a display label, for visualization
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabeledNode {
+    pub node: GraphNode,
+    pub label: String,
+}
+
+/// Whole graph in a visualization-friendly shape
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExport {
+    pub campaign_id: Uuid,
+    pub nodes: Vec<LabeledNode>,
+    pub edges: Vec<RelationshipEdge>,
+}
+
+impl GraphNode {
+    /// Check if the node is one of the edge's ends
+    pub fn is_end_of(&self, edge: &RelationshipEdge) -> bool {
+        edge.from == *self || edge.to == *self
+    }
+}
+
+impl RelationshipEdge {
+    /// Create a new edge
+    pub fn new(req: CreateEdgeRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            from: req.from,
+            to: req.to,
+            relationship_type: req.relationship_type,
+            mutual: req.mutual.unwrap_or(false),
+            notes: req.notes.unwrap_or_default(),
+            last_interaction: None,
+            from_character: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update edge data
+    pub fn update(&mut self, req: UpdateEdgeRequest) {
+        if let Some(relationship_type) = req.relationship_type { self.relationship_type = relationship_type; }
+        if let Some(mutual) = req.mutual { self.mutual = mutual; }
+        if let Some(notes) = req.notes { self.notes = notes; }
+        if let Some(last_interaction) = req.last_interaction { self.last_interaction = Some(last_interaction); }
+
+        self.updated_at = Utc::now();
+    }
+
+    /// Get the node at the other end of the edge
+    pub fn other_end(&self, node: &GraphNode) -> Option<&GraphNode> {
+        if self.from == *node {
+            Some(&self.to)
+        } else if self.to == *node {
+            Some(&self.from)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::models::compendium::ContentSource;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use uuid::Uuid;
+
+/// Spell of a campaign, homebrew or copied from the compendium
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spell {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub details: SpellDetails,
+    #[serde(default)]
+    pub source: Option<ContentSource>, // Compendium entry it was copied from
+    pub notes: String, // DM private notes
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Rules text of a spell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellDetails {
+    pub name: String,
+    pub level: u8, // 0 for cantrips
+    pub school: SpellSchool,
+    pub casting_time: String, // e.g. "1 action", "1 reaction, which you take when..."
+    pub range: String, // e.g. "Self (15-foot cone)", "120 feet"
+    pub components: SpellComponents,
+    pub duration: String, // e.g. "Instantaneous", "Up to 1 minute"
+    #[serde(default)]
+    pub concentration: bool,
+    #[serde(default)]
+    pub ritual: bool,
+    pub description: String,
+    #[serde(default)]
+    pub higher_levels: Option<String>, // "At Higher Levels" text
+    #[serde(default)]
+    pub classes: Vec<String>, // Classes with the spell on their list
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpellSchool {
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellComponents {
+    #[serde(default)]
+    pub verbal: bool,
+    #[serde(default)]
+    pub somatic: bool,
+    #[serde(default)]
+    pub material: Option<String>, // Materials, if the spell needs any
+}
+
+/// Request to create a new spell
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSpellRequest {
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub details: SpellDetails,
+    pub notes: Option<String>,
+}
+
+/// Request to update a spell: the details are replaced as a whole
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSpellRequest {
+    pub details: Option<SpellDetails>,
+    pub notes: Option<String>,
+}
+
+impl fmt::Display for SpellSchool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SpellSchool::Abjuration => "abjuration",
+            SpellSchool::Conjuration => "conjuration",
+            SpellSchool::Divination => "divination",
+            SpellSchool::Enchantment => "enchantment",
+            SpellSchool::Evocation => "evocation",
+            SpellSchool::Illusion => "illusion",
+            SpellSchool::Necromancy => "necromancy",
+            SpellSchool::Transmutation => "transmutation",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl SpellDetails {
+    /// Level and school as printed, e.g. "Evocation cantrip" or "3rd-level evocation"
+    pub fn level_and_school(&self) -> String {
+        let ordinal = match self.level {
+            0 => {
+                let school = self.school.to_string();
+                return format!("{}{} cantrip", school[..1].to_uppercase(), &school[1..]);
+            }
+            1 => "1st".to_string(),
+            2 => "2nd".to_string(),
+            3 => "3rd".to_string(),
+            level => format!("{}th", level),
+        };
+        let ritual = if self.ritual { " (ritual)" } else { "" };
+        format!("{}-level {}{}", ordinal, self.school, ritual)
+    }
+}
+
+impl Spell {
+    /// Create a new spell
+    pub fn new(req: CreateSpellRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            campaign_id: req.campaign_id,
+            details: req.details,
+            source: None,
+            notes: req.notes.unwrap_or_default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Update spell data
+    pub fn update(&mut self, req: UpdateSpellRequest) {
+        if let Some(details) = req.details { self.details = details; }
+        if let Some(notes) = req.notes { self.notes = notes; }
+
+        self.updated_at = Utc::now();
+    }
+}
@@ -0,0 +1,111 @@
+//! Ability modifiers and the values derived from them (5e rules)
+
+use crate::core::models::common::{Ability, AbilityScores, Skill};
+use crate::core::models::monster::{MonsterStats, StatBlock};
+use crate::core::rules::dice_stats;
+use crate::utils::error::DmResult;
+
+/// Modifier of an ability score: -5 for 1, +0 for 10-11, +10 for 30
+pub fn modifier(score: u8) -> i32 {
+    (score as i32 - 10).div_euclid(2)
+}
+
+/// Modifier of one of the abilities
+pub fn ability_modifier(scores: &AbilityScores, ability: Ability) -> i32 {
+    modifier(scores.get(ability))
+}
+
+/// Passive score of a check with a given bonus (PHB p.175)
+pub fn passive_score(bonus: i32) -> i32 {
+    10 + bonus
+}
+
+/// Average of a hit points formula, rounded down as in stat blocks
+pub fn hit_dice_average(formula: &str) -> DmResult<u32> {
+    let mean = dice_stats::distribution(formula)?.mean();
+    // The mean is summed from probabilities: allow for rounding errors before rounding down
+    Ok((mean + 1e-6).floor().max(0.0) as u32)
+}
+
+/// Bonus of a saving throw: the printed one if proficient, the ability modifier otherwise
+pub fn saving_throw_bonus(stat_block: &StatBlock, ability: Ability) -> i32 {
+    stat_block.saving_throws.get(&ability).copied()
+        .unwrap_or_else(|| ability_modifier(&stat_block.ability_scores, ability))
+}
+
+/// Bonus of a skill: the printed one if proficient, the ability modifier otherwise
+pub fn skill_bonus(stat_block: &StatBlock, skill: Skill) -> i32 {
+    stat_block.skills.get(&skill).copied()
+        .unwrap_or_else(|| ability_modifier(&stat_block.ability_scores, skill.ability()))
+}
+
+/// Values derived from a stat block
+pub fn monster_stats(stat_block: &StatBlock) -> MonsterStats {
+    let scores = &stat_block.ability_scores;
+    MonsterStats {
+        ability_modifiers: Ability::ALL.iter().map(|&a| (a, ability_modifier(scores, a))).collect(),
+        proficiency_bonus: stat_block.challenge_rating.proficiency_bonus(),
+        saving_throws: Ability::ALL.iter().map(|&a| (a, saving_throw_bonus(stat_block, a))).collect(),
+        skills: Skill::ALL.iter().map(|&s| (s, skill_bonus(stat_block, s))).collect(),
+        initiative: ability_modifier(scores, Ability::Dexterity),
+        passive_perception: passive_score(skill_bonus(stat_block, Skill::Perception)),
+        experience_points: stat_block.challenge_rating.experience_points(),
+        hit_dice_average: stat_block.hit_points.formula.as_deref()
+            .and_then(|formula| hit_dice_average(formula).ok()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::monster::{ChallengeRating, HitPoints};
+
+    #[test]
+    fn test_modifiers() {
+        assert_eq!(modifier(1), -5);
+        assert_eq!(modifier(8), -1);
+        assert_eq!(modifier(9), -1);
+        assert_eq!(modifier(10), 0);
+        assert_eq!(modifier(11), 0);
+        assert_eq!(modifier(15), 2);
+        assert_eq!(modifier(30), 10);
+    }
+
+    #[test]
+    fn test_hit_dice_average() {
+        assert_eq!(hit_dice_average("2d8+2").unwrap(), 11);
+        assert_eq!(hit_dice_average("2d6").unwrap(), 7);
+        assert_eq!(hit_dice_average("18d12+90").unwrap(), 207);
+        assert!(hit_dice_average("2d").is_err());
+    }
+
+    #[test]
+    fn test_monster_stats() {
+        // Goblin: Stealth +6, Dex 14, Wis 8
+        let mut goblin = StatBlock {
+            name: "Goblin".to_string(),
+            hit_points: HitPoints { average: 7, formula: Some("2d6".to_string()) },
+            challenge_rating: "1/4".parse::<ChallengeRating>().unwrap(),
+            ..StatBlock::default()
+        };
+        goblin.ability_scores.strength = 8;
+        goblin.ability_scores.dexterity = 14;
+        goblin.ability_scores.wisdom = 8;
+        goblin.skills.insert(Skill::Stealth, 6);
+
+        let stats = monster_stats(&goblin);
+        assert_eq!(stats.ability_modifiers[&Ability::Strength], -1);
+        assert_eq!(stats.initiative, 2);
+        assert_eq!(stats.skills[&Skill::Stealth], 6);
+        assert_eq!(stats.skills[&Skill::Acrobatics], 2);
+        assert_eq!(stats.saving_throws[&Ability::Dexterity], 2);
+        assert_eq!(stats.passive_perception, 9);
+        assert_eq!(stats.proficiency_bonus, 2);
+        assert_eq!(stats.experience_points, 50);
+        assert_eq!(stats.hit_dice_average, Some(7));
+
+        // A proficient Perception counts towards the passive score
+        goblin.skills.insert(Skill::Perception, 3);
+        assert_eq!(monster_stats(&goblin).passive_perception, 13);
+    }
+}
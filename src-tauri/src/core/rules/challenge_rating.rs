@@ -0,0 +1,654 @@
+//! Challenge rating tables and calculator (DMG p.273-281)
+//!
+//! The calculator follows the DMG method for creating a monster:
+//! - defensive CR from the effective hit points (raised by resistances,
+//!   immunities and Legendary Resistance), adjusted by the effective armor
+//!   class (raised by saving throw proficiencies, Magic Resistance and flying
+//!   with ranged attacks)
+//! - offensive CR from the average damage per round over the first three
+//!   rounds, adjusted by the attack bonus or save DC of the main attack
+//! - the suggested CR is the average of the two
+
+use crate::core::models::monster::{
+    ActionUsage, AttackKind, ChallengeRating, DamageDefense, DamageType, MonsterAction, StatBlock,
+};
+use crate::core::rules::dice_stats;
+use crate::utils::error::DmResult;
+use serde::Serialize;
+
+/// Expected statistics of a monster of a challenge rating (DMG p.274)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrStatistics {
+    pub armor_class: i32,
+    pub min_hit_points: u32,
+    pub max_hit_points: u32,
+    pub attack_bonus: i32,
+    pub min_damage: u32, // Damage per round
+    pub max_damage: u32,
+    pub save_dc: i32,
+}
+
+/// Rows of the table, in the order of `ChallengeRating::all()`: AC, HP, attack bonus, damage per round, save DC
+const STATISTICS: [(i32, u32, u32, i32, u32, u32, i32); 34] = [
+    (13, 1, 6, 3, 0, 1, 13),
+    (13, 7, 35, 3, 2, 3, 13),
+    (13, 36, 49, 3, 4, 5, 13),
+    (13, 50, 70, 3, 6, 8, 13),
+    (13, 71, 85, 3, 9, 14, 13),
+    (13, 86, 100, 3, 15, 20, 13),
+    (13, 101, 115, 4, 21, 26, 13),
+    (14, 116, 130, 5, 27, 32, 14),
+    (15, 131, 145, 6, 33, 38, 15),
+    (15, 146, 160, 6, 39, 44, 15),
+    (15, 161, 175, 6, 45, 50, 15),
+    (16, 176, 190, 7, 51, 56, 16),
+    (16, 191, 205, 7, 57, 62, 16),
+    (17, 206, 220, 7, 63, 68, 16),
+    (17, 221, 235, 8, 69, 74, 17),
+    (17, 236, 250, 8, 75, 80, 17),
+    (18, 251, 265, 8, 81, 86, 18),
+    (18, 266, 280, 8, 87, 92, 18),
+    (18, 281, 295, 8, 93, 98, 18),
+    (18, 296, 310, 9, 99, 104, 18),
+    (19, 311, 325, 10, 105, 110, 19),
+    (19, 326, 340, 10, 111, 116, 19),
+    (19, 341, 355, 10, 117, 122, 19),
+    (19, 356, 400, 10, 123, 140, 19),
+    (19, 401, 445, 11, 141, 158, 20),
+    (19, 446, 490, 11, 159, 176, 20),
+    (19, 491, 535, 11, 177, 194, 20),
+    (19, 536, 580, 12, 195, 212, 21),
+    (19, 581, 625, 12, 213, 230, 21),
+    (19, 626, 670, 12, 231, 248, 21),
+    (19, 671, 715, 13, 249, 266, 22),
+    (19, 716, 760, 13, 267, 284, 22),
+    (19, 761, 805, 13, 285, 302, 22),
+    (19, 806, 850, 14, 303, 320, 23),
+];
+
+/// Side of the calculation a factor belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CrSide {
+    Defensive,
+    Offensive,
+}
+
+/// One step of the calculation, explained
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrFactor {
+    pub side: CrSide,
+    pub name: String, // e.g. "Damage resistances"
+    pub detail: String, // e.g. "Effective HP x1.5 at expected CR 7"
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefensiveRating {
+    pub hit_points: u32,
+    pub effective_hit_points: u32,
+    pub armor_class: i32,
+    pub effective_armor_class: i32,
+    pub hit_points_rating: ChallengeRating, // Before adjusting for AC
+    pub challenge_rating: ChallengeRating,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OffensiveRating {
+    pub damage_per_round: Vec<f64>, // First three rounds
+    pub average_damage: f64,
+    pub attack_bonus: Option<i32>, // Set if the main attack uses an attack roll
+    pub save_dc: Option<i32>, // Set if it forces a saving throw
+    pub damage_rating: ChallengeRating, // Before adjusting for the attack bonus or DC
+    pub challenge_rating: ChallengeRating,
+}
+
+/// Suggested challenge rating of a stat block, with the breakdown
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrCalculation {
+    pub challenge_rating: ChallengeRating,
+    pub experience_points: u32,
+    pub declared: ChallengeRating,
+    pub matches_declared: bool,
+    pub defensive: DefensiveRating,
+    pub offensive: OffensiveRating,
+    pub factors: Vec<CrFactor>,
+}
+
+/// Way of dealing damage in a round
+struct DamageOption {
+    name: String,
+    damage: f64,
+    uses: Option<u32>, // None if it can be used every round
+    attack_bonus: Option<i32>,
+    save_dc: Option<i32>,
+}
+
+/// Expected statistics of a monster of a challenge rating
+pub fn statistics(cr: ChallengeRating) -> CrStatistics {
+    let (armor_class, min_hit_points, max_hit_points, attack_bonus, min_damage, max_damage, save_dc) = STATISTICS[cr.index()];
+    CrStatistics { armor_class, min_hit_points, max_hit_points, attack_bonus, min_damage, max_damage, save_dc }
+}
+
+/// Highest challenge rating whose hit points range starts at or below `hit_points`
+fn rating_for_hit_points(hit_points: u32) -> ChallengeRating {
+    ChallengeRating::all()
+        .take_while(|cr| statistics(*cr).min_hit_points <= hit_points.max(1))
+        .last()
+        .unwrap_or_default()
+}
+
+/// Highest challenge rating whose damage range starts at or below `damage`
+fn rating_for_damage(damage: f64) -> ChallengeRating {
+    ChallengeRating::all()
+        .take_while(|cr| statistics(*cr).min_damage as f64 <= damage.round())
+        .last()
+        .unwrap_or_default()
+}
+
+/// Move a challenge rating up or down the table
+fn shift(cr: ChallengeRating, steps: i32) -> ChallengeRating {
+    ChallengeRating::from_index((cr.index() as i32 + steps).max(0) as usize)
+}
+
+/// Average of a damage expression, rounded to hundredths to hide float noise
+fn average_damage(dice: &str) -> DmResult<f64> {
+    let mean = dice_stats::distribution(dice)?.mean().max(0.0);
+    Ok((mean * 100.0).round() / 100.0)
+}
+
+/// Whether damage defenses are broad enough to count: several types, or all weapon damage
+fn broad_defenses(defenses: &[&DamageDefense]) -> bool {
+    let has = |damage_type| defenses.iter().any(|d| d.damage_type == damage_type);
+    defenses.len() >= 3 || (has(DamageType::Bludgeoning) && has(DamageType::Piercing) && has(DamageType::Slashing))
+}
+
+/// Effective hit points multiplier for resistances and immunities (DMG p.277)
+fn defense_multiplier(expected: ChallengeRating, immunity: bool) -> f64 {
+    let cr = expected.value();
+    match (cr, immunity) {
+        (cr, false) if cr <= 4.0 => 2.0,
+        (cr, false) if cr <= 10.0 => 1.5,
+        (cr, false) if cr <= 16.0 => 1.25,
+        (_, false) => 1.0,
+        (cr, true) if cr <= 10.0 => 2.0,
+        (cr, true) if cr <= 16.0 => 1.5,
+        (_, true) => 1.25,
+    }
+}
+
+/// Number of uses per day written in a feature name, e.g. "Legendary Resistance (3/Day)"
+fn uses_in_name(name: &str) -> Option<u32> {
+    let (_, rest) = name.split_once('(')?;
+    let (uses, _) = rest.split_once('/')?;
+    uses.trim().parse().ok()
+}
+
+fn defensive_rating(stat_block: &StatBlock, factors: &mut Vec<CrFactor>) -> DefensiveRating {
+    let expected = stat_block.challenge_rating;
+    let hit_points = stat_block.hit_points.average;
+    let mut effective_hit_points = hit_points as f64;
+    let mut factor = |name: &str, detail: String| factors.push(CrFactor { side: CrSide::Defensive, name: name.to_string(), detail });
+
+    let immunities: Vec<&DamageDefense> = stat_block.damage_immunities.iter().collect();
+    let defenses: Vec<&DamageDefense> = immunities.iter().copied().chain(&stat_block.damage_resistances).collect();
+    if broad_defenses(&immunities) {
+        let multiplier = defense_multiplier(expected, true);
+        effective_hit_points *= multiplier;
+        factor("Damage immunities", format!("Effective HP x{} at expected CR {}", multiplier, expected));
+    } else if broad_defenses(&defenses) {
+        let multiplier = defense_multiplier(expected, false);
+        effective_hit_points *= multiplier;
+        factor("Damage resistances", format!("Effective HP x{} at expected CR {}", multiplier, expected));
+    } else if !defenses.is_empty() {
+        factor("Damage resistances", "Too few damage types to change the effective HP".to_string());
+    }
+    if !stat_block.damage_vulnerabilities.is_empty() {
+        factor("Damage vulnerabilities", "Not counted by the DMG method".to_string());
+    }
+
+    let legendary_resistance = stat_block.traits.iter()
+        .find(|feature| feature.name.to_lowercase().starts_with("legendary resistance"));
+    if let Some(feature) = legendary_resistance {
+        let uses = match feature.usage {
+            Some(ActionUsage::PerDay { uses }) => uses as u32,
+            _ => uses_in_name(&feature.name).unwrap_or(1),
+        };
+        let per_use = match expected.value() {
+            cr if cr <= 4.0 => 10,
+            cr if cr <= 10.0 => 20,
+            _ => 30,
+        };
+        effective_hit_points += (uses * per_use) as f64;
+        factor("Legendary Resistance", format!("Effective HP +{} ({} uses x {})", uses * per_use, uses, per_use));
+    }
+
+    let effective_hit_points = effective_hit_points.round() as u32;
+    let hit_points_rating = rating_for_hit_points(effective_hit_points);
+    factor("Hit points", format!(
+        "{} effective HP is in the range of CR {}", effective_hit_points, hit_points_rating
+    ));
+
+    let armor_class = stat_block.armor_class.value as i32;
+    let mut effective_armor_class = armor_class;
+    let saves = stat_block.saving_throws.len();
+    if saves >= 3 {
+        let bonus = if saves >= 5 { 4 } else { 2 };
+        effective_armor_class += bonus;
+        factor("Saving throw proficiencies", format!("Effective AC +{} for {} proficient saves", bonus, saves));
+    }
+    if stat_block.traits.iter().any(|feature| feature.name.eq_ignore_ascii_case("magic resistance")) {
+        effective_armor_class += 2;
+        factor("Magic Resistance", "Effective AC +2".to_string());
+    }
+    let ranged = stat_block.actions.iter().any(|action| !action.damage.is_empty() && matches!(
+        action.attack.as_ref().map(|attack| attack.kind),
+        Some(AttackKind::RangedWeapon | AttackKind::RangedSpell | AttackKind::MeleeOrRangedWeapon)
+    ));
+    if stat_block.speed.fly.is_some() && ranged && expected.value() <= 10.0 {
+        effective_armor_class += 2;
+        factor("Flying", "Effective AC +2 for flying and dealing damage at range".to_string());
+    }
+
+    let expected_armor_class = statistics(hit_points_rating).armor_class;
+    let steps = (effective_armor_class - expected_armor_class) / 2;
+    let challenge_rating = shift(hit_points_rating, steps);
+    factor("Armor class", format!(
+        "Effective AC {} against {} expected at CR {}: {}",
+        effective_armor_class, expected_armor_class, hit_points_rating, describe_steps(steps)
+    ));
+
+    DefensiveRating {
+        hit_points,
+        effective_hit_points,
+        armor_class,
+        effective_armor_class,
+        hit_points_rating,
+        challenge_rating,
+    }
+}
+
+/// Number of times a multiattack uses an action, if it mentions it:
+/// the number word just before the action's name, e.g. "two with its claws"
+fn multiattack_count(description: &str, action_name: &str) -> Option<u32> {
+    let name = action_name.to_lowercase();
+    let stem = name.trim_end_matches('s');
+    if stem.is_empty() {
+        return None;
+    }
+    let position = description.find(stem)?;
+    let count = description[..position].split_whitespace().rev().take(4)
+        .find_map(|word| match word.trim_matches(|c: char| !c.is_alphanumeric()) {
+            "one" | "once" => Some(1),
+            "two" | "twice" => Some(2),
+            "three" => Some(3),
+            "four" => Some(4),
+            "five" => Some(5),
+            "six" => Some(6),
+            word => word.parse().ok(),
+        });
+    Some(count.unwrap_or(1))
+}
+
+/// Average damage of an action, counting two targets for area effects
+fn action_damage(action: &MonsterAction) -> DmResult<f64> {
+    let mut damage = 0.0;
+    for roll in &action.damage {
+        damage += average_damage(&roll.dice)?;
+    }
+    let description = action.description.to_lowercase();
+    let area = action.attack.is_none() && action.saving_throw.is_some()
+        && ["cone", "line", "sphere", "radius", "cube", "each creature"].iter().any(|word| description.contains(word));
+    Ok(if area { damage * 2.0 } else { damage })
+}
+
+fn uses_of(action: &MonsterAction) -> Option<u32> {
+    match action.usage {
+        None => None,
+        Some(ActionUsage::PerDay { uses }) => Some(uses as u32),
+        // Recharging actions are assumed to be used once in three rounds
+        Some(ActionUsage::Recharge { .. } | ActionUsage::RechargeAfterRest) => Some(1),
+    }
+}
+
+/// Ways of dealing damage with a list of actions, multiattack included
+fn damage_options(actions: &[MonsterAction], factors: &mut Vec<CrFactor>) -> DmResult<Vec<DamageOption>> {
+    let mut options = Vec::new();
+    for action in actions.iter().filter(|action| !action.damage.is_empty()) {
+        options.push(DamageOption {
+            name: action.name.clone(),
+            damage: action_damage(action)?,
+            uses: uses_of(action),
+            attack_bonus: action.attack.as_ref().map(|attack| attack.to_hit),
+            save_dc: action.saving_throw.as_ref().filter(|_| action.attack.is_none()).map(|save| save.dc as i32),
+        });
+    }
+
+    let multiattack = actions.iter().find(|action| action.name.eq_ignore_ascii_case("multiattack"));
+    if let Some(multiattack) = multiattack {
+        let description = multiattack.description.to_lowercase();
+        let mut damage = 0.0;
+        let mut parts = Vec::new();
+        let mut attack_bonus = None;
+        let mut save_dc = None;
+        for option in options.iter().filter(|option| option.uses.is_none()) {
+            if let Some(count) = multiattack_count(&description, &option.name) {
+                damage += option.damage * count as f64;
+                parts.push(format!("{}x {}", count, option.name));
+                attack_bonus = attack_bonus.max(option.attack_bonus);
+                save_dc = save_dc.max(option.save_dc);
+            }
+        }
+        if parts.is_empty() {
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: "Multiattack".to_string(),
+                detail: "No action it uses was recognized, counted as a single attack".to_string(),
+            });
+        } else {
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: "Multiattack".to_string(),
+                detail: format!("{}: {:.1} damage", parts.join(", "), damage),
+            });
+            options.push(DamageOption {
+                name: multiattack.name.clone(),
+                damage,
+                uses: None,
+                attack_bonus,
+                save_dc: if attack_bonus.is_some() { None } else { save_dc },
+            });
+        }
+    }
+    Ok(options)
+}
+
+/// Pick the most damaging option available in each of three rounds
+fn best_per_round(options: &mut [DamageOption]) -> Vec<Option<usize>> {
+    (0..3).map(|_| {
+        let best = options.iter().enumerate()
+            .filter(|(_, option)| option.uses != Some(0))
+            .max_by(|(_, a), (_, b)| a.damage.total_cmp(&b.damage))
+            .map(|(i, _)| i);
+        if let Some(i) = best {
+            if let Some(uses) = options[i].uses.as_mut() {
+                *uses -= 1;
+            }
+        }
+        best
+    }).collect()
+}
+
+fn offensive_rating(stat_block: &StatBlock, factors: &mut Vec<CrFactor>) -> DmResult<OffensiveRating> {
+    let mut actions = damage_options(&stat_block.actions, factors)?;
+    let mut bonus_actions = damage_options(&stat_block.bonus_actions, factors)?;
+    let action_rounds = best_per_round(&mut actions);
+    let bonus_rounds = best_per_round(&mut bonus_actions);
+
+    let mut legendary_damage = 0.0;
+    if let Some(legendary) = &stat_block.legendary_actions {
+        // Spend every legendary action of the round on the most damaging one
+        for action in &legendary.actions {
+            let per_round = action_damage(&action.action)? * (legendary.per_round / action.cost.max(1)) as f64;
+            legendary_damage = f64::max(legendary_damage, per_round);
+        }
+        if legendary_damage > 0.0 {
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: "Legendary actions".to_string(),
+                detail: format!("+{:.1} damage per round", legendary_damage),
+            });
+        }
+    }
+
+    let damage_per_round: Vec<f64> = (0..3).map(|round| {
+        action_rounds[round].map(|i| actions[i].damage).unwrap_or(0.0)
+            + bonus_rounds[round].map(|i| bonus_actions[i].damage).unwrap_or(0.0)
+            + legendary_damage
+    }).collect();
+    let average_damage = damage_per_round.iter().sum::<f64>() / 3.0;
+    for (round, choice) in action_rounds.iter().enumerate() {
+        if let Some(i) = choice {
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: format!("Round {}", round + 1),
+                detail: format!("{} for {:.1} damage, {:.1} in total", actions[*i].name, actions[*i].damage, damage_per_round[round]),
+            });
+        }
+    }
+
+    let damage_rating = rating_for_damage(average_damage);
+    factors.push(CrFactor {
+        side: CrSide::Offensive,
+        name: "Damage per round".to_string(),
+        detail: format!("{:.1} average damage is in the range of CR {}", average_damage, damage_rating),
+    });
+
+    // The main attack is the one of the first round
+    let main = action_rounds[0].map(|i| &actions[i]);
+    let attack_bonus = main.and_then(|option| option.attack_bonus);
+    let save_dc = main.and_then(|option| option.save_dc).filter(|_| attack_bonus.is_none());
+    let expected = statistics(damage_rating);
+    let steps = match (attack_bonus, save_dc) {
+        (Some(bonus), _) => {
+            let steps = (bonus - expected.attack_bonus) / 2;
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: "Attack bonus".to_string(),
+                detail: format!("+{} against +{} expected at CR {}: {}", bonus, expected.attack_bonus, damage_rating, describe_steps(steps)),
+            });
+            steps
+        }
+        (None, Some(dc)) => {
+            let steps = (dc - expected.save_dc) / 2;
+            factors.push(CrFactor {
+                side: CrSide::Offensive,
+                name: "Save DC".to_string(),
+                detail: format!("DC {} against {} expected at CR {}: {}", dc, expected.save_dc, damage_rating, describe_steps(steps)),
+            });
+            steps
+        }
+        (None, None) => 0,
+    };
+
+    Ok(OffensiveRating {
+        damage_per_round,
+        average_damage,
+        attack_bonus,
+        save_dc,
+        damage_rating,
+        challenge_rating: shift(damage_rating, steps),
+    })
+}
+
+fn describe_steps(steps: i32) -> String {
+    match steps {
+        0 => "no change".to_string(),
+        steps if steps > 0 => format!("CR +{}", steps),
+        steps => format!("CR {}", steps),
+    }
+}
+
+/// Suggested challenge rating of a stat block, following the DMG method
+pub fn calculate(stat_block: &StatBlock) -> DmResult<CrCalculation> {
+    let mut factors = Vec::new();
+    let defensive = defensive_rating(stat_block, &mut factors);
+    let offensive = offensive_rating(stat_block, &mut factors)?;
+
+    // Average of the two, to the nearest challenge rating (rounding up halfway)
+    let average = (defensive.challenge_rating.value() + offensive.challenge_rating.value()) / 2.0;
+    let challenge_rating = ChallengeRating::all()
+        .min_by(|a, b| {
+            (a.value() - average).abs().total_cmp(&(b.value() - average).abs())
+                .then(b.cmp(a))
+        })
+        .unwrap_or_default();
+
+    Ok(CrCalculation {
+        challenge_rating,
+        experience_points: challenge_rating.experience_points(),
+        declared: stat_block.challenge_rating,
+        matches_declared: challenge_rating == stat_block.challenge_rating,
+        defensive,
+        offensive,
+        factors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::common::Ability;
+    use crate::core::models::monster::{
+        ArmorClass, Attack, Damage, HitPoints, MonsterFeature, SavingThrowEffect,
+    };
+
+    fn cr(s: &str) -> ChallengeRating {
+        s.parse().unwrap()
+    }
+
+    fn attack(name: &str, to_hit: i32, dice: &str, damage_type: DamageType) -> MonsterAction {
+        MonsterAction {
+            name: name.to_string(),
+            description: String::new(),
+            attack: Some(Attack { kind: AttackKind::MeleeWeapon, to_hit, reach: Some(5), range: None, long_range: None }),
+            saving_throw: None,
+            damage: vec![Damage { dice: dice.to_string(), damage_type }],
+            usage: None,
+        }
+    }
+
+    fn multiattack(description: &str) -> MonsterAction {
+        MonsterAction {
+            name: "Multiattack".to_string(),
+            description: description.to_string(),
+            attack: None,
+            saving_throw: None,
+            damage: Vec::new(),
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn test_experience_and_proficiency_by_cr() {
+        assert_eq!(cr("0").experience_points(), 10);
+        assert_eq!(cr("1/4").experience_points(), 50);
+        assert_eq!(cr("5").experience_points(), 1_800);
+        assert_eq!(cr("30").experience_points(), 155_000);
+
+        assert_eq!(cr("1/8").proficiency_bonus(), 2);
+        assert_eq!(cr("4").proficiency_bonus(), 2);
+        assert_eq!(cr("5").proficiency_bonus(), 3);
+        assert_eq!(cr("17").proficiency_bonus(), 6);
+        assert_eq!(cr("30").proficiency_bonus(), 9);
+    }
+
+    #[test]
+    fn test_challenge_rating_notation() {
+        assert_eq!(ChallengeRating::all().count(), STATISTICS.len());
+        assert_eq!(serde_json::to_value(cr("1/8")).unwrap(), "1/8");
+        // Numbers are accepted as well as fractions
+        let half: ChallengeRating = serde_json::from_value(serde_json::json!(0.5)).unwrap();
+        assert_eq!(half.to_string(), "1/2");
+        let twelve: ChallengeRating = serde_json::from_value(serde_json::json!("12")).unwrap();
+        assert_eq!(twelve, ChallengeRating::whole(12).unwrap());
+        assert!("1/3".parse::<ChallengeRating>().is_err());
+        assert!("31".parse::<ChallengeRating>().is_err());
+    }
+
+    #[test]
+    fn test_goblin_boss() {
+        // Goblin Boss: AC 17, 21 HP, two scimitar attacks at +4 for 1d6+2
+        let stat_block = StatBlock {
+            name: "Goblin Boss".to_string(),
+            armor_class: ArmorClass { value: 17, source: Some("chain shirt, shield".to_string()) },
+            hit_points: HitPoints { average: 21, formula: Some("6d6".to_string()) },
+            challenge_rating: cr("1"),
+            actions: vec![
+                multiattack("The goblin makes two attacks with its scimitar."),
+                attack("Scimitar", 4, "1d6+2", DamageType::Slashing),
+            ],
+            ..StatBlock::default()
+        };
+
+        let calculation = calculate(&stat_block).unwrap();
+        // 21 HP is CR 1/8, AC 17 is 4 over the expected 13: two steps up
+        assert_eq!(calculation.defensive.hit_points_rating, cr("1/8"));
+        assert_eq!(calculation.defensive.challenge_rating, cr("1/2"));
+        // 11 damage per round is CR 1, +4 is one over the expected +3: no change
+        assert_eq!(calculation.offensive.damage_per_round, vec![11.0, 11.0, 11.0]);
+        assert_eq!(calculation.offensive.damage_rating, cr("1"));
+        assert_eq!(calculation.offensive.attack_bonus, Some(4));
+        assert_eq!(calculation.offensive.challenge_rating, cr("1"));
+        // Average of 1/2 and 1 rounds to 1
+        assert_eq!(calculation.challenge_rating, cr("1"));
+        assert!(calculation.matches_declared);
+        assert_eq!(calculation.experience_points, 200);
+        assert!(calculation.factors.iter().any(|f| f.name == "Multiattack" && f.detail.starts_with("2x Scimitar")));
+    }
+
+    #[test]
+    fn test_defenses_and_limited_actions() {
+        // Breath weapon once, then claws; resistant to weapons, Magic Resistance, flying archer
+        let mut breath = MonsterAction {
+            name: "Fire Breath".to_string(),
+            description: "Exhales fire in a 30-foot cone.".to_string(),
+            attack: None,
+            saving_throw: Some(SavingThrowEffect { ability: Ability::Dexterity, dc: 15, half_on_success: true }),
+            damage: vec![Damage { dice: "10d6".to_string(), damage_type: DamageType::Fire }],
+            usage: Some(ActionUsage::Recharge { min: 5 }),
+        };
+        let mut stat_block = StatBlock {
+            name: "Drake".to_string(),
+            armor_class: ArmorClass { value: 15, source: None },
+            hit_points: HitPoints { average: 100, formula: None },
+            challenge_rating: cr("7"),
+            damage_resistances: [DamageType::Bludgeoning, DamageType::Piercing, DamageType::Slashing].into_iter()
+                .map(|damage_type| DamageDefense { damage_type, condition: Some("from nonmagical attacks".to_string()) })
+                .collect(),
+            traits: vec![MonsterFeature { name: "Magic Resistance".to_string(), description: String::new(), usage: None }],
+            actions: vec![
+                multiattack("The drake makes two claw attacks."),
+                attack("Claw", 6, "2d6+3", DamageType::Slashing),
+                breath.clone(),
+            ],
+            ..StatBlock::default()
+        };
+
+        let calculation = calculate(&stat_block).unwrap();
+        // 100 HP x1.5 = 150: CR 6; AC 15 + 2 against 15: one step up
+        assert_eq!(calculation.defensive.effective_hit_points, 150);
+        assert_eq!(calculation.defensive.effective_armor_class, 17);
+        assert_eq!(calculation.defensive.challenge_rating, cr("7"));
+        // Breath on two targets in the first round, then two claws
+        assert_eq!(calculation.offensive.damage_per_round, vec![70.0, 20.0, 20.0]);
+        assert_eq!(calculation.offensive.save_dc, Some(15));
+        // 36.7 damage per round is CR 5, DC 15 as expected
+        assert_eq!(calculation.offensive.damage_rating, cr("5"));
+        assert_eq!(calculation.offensive.challenge_rating, cr("5"));
+        assert_eq!(calculation.challenge_rating, cr("6"));
+        assert!(!calculation.matches_declared);
+
+        // Three times a day: the breath every round
+        breath.usage = Some(ActionUsage::PerDay { uses: 3 });
+        stat_block.actions[2] = breath;
+        let calculation = calculate(&stat_block).unwrap();
+        assert_eq!(calculation.offensive.damage_per_round, vec![70.0, 70.0, 70.0]);
+        assert_eq!(calculation.offensive.damage_rating, cr("11"));
+    }
+
+    #[test]
+    fn test_statistics_table_is_continuous() {
+        let rows: Vec<CrStatistics> = ChallengeRating::all().map(statistics).collect();
+        for pair in rows.windows(2) {
+            assert_eq!(pair[0].max_hit_points + 1, pair[1].min_hit_points);
+            assert_eq!(pair[0].max_damage + 1, pair[1].min_damage);
+        }
+        assert_eq!(rating_for_hit_points(0), cr("0"));
+        assert_eq!(rating_for_hit_points(1_000), cr("30"));
+    }
+}
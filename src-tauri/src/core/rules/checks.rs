@@ -0,0 +1,317 @@
+//! d20 test resolution: ability checks, saving throws and attack rolls (5e rules)
+
+use crate::core::rules::dice;
+use crate::utils::error::{DmAssistantError, DmResult};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Kind of d20 test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckKind {
+    AbilityCheck,
+    SavingThrow,
+    AttackRoll,
+}
+
+/// How the d20 is rolled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RollMode {
+    Normal,
+    Advantage,
+    Disadvantage,
+    ElvenAccuracy, // Advantage rolling three dice
+}
+
+/// Proficiency applied to the roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Proficiency {
+    #[default]
+    None,
+    Half, // Jack of All Trades, rounded down
+    Proficient,
+    Expertise,
+}
+
+/// Request to resolve a d20 test
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckRequest {
+    pub kind: CheckKind,
+    pub modifier: i32, // Ability modifier plus any flat bonus
+    #[serde(default)]
+    pub proficiency: Proficiency,
+    #[serde(default)]
+    pub proficiency_bonus: i32,
+    #[serde(default)]
+    pub advantage: bool,
+    #[serde(default)]
+    pub disadvantage: bool,
+    #[serde(default)]
+    pub elven_accuracy: bool,
+    pub target: i32, // DC for checks and saves, AC for attacks
+    #[serde(default = "default_critical_range")]
+    pub critical_range: u32, // Lowest natural roll that crits (19 for a Champion)
+}
+
+/// Outcome of a d20 test
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    pub kind: CheckKind,
+    pub roll_mode: RollMode,
+    pub d20_rolls: Vec<u32>, // Every d20 rolled
+    pub natural: u32, // The d20 that counts
+    pub total: i32,
+    pub target: i32,
+    pub success: bool,
+    pub natural_one: bool,
+    pub natural_twenty: bool,
+    pub critical_hit: bool, // Attack rolls only
+    pub margin: i32, // Total minus target, negative on failure
+}
+
+fn default_critical_range() -> u32 {
+    20
+}
+
+impl RollMode {
+    /// Combine advantage and disadvantage sources.
+    /// Having both cancels out, no matter how many of each (PHB p.173);
+    /// Elven Accuracy only matters when the roll has advantage.
+    pub fn from_sources(advantage: bool, disadvantage: bool, elven_accuracy: bool) -> Self {
+        match (advantage, disadvantage) {
+            (true, false) if elven_accuracy => RollMode::ElvenAccuracy,
+            (true, false) => RollMode::Advantage,
+            (false, true) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
+        }
+    }
+
+    /// Number of d20s rolled
+    pub fn dice_count(&self) -> usize {
+        match self {
+            RollMode::Normal => 1,
+            RollMode::Advantage | RollMode::Disadvantage => 2,
+            RollMode::ElvenAccuracy => 3,
+        }
+    }
+
+    /// Dice notation for the d20 roll
+    pub fn notation(&self) -> &'static str {
+        match self {
+            RollMode::Normal => "1d20",
+            RollMode::Advantage => "2d20kh1",
+            RollMode::Disadvantage => "2d20kl1",
+            RollMode::ElvenAccuracy => "3d20kh1",
+        }
+    }
+}
+
+impl Proficiency {
+    /// Bonus added to the roll for a given proficiency bonus
+    pub fn bonus(&self, proficiency_bonus: i32) -> i32 {
+        match self {
+            Proficiency::None => 0,
+            Proficiency::Half => proficiency_bonus.div_euclid(2),
+            Proficiency::Proficient => proficiency_bonus,
+            Proficiency::Expertise => proficiency_bonus * 2,
+        }
+    }
+}
+
+impl CheckRequest {
+    /// Roll mode after combining advantage sources
+    pub fn roll_mode(&self) -> RollMode {
+        RollMode::from_sources(self.advantage, self.disadvantage, self.elven_accuracy)
+    }
+
+    /// Total bonus added to the natural roll
+    pub fn total_bonus(&self) -> i32 {
+        self.modifier + self.proficiency.bonus(self.proficiency_bonus)
+    }
+}
+
+/// Roll and resolve a d20 test
+pub fn resolve_check<R: Rng + ?Sized>(req: &CheckRequest, rng: &mut R) -> DmResult<CheckResult> {
+    let roll = dice::roll_with_rng(req.roll_mode().notation(), rng)?;
+    let rolls: Vec<u32> = roll.all_dice().map(|d| d.natural()).collect();
+    resolve_with_rolls(req, &rolls)
+}
+
+/// Resolve a d20 test from already rolled d20s
+pub fn resolve_with_rolls(req: &CheckRequest, rolls: &[u32]) -> DmResult<CheckResult> {
+    if !(2..=20).contains(&req.critical_range) {
+        return Err(DmAssistantError::validation("Critical range must be between 2 and 20"));
+    }
+    let mode = req.roll_mode();
+    if rolls.len() != mode.dice_count() {
+        return Err(DmAssistantError::invalid_input(&format!(
+            "Expected {} d20 rolls, got {}", mode.dice_count(), rolls.len()
+        )));
+    }
+    if rolls.iter().any(|r| !(1..=20).contains(r)) {
+        return Err(DmAssistantError::invalid_input("d20 rolls must be between 1 and 20"));
+    }
+
+    let natural = match mode {
+        RollMode::Disadvantage => rolls.iter().copied().min(),
+        _ => rolls.iter().copied().max(),
+    }.unwrap_or(1);
+
+    let total = natural as i32 + req.total_bonus();
+    let margin = total - req.target;
+    let natural_one = natural == 1;
+    let natural_twenty = natural == 20;
+
+    let (success, critical_hit) = match req.kind {
+        // A natural 1 always misses, a critical always hits
+        CheckKind::AttackRoll if natural_one => (false, false),
+        CheckKind::AttackRoll if natural >= req.critical_range => (true, true),
+        CheckKind::AttackRoll => (margin >= 0, false),
+        // Checks and saves have no automatic success or failure
+        CheckKind::AbilityCheck | CheckKind::SavingThrow => (margin >= 0, false),
+    };
+
+    Ok(CheckResult {
+        kind: req.kind,
+        roll_mode: mode,
+        d20_rolls: rolls.to_vec(),
+        natural,
+        total,
+        target: req.target,
+        success,
+        natural_one,
+        natural_twenty,
+        critical_hit,
+        margin,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn request(kind: CheckKind, modifier: i32, target: i32) -> CheckRequest {
+        CheckRequest {
+            kind,
+            modifier,
+            proficiency: Proficiency::None,
+            proficiency_bonus: 2,
+            advantage: false,
+            disadvantage: false,
+            elven_accuracy: false,
+            target,
+            critical_range: 20,
+        }
+    }
+
+    #[test]
+    fn test_advantage_and_disadvantage_cancel() {
+        assert_eq!(RollMode::from_sources(true, true, false), RollMode::Normal);
+        assert_eq!(RollMode::from_sources(true, true, true), RollMode::Normal);
+        assert_eq!(RollMode::from_sources(false, false, true), RollMode::Normal);
+        assert_eq!(RollMode::from_sources(true, false, true), RollMode::ElvenAccuracy);
+        assert_eq!(RollMode::from_sources(false, true, true), RollMode::Disadvantage);
+    }
+
+    #[test]
+    fn test_roll_mode_picks_the_right_die() {
+        let mut req = request(CheckKind::AbilityCheck, 0, 10);
+        req.advantage = true;
+        assert_eq!(resolve_with_rolls(&req, &[4, 15]).unwrap().natural, 15);
+
+        req.advantage = false;
+        req.disadvantage = true;
+        assert_eq!(resolve_with_rolls(&req, &[4, 15]).unwrap().natural, 4);
+
+        req.advantage = true;
+        req.elven_accuracy = true;
+        // Advantage and disadvantage cancel, so a single die is expected
+        assert!(resolve_with_rolls(&req, &[4, 15]).is_err());
+        assert_eq!(resolve_with_rolls(&req, &[9]).unwrap().natural, 9);
+
+        req.disadvantage = false;
+        assert_eq!(resolve_with_rolls(&req, &[4, 18, 11]).unwrap().natural, 18);
+    }
+
+    #[test]
+    fn test_proficiency_and_margin() {
+        let mut req = request(CheckKind::AbilityCheck, 3, 15);
+        req.proficiency_bonus = 3;
+
+        req.proficiency = Proficiency::Half;
+        assert_eq!(resolve_with_rolls(&req, &[10]).unwrap().total, 14);
+
+        req.proficiency = Proficiency::Expertise;
+        let result = resolve_with_rolls(&req, &[10]).unwrap();
+        assert_eq!(result.total, 19);
+        assert_eq!(result.margin, 4);
+        assert!(result.success);
+
+        let result = resolve_with_rolls(&req, &[5]).unwrap();
+        assert_eq!(result.margin, -1);
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn test_attack_natural_one_and_twenty() {
+        let req = request(CheckKind::AttackRoll, 30, 12);
+        let result = resolve_with_rolls(&req, &[1]).unwrap();
+        assert!(result.natural_one);
+        assert!(!result.success);
+
+        let req = request(CheckKind::AttackRoll, 0, 30);
+        let result = resolve_with_rolls(&req, &[20]).unwrap();
+        assert!(result.natural_twenty && result.success && result.critical_hit);
+    }
+
+    #[test]
+    fn test_extended_critical_range() {
+        let mut req = request(CheckKind::AttackRoll, 0, 25);
+        req.critical_range = 19;
+
+        let result = resolve_with_rolls(&req, &[19]).unwrap();
+        assert!(result.success && result.critical_hit);
+        assert!(!result.natural_twenty);
+
+        let result = resolve_with_rolls(&req, &[18]).unwrap();
+        assert!(!result.success && !result.critical_hit);
+
+        req.critical_range = 1;
+        assert!(resolve_with_rolls(&req, &[18]).is_err());
+    }
+
+    #[test]
+    fn test_saves_and_checks_have_no_automatic_results() {
+        let req = request(CheckKind::SavingThrow, 0, 25);
+        let result = resolve_with_rolls(&req, &[20]).unwrap();
+        assert!(result.natural_twenty);
+        assert!(!result.success);
+        assert!(!result.critical_hit);
+
+        let req = request(CheckKind::AbilityCheck, 12, 10);
+        let result = resolve_with_rolls(&req, &[1]).unwrap();
+        assert!(result.natural_one);
+        assert!(result.success);
+    }
+
+    #[test]
+    fn test_resolve_check_rolls_the_right_number_of_dice() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut req = request(CheckKind::AttackRoll, 5, 15);
+        req.advantage = true;
+        req.elven_accuracy = true;
+
+        for _ in 0..100 {
+            let result = resolve_check(&req, &mut rng).unwrap();
+            assert_eq!(result.d20_rolls.len(), 3);
+            assert_eq!(result.natural, *result.d20_rolls.iter().max().unwrap());
+            assert_eq!(result.total, result.natural as i32 + 5);
+        }
+    }
+}
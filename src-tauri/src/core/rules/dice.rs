@@ -0,0 +1,758 @@
+//! Dice notation parser and evaluator
+//!
+//! Supported notation (case-insensitive, whitespace between terms is ignored):
+//! - `NdM`, `dM`, `d%`: roll N dice with M sides (N defaults to 1, `%` is 100)
+//! - `+ - * /` and parentheses: arithmetic and grouping, `/` rounds down
+//! - `khN`/`klN` (`kN` = `khN`): keep the N highest/lowest dice (N defaults to 1)
+//! - `dhN`/`dlN` (`dN` = `dlN`): drop the N highest/lowest dice
+//! - `!`, `!>N`, ...: explode (roll again and add) on max or on the compare point
+//! - `rN`, `r<N`, ...: reroll until the die no longer matches
+//! - `roN`, `ro<N`, ...: reroll once if the die matches
+//! - `minN`/`maxN`: clamp each die to at least/at most N
+
+use crate::core::models::common::{DiceRoll, DiceTermRoll, DieResult};
+use crate::utils::error::{DmAssistantError, DmResult};
+use rand::Rng;
+
+/// Maximum number of dice in a single term
+pub const MAX_DICE: u32 = 1000;
+/// Maximum number of sides of a die
+pub const MAX_SIDES: u32 = 10_000;
+/// Maximum number of extra rolls a single die can explode into
+pub const MAX_EXPLOSIONS: usize = 100;
+/// Maximum number of rerolls for reroll-until before giving up
+pub const MAX_REROLLS: usize = 1000;
+
+/// Parsed dice expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiceExpr {
+    Number(i64),
+    Dice(DiceTerm),
+    Negate(Box<DiceExpr>),
+    Binary {
+        op: BinaryOp,
+        left: Box<DiceExpr>,
+        right: Box<DiceExpr>,
+        position: usize, // Position of the operator, used for evaluation errors
+    },
+    Group(Box<DiceExpr>),
+}
+
+/// Arithmetic operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// A single `NdM` term with its modifiers
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiceTerm {
+    pub count: u32,
+    pub sides: u32,
+    pub keep: Option<KeepRule>,
+    pub explode: Option<ComparePoint>, // None if the term doesn't explode
+    pub reroll_until: Vec<ComparePoint>,
+    pub reroll_once: Vec<ComparePoint>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub notation: String, // Source text of the term, lowercased
+    pub position: usize,
+}
+
+/// Keep/drop modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepRule {
+    KeepHighest(u32),
+    KeepLowest(u32),
+    DropHighest(u32),
+    DropLowest(u32),
+}
+
+/// Comparison used by explode and reroll modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// A comparison against a fixed value, e.g. `<3` in `r<3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComparePoint {
+    pub op: CompareOp,
+    pub value: i64,
+}
+
+impl ComparePoint {
+    /// Create a compare point matching a single value
+    pub fn equal(value: i64) -> Self {
+        Self { op: CompareOp::Equal, value }
+    }
+
+    /// Check if a face matches this compare point
+    pub fn matches(&self, face: i64) -> bool {
+        match self.op {
+            CompareOp::Equal => face == self.value,
+            CompareOp::Less => face < self.value,
+            CompareOp::LessOrEqual => face <= self.value,
+            CompareOp::Greater => face > self.value,
+            CompareOp::GreaterOrEqual => face >= self.value,
+        }
+    }
+}
+
+impl KeepRule {
+    /// Resolve the rule to (keep highest?, number of dice kept) for a term of `count` dice
+    pub fn kept(&self, count: u32) -> (bool, u32) {
+        match *self {
+            KeepRule::KeepHighest(n) => (true, n.min(count)),
+            KeepRule::KeepLowest(n) => (false, n.min(count)),
+            KeepRule::DropHighest(n) => (false, count.saturating_sub(n)),
+            KeepRule::DropLowest(n) => (true, count.saturating_sub(n)),
+        }
+    }
+}
+
+impl DiceTerm {
+    /// Check if a face triggers a reroll-until
+    pub fn rerolls_until(&self, face: i64) -> bool {
+        self.reroll_until.iter().any(|c| c.matches(face))
+    }
+
+    /// Check if a face triggers a reroll-once
+    pub fn rerolls_once(&self, face: i64) -> bool {
+        self.reroll_once.iter().any(|c| c.matches(face))
+    }
+
+    /// Check if a face makes the die explode
+    pub fn explodes_on(&self, face: i64) -> bool {
+        self.explode.map(|c| c.matches(face)).unwrap_or(false)
+    }
+
+    /// Apply min/max clamps to a die value
+    pub fn clamp(&self, value: i64) -> i64 {
+        let mut value = value;
+        if let Some(min) = self.min { value = value.max(min); }
+        if let Some(max) = self.max { value = value.min(max); }
+        value
+    }
+
+    /// Roll every die of the term, applying modifiers
+    fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> DiceTermRoll {
+        let mut dice: Vec<DieResult> = (0..self.count).map(|_| self.roll_die(rng)).collect();
+
+        if let Some(rule) = self.keep {
+            let (highest, keep) = rule.kept(self.count);
+            let mut order: Vec<usize> = (0..dice.len()).collect();
+            // Stable sort so that ties are resolved in roll order
+            if highest {
+                order.sort_by_key(|&i| std::cmp::Reverse(dice[i].value));
+            } else {
+                order.sort_by_key(|&i| dice[i].value);
+            }
+            for (rank, &i) in order.iter().enumerate() {
+                dice[i].kept = (rank as u32) < keep;
+            }
+        }
+
+        let subtotal = dice.iter().filter(|d| d.kept).map(|d| d.value).sum();
+        DiceTermRoll {
+            notation: self.notation.clone(),
+            sides: self.sides,
+            dice,
+            subtotal,
+        }
+    }
+
+    fn roll_die<R: Rng + ?Sized>(&self, rng: &mut R) -> DieResult {
+        let mut rerolled = Vec::new();
+        let mut face = rng.gen_range(1..=self.sides);
+
+        let mut rerolls = 0;
+        while self.rerolls_until(face as i64) && rerolls < MAX_REROLLS {
+            rerolled.push(face);
+            face = rng.gen_range(1..=self.sides);
+            rerolls += 1;
+        }
+        if self.rerolls_once(face as i64) {
+            rerolled.push(face);
+            face = rng.gen_range(1..=self.sides);
+        }
+
+        let mut faces = vec![face];
+        while self.explodes_on(face as i64) && faces.len() <= MAX_EXPLOSIONS {
+            face = rng.gen_range(1..=self.sides);
+            faces.push(face);
+        }
+
+        let raw: i64 = faces.iter().map(|&f| f as i64).sum();
+        let value = self.clamp(raw);
+        DieResult {
+            value,
+            exploded: faces.len() > 1,
+            faces,
+            rerolled,
+            clamped: value != raw,
+            kept: true,
+        }
+    }
+}
+
+impl DiceExpr {
+    /// Roll the expression with the given random number generator
+    pub fn roll<R: Rng + ?Sized>(&self, expression: &str, rng: &mut R) -> DmResult<DiceRoll> {
+        let mut terms = Vec::new();
+        let mut breakdown = String::new();
+        let total = self.evaluate(rng, &mut terms, &mut breakdown)?;
+
+        Ok(DiceRoll {
+            expression: expression.trim().to_string(),
+            total,
+            terms,
+            breakdown,
+        })
+    }
+
+    /// Iterate over every dice term of the expression
+    pub fn dice_terms(&self) -> Vec<&DiceTerm> {
+        let mut terms = Vec::new();
+        self.collect_terms(&mut terms);
+        terms
+    }
+
+    fn collect_terms<'a>(&'a self, terms: &mut Vec<&'a DiceTerm>) {
+        match self {
+            DiceExpr::Number(_) => {}
+            DiceExpr::Dice(term) => terms.push(term),
+            DiceExpr::Negate(inner) | DiceExpr::Group(inner) => inner.collect_terms(terms),
+            DiceExpr::Binary { left, right, .. } => {
+                left.collect_terms(terms);
+                right.collect_terms(terms);
+            }
+        }
+    }
+
+    fn evaluate<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        terms: &mut Vec<DiceTermRoll>,
+        breakdown: &mut String,
+    ) -> DmResult<i64> {
+        match self {
+            DiceExpr::Number(n) => {
+                breakdown.push_str(&n.to_string());
+                Ok(*n)
+            }
+            DiceExpr::Dice(term) => {
+                let rolled = term.roll(rng);
+                breakdown.push_str(&format_term(&rolled));
+                let subtotal = rolled.subtotal;
+                terms.push(rolled);
+                Ok(subtotal)
+            }
+            DiceExpr::Negate(inner) => {
+                breakdown.push('-');
+                let value = inner.evaluate(rng, terms, breakdown)?;
+                value.checked_neg()
+                    .ok_or_else(|| DmAssistantError::dice("Arithmetic overflow", 0))
+            }
+            DiceExpr::Group(inner) => {
+                breakdown.push('(');
+                let value = inner.evaluate(rng, terms, breakdown)?;
+                breakdown.push(')');
+                Ok(value)
+            }
+            DiceExpr::Binary { op, left, right, position } => {
+                let lhs = left.evaluate(rng, terms, breakdown)?;
+                breakdown.push_str(match op {
+                    BinaryOp::Add => " + ",
+                    BinaryOp::Subtract => " - ",
+                    BinaryOp::Multiply => " * ",
+                    BinaryOp::Divide => " / ",
+                });
+                let rhs = right.evaluate(rng, terms, breakdown)?;
+                apply_op(*op, lhs, rhs, *position)
+            }
+        }
+    }
+}
+
+/// Apply an arithmetic operator with overflow checks, rounding divisions down
+pub fn apply_op(op: BinaryOp, lhs: i64, rhs: i64, position: usize) -> DmResult<i64> {
+    let result = match op {
+        BinaryOp::Add => lhs.checked_add(rhs),
+        BinaryOp::Subtract => lhs.checked_sub(rhs),
+        BinaryOp::Multiply => lhs.checked_mul(rhs),
+        BinaryOp::Divide => {
+            if rhs == 0 {
+                return Err(DmAssistantError::dice("Division by zero", position));
+            }
+            lhs.checked_div(rhs).map(|q| {
+                if lhs % rhs != 0 && ((lhs < 0) != (rhs < 0)) { q - 1 } else { q }
+            })
+        }
+    };
+    result.ok_or_else(|| DmAssistantError::dice("Arithmetic overflow", position))
+}
+
+fn format_term(rolled: &DiceTermRoll) -> String {
+    let dice: Vec<String> = rolled.dice.iter()
+        .map(|d| {
+            let value = if d.exploded && !d.clamped {
+                d.faces.iter().map(|f| f.to_string()).collect::<Vec<_>>().join("+")
+            } else {
+                d.value.to_string()
+            };
+            if d.kept { value } else { format!("~{}~", value) }
+        })
+        .collect();
+    format!("{}[{}]", rolled.notation, dice.join(", "))
+}
+
+/// Parse a dice expression
+pub fn parse(notation: &str) -> DmResult<DiceExpr> {
+    let mut parser = Parser::new(notation);
+    parser.skip_whitespace();
+    if parser.at_end() {
+        return Err(DmAssistantError::dice("Empty dice expression", 0));
+    }
+
+    let expr = parser.parse_expr()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek() {
+        return Err(DmAssistantError::dice(&format!("Unexpected character '{}'", c), parser.pos));
+    }
+    Ok(expr)
+}
+
+/// Parse and roll a dice expression using the thread-local RNG
+pub fn roll(notation: &str) -> DmResult<DiceRoll> {
+    roll_with_rng(notation, &mut rand::thread_rng())
+}
+
+/// Parse and roll a dice expression using the given RNG
+pub fn roll_with_rng<R: Rng + ?Sized>(notation: &str, rng: &mut R) -> DmResult<DiceRoll> {
+    parse(notation)?.roll(notation, rng)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Self {
+        Self { chars: input.chars().collect(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_lower(&self) -> Option<char> {
+        self.peek().map(|c| c.to_ascii_lowercase())
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+            self.pos += 1;
+        }
+    }
+
+    fn error<T>(&self, msg: &str) -> DmResult<T> {
+        Err(DmAssistantError::dice(msg, self.pos))
+    }
+
+    fn unexpected<T>(&self) -> DmResult<T> {
+        match self.peek() {
+            Some(c) => self.error(&format!("Unexpected character '{}'", c)),
+            None => self.error("Unexpected end of expression"),
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn parse_expr(&mut self) -> DmResult<DiceExpr> {
+        let mut left = self.parse_term()?;
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek() {
+                Some('+') => BinaryOp::Add,
+                Some('-') => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            let position = self.pos;
+            self.pos += 1;
+            let right = self.parse_term()?;
+            left = DiceExpr::Binary { op, left: Box::new(left), right: Box::new(right), position };
+        }
+    }
+
+    // term := factor (('*' | '/') factor)*
+    fn parse_term(&mut self) -> DmResult<DiceExpr> {
+        let mut left = self.parse_factor()?;
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek() {
+                Some('*') => BinaryOp::Multiply,
+                Some('/') => BinaryOp::Divide,
+                _ => return Ok(left),
+            };
+            let position = self.pos;
+            self.pos += 1;
+            let right = self.parse_factor()?;
+            left = DiceExpr::Binary { op, left: Box::new(left), right: Box::new(right), position };
+        }
+    }
+
+    // factor := '-' factor | '+' factor | primary
+    fn parse_factor(&mut self) -> DmResult<DiceExpr> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(DiceExpr::Negate(Box::new(self.parse_factor()?)))
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.parse_factor()
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    // primary := number | dice | '(' expr ')'
+    fn parse_primary(&mut self) -> DmResult<DiceExpr> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek_lower() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.parse_expr()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return self.error("Expected ')'");
+                }
+                self.pos += 1;
+                Ok(DiceExpr::Group(Box::new(inner)))
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let value = self.parse_number()?;
+                if self.peek_lower() == Some('d') {
+                    let count = u32::try_from(value)
+                        .ok()
+                        .filter(|&n| n <= MAX_DICE)
+                        .ok_or_else(|| DmAssistantError::dice(&format!("Cannot roll more than {} dice", MAX_DICE), start))?;
+                    self.parse_dice(count, start)
+                } else {
+                    Ok(DiceExpr::Number(value))
+                }
+            }
+            Some('d') => self.parse_dice(1, start),
+            _ => self.unexpected(),
+        }
+    }
+
+    fn parse_number(&mut self) -> DmResult<i64> {
+        let start = self.pos;
+        while self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return self.unexpected();
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits.parse::<i64>()
+            .map_err(|_| DmAssistantError::dice("Number is too large", start))
+    }
+
+    fn parse_dice(&mut self, count: u32, start: usize) -> DmResult<DiceExpr> {
+        // Skip the 'd'
+        self.pos += 1;
+
+        let sides_pos = self.pos;
+        let sides = if self.peek() == Some('%') {
+            self.pos += 1;
+            100
+        } else {
+            if !self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                return self.error("Expected number of sides");
+            }
+            let sides = self.parse_number()?;
+            if !(1..=MAX_SIDES as i64).contains(&sides) {
+                return Err(DmAssistantError::dice(&format!("Dice must have between 1 and {} sides", MAX_SIDES), sides_pos));
+            }
+            sides as u32
+        };
+
+        let mut term = DiceTerm {
+            count,
+            sides,
+            keep: None,
+            explode: None,
+            reroll_until: Vec::new(),
+            reroll_once: Vec::new(),
+            min: None,
+            max: None,
+            notation: String::new(),
+            position: start,
+        };
+
+        while let Some(c) = self.peek_lower() {
+            let modifier_pos = self.pos;
+            match c {
+                'k' | 'd' => {
+                    self.pos += 1;
+                    let rule = match (c, self.peek_lower()) {
+                        ('k', Some('l')) => { self.pos += 1; KeepRule::KeepLowest(self.parse_modifier_count()?) }
+                        ('k', Some('h')) => { self.pos += 1; KeepRule::KeepHighest(self.parse_modifier_count()?) }
+                        ('k', _) => KeepRule::KeepHighest(self.parse_modifier_count()?),
+                        ('d', Some('h')) => { self.pos += 1; KeepRule::DropHighest(self.parse_modifier_count()?) }
+                        ('d', Some('l')) => { self.pos += 1; KeepRule::DropLowest(self.parse_modifier_count()?) }
+                        ('d', Some(d)) if d.is_ascii_digit() => KeepRule::DropLowest(self.parse_modifier_count()?),
+                        _ => return Err(DmAssistantError::dice("Expected 'dh' or 'dl' modifier", modifier_pos)),
+                    };
+                    if term.keep.is_some() {
+                        return Err(DmAssistantError::dice("Only one keep/drop modifier is allowed per term", modifier_pos));
+                    }
+                    match rule {
+                        KeepRule::KeepHighest(n) | KeepRule::KeepLowest(n) if n == 0 || n > count => {
+                            return Err(DmAssistantError::dice(&format!("Cannot keep {} of {} dice", n, count), modifier_pos));
+                        }
+                        KeepRule::DropHighest(n) | KeepRule::DropLowest(n) if n > count => {
+                            return Err(DmAssistantError::dice(&format!("Cannot drop {} of {} dice", n, count), modifier_pos));
+                        }
+                        _ => {}
+                    }
+                    term.keep = Some(rule);
+                }
+                '!' => {
+                    self.pos += 1;
+                    if term.explode.is_some() {
+                        return Err(DmAssistantError::dice("Only one explode modifier is allowed per term", modifier_pos));
+                    }
+                    let point = if self.at_compare_point() {
+                        self.parse_compare_point()?
+                    } else {
+                        ComparePoint::equal(sides as i64)
+                    };
+                    if (1..=sides as i64).all(|f| point.matches(f)) {
+                        return Err(DmAssistantError::dice("Explode condition matches every face", modifier_pos));
+                    }
+                    term.explode = Some(point);
+                }
+                'r' => {
+                    self.pos += 1;
+                    let once = self.peek_lower() == Some('o');
+                    if once {
+                        self.pos += 1;
+                    }
+                    if !self.at_compare_point() {
+                        return self.error("Expected reroll condition");
+                    }
+                    let point = self.parse_compare_point()?;
+                    if once {
+                        term.reroll_once.push(point);
+                    } else {
+                        term.reroll_until.push(point);
+                        if (1..=sides as i64).all(|f| term.rerolls_until(f)) {
+                            return Err(DmAssistantError::dice("Reroll condition matches every face", modifier_pos));
+                        }
+                    }
+                }
+                'm' => {
+                    let keyword: String = self.chars[self.pos..].iter().take(3).collect::<String>().to_ascii_lowercase();
+                    if keyword != "min" && keyword != "max" {
+                        return Err(DmAssistantError::dice("Expected 'min' or 'max' modifier", modifier_pos));
+                    }
+                    self.pos += 3;
+                    if !self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                        return self.error(&format!("Expected value after '{}'", keyword));
+                    }
+                    let value = self.parse_number()?;
+                    if keyword == "min" { term.min = Some(value); } else { term.max = Some(value); }
+                    if let (Some(min), Some(max)) = (term.min, term.max) {
+                        if min > max {
+                            return Err(DmAssistantError::dice("Minimum is greater than maximum", modifier_pos));
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        term.notation = self.chars[start..self.pos].iter().collect::<String>().to_ascii_lowercase();
+        Ok(DiceExpr::Dice(term))
+    }
+
+    fn parse_modifier_count(&mut self) -> DmResult<u32> {
+        if !self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+            return Ok(1);
+        }
+        let start = self.pos;
+        let value = self.parse_number()?;
+        u32::try_from(value).map_err(|_| DmAssistantError::dice("Number is too large", start))
+    }
+
+    fn at_compare_point(&self) -> bool {
+        matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '<' || c == '>' || c == '=')
+    }
+
+    fn parse_compare_point(&mut self) -> DmResult<ComparePoint> {
+        let op = match self.peek() {
+            Some('<') => {
+                self.pos += 1;
+                if self.peek() == Some('=') { self.pos += 1; CompareOp::LessOrEqual } else { CompareOp::Less }
+            }
+            Some('>') => {
+                self.pos += 1;
+                if self.peek() == Some('=') { self.pos += 1; CompareOp::GreaterOrEqual } else { CompareOp::Greater }
+            }
+            Some('=') => {
+                self.pos += 1;
+                CompareOp::Equal
+            }
+            _ => CompareOp::Equal,
+        };
+        if !self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+            return self.error("Expected number in condition");
+        }
+        let value = self.parse_number()?;
+        Ok(ComparePoint { op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn dice_term(expr: &DiceExpr) -> &DiceTerm {
+        match expr {
+            DiceExpr::Dice(term) => term,
+            other => panic!("expected dice term, got {:?}", other),
+        }
+    }
+
+    fn error_position(result: DmResult<DiceExpr>) -> usize {
+        match result {
+            Err(DmAssistantError::DiceError { position, .. }) => position,
+            other => panic!("expected dice error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_basic_terms() {
+        let term = parse("3d6").unwrap();
+        let term = dice_term(&term);
+        assert_eq!((term.count, term.sides), (3, 6));
+
+        let term = parse("d%").unwrap();
+        assert_eq!(dice_term(&term).sides, 100);
+
+        let term = parse("D20").unwrap();
+        let term = dice_term(&term);
+        assert_eq!((term.count, term.sides, term.notation.as_str()), (1, 20, "d20"));
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        let expr = parse("4d6kh3").unwrap();
+        assert_eq!(dice_term(&expr).keep, Some(KeepRule::KeepHighest(3)));
+
+        let expr = parse("2d20kl").unwrap();
+        assert_eq!(dice_term(&expr).keep, Some(KeepRule::KeepLowest(1)));
+
+        let expr = parse("4d6dl1").unwrap();
+        assert_eq!(dice_term(&expr).keep, Some(KeepRule::DropLowest(1)));
+
+        let expr = parse("3d6!>5").unwrap();
+        assert_eq!(dice_term(&expr).explode, Some(ComparePoint { op: CompareOp::Greater, value: 5 }));
+
+        let expr = parse("2d6r<3ro4").unwrap();
+        let term = dice_term(&expr);
+        assert_eq!(term.reroll_until, vec![ComparePoint { op: CompareOp::Less, value: 3 }]);
+        assert_eq!(term.reroll_once, vec![ComparePoint::equal(4)]);
+
+        let expr = parse("4d6min2max5").unwrap();
+        let term = dice_term(&expr);
+        assert_eq!((term.min, term.max), (Some(2), Some(5)));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        assert_eq!(error_position(parse("")), 0);
+        assert_eq!(error_position(parse("2d")), 2);
+        assert_eq!(error_position(parse("1d20 + ")), 7);
+        assert_eq!(error_position(parse("1d20 $ 3")), 5);
+        assert_eq!(error_position(parse("(1d6 + 2")), 8);
+        assert_eq!(error_position(parse("2d6kh3")), 3);
+        assert_eq!(error_position(parse("1d6r<7")), 3);
+        assert_eq!(error_position(parse("1d1!")), 3);
+        assert_eq!(error_position(parse("1d0")), 2);
+    }
+
+    #[test]
+    fn test_arithmetic_and_grouping() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(roll_with_rng("2 + 3 * 4", &mut rng).unwrap().total, 14);
+        assert_eq!(roll_with_rng("(2 + 3) * 4", &mut rng).unwrap().total, 20);
+        assert_eq!(roll_with_rng("7 / 2", &mut rng).unwrap().total, 3);
+        assert_eq!(roll_with_rng("-7 / 2", &mut rng).unwrap().total, -4);
+        assert_eq!(roll_with_rng("10 - -2", &mut rng).unwrap().total, 12);
+        assert!(roll_with_rng("1 / (2 - 2)", &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_roll_records_every_die() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let roll = roll_with_rng("4d6kh3 + 1d4 + 2", &mut rng).unwrap();
+
+        assert_eq!(roll.terms.len(), 2);
+        assert_eq!(roll.terms[0].dice.len(), 4);
+        assert_eq!(roll.terms[0].dice.iter().filter(|d| d.kept).count(), 3);
+        let lowest = roll.terms[0].dice.iter().map(|d| d.value).min().unwrap();
+        let kept_sum: i64 = roll.terms[0].dice.iter().map(|d| d.value).sum::<i64>() - lowest;
+        assert_eq!(roll.terms[0].subtotal, kept_sum);
+        assert_eq!(roll.total, roll.terms[0].subtotal + roll.terms[1].subtotal + 2);
+    }
+
+    #[test]
+    fn test_totals_stay_in_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..500 {
+            let total = roll_with_rng("2d20kh1 + 3", &mut rng).unwrap().total;
+            assert!((4..=23).contains(&total));
+            let total = roll_with_rng("4d6min3", &mut rng).unwrap().total;
+            assert!((12..=24).contains(&total));
+            let total = roll_with_rng("1d6r<3", &mut rng).unwrap().total;
+            assert!(total >= 3);
+        }
+    }
+
+    #[test]
+    fn test_exploding_dice() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut explosions = 0;
+        for _ in 0..200 {
+            let roll = roll_with_rng("1d6!", &mut rng).unwrap();
+            let die = &roll.terms[0].dice[0];
+            let (last, chain) = die.faces.split_last().unwrap();
+            assert!(chain.iter().all(|&f| f == 6));
+            assert_ne!(*last, 6);
+            assert_eq!(die.exploded, !chain.is_empty());
+            assert_eq!(die.value, die.faces.iter().map(|&f| f as i64).sum::<i64>());
+            explosions += chain.len();
+        }
+        assert!(explosions > 0);
+    }
+}
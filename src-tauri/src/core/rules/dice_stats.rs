@@ -0,0 +1,399 @@
+//! Exact probability distributions of dice expressions
+//!
+//! Works on the same AST as the roller: sums and differences are convolutions,
+//! keep/drop uses order statistics and exploding dice are expanded until the
+//! remaining probability mass is negligible.
+
+use crate::core::models::common::{DiceStatistics, ValueProbability};
+use crate::core::rules::dice::{self, apply_op, BinaryOp, DiceExpr, DiceTerm, MAX_EXPLOSIONS};
+use crate::utils::error::{DmAssistantError, DmResult};
+use std::collections::BTreeMap;
+
+/// Maximum number of distinct totals tracked for a single distribution
+pub const MAX_SUPPORT: usize = 200_000;
+/// Maximum number of elementary operations spent on a single distribution
+pub const MAX_WORK: u64 = 20_000_000;
+/// Explosion depth at which the remaining probability mass is ignored
+const EXPLOSION_TAIL_MASS: f64 = 1e-15;
+
+/// Discrete probability distribution over integer totals
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pmf: BTreeMap<i64, f64>,
+}
+
+impl Distribution {
+    /// Distribution that is always `value`
+    pub fn constant(value: i64) -> Self {
+        Self { pmf: BTreeMap::from([(value, 1.0)]) }
+    }
+
+    fn from_map(pmf: BTreeMap<i64, f64>) -> Self {
+        Self { pmf: pmf.into_iter().filter(|(_, p)| *p > 0.0).collect() }
+    }
+
+    /// Probability of each total, sorted by total
+    pub fn pmf(&self) -> impl Iterator<Item = (i64, f64)> + '_ {
+        self.pmf.iter().map(|(v, p)| (*v, *p))
+    }
+
+    /// Probability that the total is exactly `value`
+    pub fn probability(&self, value: i64) -> f64 {
+        self.pmf.get(&value).copied().unwrap_or(0.0)
+    }
+
+    /// Probability that the total is at least `value`
+    pub fn probability_at_least(&self, value: i64) -> f64 {
+        self.pmf.range(value..).map(|(_, p)| p).sum::<f64>().min(1.0)
+    }
+
+    pub fn min(&self) -> i64 {
+        self.pmf.keys().next().copied().unwrap_or(0)
+    }
+
+    pub fn max(&self) -> i64 {
+        self.pmf.keys().next_back().copied().unwrap_or(0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.pmf.iter().map(|(v, p)| *v as f64 * p).sum()
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.pmf.iter().map(|(v, p)| (*v as f64 - mean).powi(2) * p).sum()
+    }
+
+    fn map_values(&self, f: impl Fn(i64) -> i64) -> Self {
+        let mut pmf = BTreeMap::new();
+        for (v, p) in &self.pmf {
+            *pmf.entry(f(*v)).or_insert(0.0) += p;
+        }
+        Self { pmf }
+    }
+
+    /// Distribution of `f(a, b)` for independent `a` and `b`
+    fn combine(&self, other: &Self, f: impl Fn(i64, i64) -> DmResult<i64>) -> DmResult<Self> {
+        check_work(self.pmf.len() as u64 * other.pmf.len() as u64)?;
+        let mut pmf = BTreeMap::new();
+        for (a, pa) in &self.pmf {
+            for (b, pb) in &other.pmf {
+                *pmf.entry(f(*a, *b)?).or_insert(0.0) += pa * pb;
+            }
+        }
+        check_support(pmf.len())?;
+        Ok(Self { pmf })
+    }
+
+    /// Distribution of the sum of `count` independent copies
+    fn sum_of(&self, count: u32) -> DmResult<Self> {
+        let mut total = Distribution::constant(0);
+        for _ in 0..count {
+            total = total.combine(self, |a, b| apply_op(BinaryOp::Add, a, b, 0))?;
+        }
+        Ok(total)
+    }
+}
+
+/// Compute the exact distribution of a dice expression
+pub fn distribution(notation: &str) -> DmResult<Distribution> {
+    expr_distribution(&dice::parse(notation)?)
+}
+
+/// Compute summary statistics of a dice expression
+pub fn statistics(notation: &str, target: Option<i64>) -> DmResult<DiceStatistics> {
+    let dist = distribution(notation)?;
+
+    let pmf: Vec<ValueProbability> = dist.pmf()
+        .map(|(value, probability)| ValueProbability { value, probability })
+        .collect();
+
+    // Accumulate from the top so P(total >= x) is a single pass
+    let mut remaining = 0.0;
+    let mut at_least: Vec<ValueProbability> = pmf.iter().rev()
+        .map(|vp| {
+            remaining += vp.probability;
+            ValueProbability { value: vp.value, probability: remaining.min(1.0) }
+        })
+        .collect();
+    at_least.reverse();
+
+    let variance = dist.variance();
+    Ok(DiceStatistics {
+        expression: notation.trim().to_string(),
+        mean: dist.mean(),
+        variance,
+        std_dev: variance.sqrt(),
+        min: dist.min(),
+        max: dist.max(),
+        pmf,
+        at_least,
+        target,
+        target_probability: target.map(|t| dist.probability_at_least(t)),
+    })
+}
+
+/// Compute the distribution of a parsed expression
+pub fn expr_distribution(expr: &DiceExpr) -> DmResult<Distribution> {
+    match expr {
+        DiceExpr::Number(n) => Ok(Distribution::constant(*n)),
+        DiceExpr::Dice(term) => term_distribution(term),
+        DiceExpr::Negate(inner) => Ok(expr_distribution(inner)?.map_values(|v| -v)),
+        DiceExpr::Group(inner) => expr_distribution(inner),
+        DiceExpr::Binary { op, left, right, position } => {
+            let lhs = expr_distribution(left)?;
+            let rhs = expr_distribution(right)?;
+            if *op == BinaryOp::Divide && rhs.probability(0) > 0.0 {
+                return Err(DmAssistantError::dice("Division by zero", *position));
+            }
+            lhs.combine(&rhs, |a, b| apply_op(*op, a, b, *position))
+        }
+    }
+}
+
+/// Distribution of a dice term, including keep/drop
+pub fn term_distribution(term: &DiceTerm) -> DmResult<Distribution> {
+    let die = die_distribution(term)?;
+    match term.keep {
+        None => {
+            // Convolving n dice costs roughly n^2 * support^2 / 2 operations
+            let support = die.pmf.len() as u64;
+            check_work((term.count as u64).pow(2) * support.pow(2) / 2)?;
+            die.sum_of(term.count)
+        }
+        Some(rule) => {
+            let (highest, keep) = rule.kept(term.count);
+            kept_sum(&die, term.count, keep, highest)
+        }
+    }
+}
+
+/// Distribution of a single die of the term, after rerolls, explosions and clamps
+pub fn die_distribution(term: &DiceTerm) -> DmResult<Distribution> {
+    let sides = term.sides as i64;
+    let uniform = 1.0 / sides as f64;
+
+    // Reroll-until: the result is uniform over the faces that don't match
+    let allowed: Vec<i64> = (1..=sides).filter(|f| !term.rerolls_until(*f)).collect();
+    let mut faces: BTreeMap<i64, f64> = allowed.iter()
+        .map(|f| (*f, 1.0 / allowed.len() as f64))
+        .collect();
+
+    // Reroll-once: matching faces are replaced by a fresh uniform roll
+    let rerolled_mass: f64 = faces.iter()
+        .filter(|(f, _)| term.rerolls_once(**f))
+        .map(|(_, p)| p)
+        .sum();
+    if rerolled_mass > 0.0 {
+        faces.retain(|f, _| !term.rerolls_once(*f));
+        for f in 1..=sides {
+            *faces.entry(f).or_insert(0.0) += rerolled_mass * uniform;
+        }
+    }
+
+    let die = if term.explode.is_some() {
+        // Chain of raw rolls after an explosion, built from the deepest level up
+        let exploding = (1..=sides).filter(|f| term.explodes_on(*f)).count() as f64;
+        let depth = explosion_depth(exploding * uniform);
+        check_work(depth as u64 * (sides as u64).pow(2) * depth as u64)?;
+
+        let mut chain = Distribution::from_map((1..=sides).map(|f| (f, uniform)).collect());
+        for _ in 1..depth {
+            chain = explode_once(term, &faces_uniform(sides), &chain);
+        }
+        explode_once(term, &faces, &chain)
+    } else {
+        Distribution::from_map(faces)
+    };
+
+    Ok(die.map_values(|v| term.clamp(v)))
+}
+
+fn faces_uniform(sides: i64) -> BTreeMap<i64, f64> {
+    (1..=sides).map(|f| (f, 1.0 / sides as f64)).collect()
+}
+
+/// Combine a face distribution with the chain rolled when a face explodes
+fn explode_once(term: &DiceTerm, faces: &BTreeMap<i64, f64>, chain: &Distribution) -> Distribution {
+    let mut pmf = BTreeMap::new();
+    for (f, p) in faces {
+        if term.explodes_on(*f) {
+            for (v, pv) in chain.pmf() {
+                *pmf.entry(f + v).or_insert(0.0) += p * pv;
+            }
+        } else {
+            *pmf.entry(*f).or_insert(0.0) += p;
+        }
+    }
+    Distribution::from_map(pmf)
+}
+
+/// Number of explosions to expand before the tail mass is negligible
+fn explosion_depth(explode_probability: f64) -> usize {
+    let mut depth = 1;
+    let mut mass = explode_probability;
+    while mass > EXPLOSION_TAIL_MASS && depth < MAX_EXPLOSIONS {
+        mass *= explode_probability;
+        depth += 1;
+    }
+    depth
+}
+
+/// Distribution of the sum of the `keep` highest (or lowest) of `count` dice.
+///
+/// Walks the die values from best to worst, deciding how many dice land on
+/// each value; the first `keep` dice assigned are the kept ones.
+fn kept_sum(die: &Distribution, count: u32, keep: u32, highest: bool) -> DmResult<Distribution> {
+    let n = count as usize;
+    let k = keep as usize;
+    let mut values: Vec<(i64, f64)> = die.pmf().collect();
+    if highest {
+        values.reverse();
+    }
+    check_work(values.len() as u64 * (n as u64 + 1).pow(2) * (k as u64 * values.len() as u64 + 1))?;
+
+    let binomial = binomial_table(n);
+    // assigned[j] = distribution of the kept sum with j dice assigned so far
+    let mut assigned: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); n + 1];
+    assigned[0].insert(0, 1.0);
+
+    for (value, p) in values {
+        let mut next: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); n + 1];
+        for (j, sums) in assigned.iter().enumerate() {
+            for (sum, prob) in sums {
+                for c in 0..=(n - j) {
+                    let weight = binomial[n - j][c] * p.powi(c as i32);
+                    if weight == 0.0 {
+                        continue;
+                    }
+                    let kept = ((j + c).min(k) - j.min(k)) as i64;
+                    *next[j + c].entry(sum + kept * value).or_insert(0.0) += prob * weight;
+                }
+            }
+        }
+        assigned = next;
+    }
+
+    let result = Distribution::from_map(assigned.pop().unwrap_or_default());
+    check_support(result.pmf.len())?;
+    Ok(result)
+}
+
+fn binomial_table(n: usize) -> Vec<Vec<f64>> {
+    let mut table = vec![vec![0.0; n + 1]; n + 1];
+    for i in 0..=n {
+        table[i][0] = 1.0;
+        for j in 1..=i {
+            table[i][j] = table[i - 1][j - 1] + if j < i { table[i - 1][j] } else { 0.0 };
+        }
+    }
+    table
+}
+
+fn check_work(work: u64) -> DmResult<()> {
+    if work > MAX_WORK {
+        Err(DmAssistantError::invalid_input("Dice expression is too large for exact statistics"))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_support(size: usize) -> DmResult<()> {
+    if size > MAX_SUPPORT {
+        Err(DmAssistantError::invalid_input("Dice expression is too large for exact statistics"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    fn total_probability(dist: &Distribution) -> f64 {
+        dist.pmf().map(|(_, p)| p).sum()
+    }
+
+    #[test]
+    fn test_single_die_and_sums() {
+        let d6 = distribution("1d6").unwrap();
+        assert_close(d6.mean(), 3.5);
+        assert_close(d6.variance(), 35.0 / 12.0);
+
+        let two_d6 = distribution("2d6 + 3").unwrap();
+        assert_eq!((two_d6.min(), two_d6.max()), (5, 15));
+        assert_close(two_d6.probability(10), 6.0 / 36.0);
+        assert_close(two_d6.mean(), 10.0);
+    }
+
+    #[test]
+    fn test_keep_and_drop() {
+        let stats = distribution("2d20kh1").unwrap();
+        assert_close(stats.mean(), 13.825);
+        assert_close(distribution("2d20kl1").unwrap().probability_at_least(11), 0.25);
+
+        let ability = distribution("4d6kh3").unwrap();
+        assert_close(ability.mean(), 15869.0 / 1296.0);
+        assert_close(total_probability(&ability), 1.0);
+        assert_eq!(ability, distribution("4d6dl1").unwrap());
+        assert_eq!(distribution("3d6kl2").unwrap(), distribution("3d6dh1").unwrap());
+    }
+
+    #[test]
+    fn test_rerolls_and_clamps() {
+        assert_close(distribution("1d6r1").unwrap().mean(), 4.0);
+        assert_close(distribution("1d6ro1").unwrap().mean(), 3.5 + (3.5 - 1.0) / 6.0);
+        assert_close(distribution("1d6min3").unwrap().mean(), 4.0);
+        assert_close(distribution("1d6max4").unwrap().mean(), 3.0);
+    }
+
+    #[test]
+    fn test_exploding_dice() {
+        let dist = distribution("1d6!").unwrap();
+        assert_close(dist.mean(), 4.2);
+        assert_close(total_probability(&dist), 1.0);
+        assert_eq!(dist.probability(6), 0.0);
+        assert_close(dist.probability(7), 1.0 / 36.0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_close(distribution("1d6 * 2").unwrap().mean(), 7.0);
+        assert_close(distribution("-1d4").unwrap().mean(), -2.5);
+        let halved = distribution("1d4 / 2").unwrap();
+        assert_close(halved.probability(0), 0.25);
+        assert_close(halved.probability(2), 0.25);
+        assert!(distribution("1d6 / (1d2 - 1)").is_err());
+    }
+
+    #[test]
+    fn test_statistics_report() {
+        let stats = statistics("2d6", Some(8)).unwrap();
+        assert_eq!(stats.pmf.len(), 11);
+        assert_eq!(stats.at_least.first().unwrap().value, 2);
+        assert_close(stats.at_least.first().unwrap().probability, 1.0);
+        assert_close(stats.target_probability.unwrap(), 15.0 / 36.0);
+        assert!(matches!(statistics("2d", None), Err(DmAssistantError::DiceError { .. })));
+        assert!(statistics("1000d10000", None).is_err());
+    }
+
+    #[test]
+    fn test_matches_sampled_rolls() {
+        let expression = "3d6!kh2 + 1d4ro1 - 1";
+        let expected = distribution(expression).unwrap().mean();
+
+        let mut rng = StdRng::seed_from_u64(2024);
+        let samples = 20_000;
+        let total: i64 = (0..samples)
+            .map(|_| dice::roll_with_rng(expression, &mut rng).unwrap().total)
+            .sum();
+        let sampled = total as f64 / samples as f64;
+        assert!((sampled - expected).abs() < 0.15, "sampled {} vs exact {}", sampled, expected);
+    }
+}
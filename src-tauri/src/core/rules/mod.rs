@@ -0,0 +1,5 @@
+pub mod ability_scores;
+pub mod challenge_rating;
+pub mod checks;
+pub mod dice;
+pub mod dice_stats;
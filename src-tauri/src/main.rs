@@ -11,11 +11,44 @@ use tauri::Manager;
 fn main() {
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
+            // Campaign commands
+            commands::campaigns::create_campaign,
+            commands::campaigns::get_campaign,
+            commands::campaigns::list_campaigns,
+            commands::campaigns::update_campaign,
+            commands::campaigns::delete_campaign,
+            commands::campaigns::archive_campaign,
+            commands::campaigns::restore_campaign,
+            commands::campaigns::duplicate_campaign,
+            commands::campaigns::get_campaign_storage_backend,
+            commands::campaigns::convert_campaign_storage,
+            commands::campaigns::set_active_campaign,
+            commands::campaigns::get_active_campaign,
+            commands::campaigns::start_session,
+            // Backup commands
+            commands::backups::create_backup,
+            commands::backups::list_backups,
+            commands::backups::preview_restore,
+            commands::backups::restore_backup,
+            commands::backups::prune_backups,
+            commands::backups::get_backup_retention,
+            commands::backups::set_backup_retention,
+            // Import/export commands
+            commands::import_export::export_campaign,
+            commands::import_export::inspect_campaign_archive,
+            commands::import_export::import_campaign,
+            commands::import_export::import_bestiary,
+            // History commands
+            commands::history::undo,
+            commands::history::redo,
+            commands::history::list_history,
             // Character commands
             commands::characters::create_character,
+            commands::characters::get_character,
             commands::characters::get_character_with_campaign,
             commands::characters::get_characters_by_campaign,
             commands::characters::get_active_characters_by_campaign,
+            commands::characters::update_character,
             commands::characters::update_character_with_campaign,
             commands::characters::delete_character,
             commands::characters::add_character_achievement,
@@ -28,6 +61,58 @@ fn main() {
             commands::characters::save_characters_to_file,
             commands::characters::load_characters_from_file,
             commands::characters::characters_file_exists,
+            // NPC commands
+            commands::npcs::create_npc,
+            commands::npcs::get_npc,
+            commands::npcs::get_npcs_by_campaign,
+            commands::npcs::update_npc,
+            commands::npcs::delete_npc,
+            commands::npcs::log_npc_interaction,
+            // Monster commands
+            commands::monsters::create_monster,
+            commands::monsters::get_monster,
+            commands::monsters::get_monsters_by_campaign,
+            commands::monsters::update_monster,
+            commands::monsters::delete_monster,
+            commands::monsters::get_monster_stats,
+            commands::monsters::calculate_challenge_rating,
+            commands::monsters::scale_monster,
+            // Spell commands
+            commands::spells::create_spell,
+            commands::spells::get_spell,
+            commands::spells::get_spells_by_campaign,
+            commands::spells::update_spell,
+            commands::spells::delete_spell,
+            // Item commands
+            commands::items::create_item,
+            commands::items::get_item,
+            commands::items::get_items_by_campaign,
+            commands::items::update_item,
+            commands::items::delete_item,
+            // Compendium commands
+            commands::compendium::list_content_packs,
+            commands::compendium::search_compendium,
+            commands::compendium::get_compendium_entry,
+            commands::compendium::copy_compendium_entry,
+            // Relationship graph commands
+            commands::relationships::get_relationship_graph,
+            commands::relationships::add_relationship_edge,
+            commands::relationships::update_relationship_edge,
+            commands::relationships::remove_relationship_edge,
+            commands::relationships::get_node_neighborhood,
+            commands::relationships::find_relationship_path,
+            commands::relationships::export_relationship_graph_dot,
+            // Dice commands
+            commands::dice::roll_dice,
+            commands::dice::get_roll_history,
+            commands::dice::verify_roll,
+            commands::dice::dice_statistics,
+            // System commands
+            commands::system::rebuild_entity_index,
+            commands::system::resolve_entity,
+            commands::system::migrate_campaigns,
+            commands::system::get_storage_stats,
+            commands::system::clear_storage_cache,
         ])
         .setup(|app| {
             // App setup logic
@@ -40,6 +125,24 @@ fn main() {
             }
             
             println!("File system initialized");
+
+            // Send entity changes to the frontend
+            services::events::EventService::subscribe(std::sync::Arc::new(
+                services::events::TauriEmitter::new(app.app_handle().clone()),
+            ));
+            
+            // Bring files written by older versions to the current schema
+            match services::migrations::MigrationService::migrate_all() {
+                Ok(report) => {
+                    for migration in &report.migrated {
+                        println!("Migrated {} from schema {} to {}", migration.path, migration.from_version, migration.to_version);
+                    }
+                    for failure in &report.failures {
+                        eprintln!("Failed to migrate {}: {}", failure.path, failure.error);
+                    }
+                }
+                Err(e) => eprintln!("Failed to migrate campaign files: {}", e),
+            }
             
             // Future: Load configuration, initialize services, etc.
             
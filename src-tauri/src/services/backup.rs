@@ -0,0 +1,595 @@
+// src-tauri/src/services/backup.rs
+
+use crate::core::models::backup::{
+    BackupInfo, BackupReason, EntityRestoreChange, FileRestoreChange, RestoreChange, RestorePreview,
+    RestoreResult, RetentionPolicy,
+};
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::core::models::event::ReloadReason;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::entity_index::EntityIndexService;
+use crate::services::events::EventService;
+use crate::services::storage::CampaignStore;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{
+    dir_exists, ensure_dir_exists, file_exists, get_app_data_dir, is_storage_artifact, list_files_recursive,
+    portable_path,
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const ARCHIVE_EXTENSION: &str = "zip";
+
+/// Archive entry describing the backup
+const MANIFEST_ENTRY: &str = "backup.json";
+
+/// Archive directory holding the campaign files
+const FILES_PREFIX: &str = "campaign/";
+
+/// Manifest stored in every backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    #[serde(alias = "schema_version")] // Key of the backups made before schema version 2
+    schema_version: u32,
+    campaign_id: Uuid,
+    campaign_name: String,
+    reason: BackupReason,
+    session_number: Option<u32>,
+    created_at: DateTime<Utc>,
+    files: Vec<String>,
+}
+
+/// Files of a campaign, by path relative to the campaign directory
+type FileContents = BTreeMap<String, Vec<u8>>;
+
+/// Which backups a policy keeps.
+/// `created` holds the creation times of the backups, newest first.
+pub fn retained(created: &[DateTime<Utc>], policy: &RetentionPolicy, now: DateTime<Utc>) -> Vec<bool> {
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+
+    created.iter().enumerate().map(|(i, time)| {
+        let age = now - *time;
+        // Newest first, so the first backup seen in a day or week is its newest
+        let recent = i < policy.keep_last;
+        let daily = age < Duration::days(policy.daily_days as i64) && days.insert(time.date_naive());
+        let iso_week = time.iso_week();
+        let weekly = age < Duration::weeks(policy.weekly_weeks as i64)
+            && weeks.insert((iso_week.year(), iso_week.week()));
+        recent || daily || weekly
+    }).collect()
+}
+
+/// Compressed snapshots of whole campaigns, in `backups/campaigns/<campaign_id>/`
+pub struct BackupService;
+
+impl BackupService {
+    /// Directory holding the backups of a campaign
+    fn backups_dir(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        Ok(get_app_data_dir()?.join("backups").join("campaigns").join(campaign_id.to_string()))
+    }
+
+    /// Path of a backup archive, rejecting IDs that would point outside the backups directory
+    fn archive_path(campaign_id: &Uuid, backup_id: &str) -> DmResult<PathBuf> {
+        let valid = !backup_id.is_empty()
+            && backup_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(DmAssistantError::validation(&format!("Invalid backup ID '{}'", backup_id)));
+        }
+
+        let path = Self::backups_dir(campaign_id)?.join(format!("{}.{}", backup_id, ARCHIVE_EXTENSION));
+        if !file_exists(&path) {
+            return Err(DmAssistantError::not_found("Backup", backup_id));
+        }
+        Ok(path)
+    }
+
+    /// Get the retention policy
+    pub fn retention_policy() -> DmResult<RetentionPolicy> {
+        Ok(CampaignStorageService::load_app_state()?.backup_retention)
+    }
+
+    /// Set the retention policy. It's applied to each campaign the next time it's backed up.
+    pub fn set_retention_policy(policy: RetentionPolicy) -> DmResult<RetentionPolicy> {
+        if policy.keep_last == 0 {
+            return Err(DmAssistantError::validation("At least the most recent backup must be kept"));
+        }
+
+        let mut state = CampaignStorageService::load_app_state()?;
+        state.backup_retention = policy;
+        CampaignStorageService::save_app_state(&state)?;
+        Ok(policy)
+    }
+
+    /// Snapshot a campaign into a compressed archive, then apply the retention policy
+    pub fn create_backup(campaign_id: &Uuid, reason: BackupReason) -> DmResult<BackupInfo> {
+        let _lock = lock_campaign(campaign_id)?;
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        if !dir_exists(&campaign_dir) {
+            return Err(DmAssistantError::not_found("Campaign", &campaign_id.to_string()));
+        }
+
+        // A campaign with a damaged manifest can still be backed up
+        let campaign = CampaignStorageService::get_campaign(campaign_id).ok().flatten();
+        let created_at = Utc::now();
+        let files: Vec<String> = list_files_recursive(&campaign_dir)?
+            .into_iter()
+            .filter(|relative| !is_storage_artifact(relative))
+            .map(|relative| portable_path(&relative))
+            .collect();
+        let manifest = BackupManifest {
+            schema_version: SCHEMA_VERSION,
+            campaign_id: *campaign_id,
+            campaign_name: campaign.as_ref().map(|c| c.name.clone()).unwrap_or_default(),
+            reason,
+            session_number: campaign.as_ref().map(|c| c.current_session),
+            created_at,
+            files,
+        };
+
+        let backups_dir = Self::backups_dir(campaign_id)?;
+        ensure_dir_exists(&backups_dir)?;
+        let archive_path = Self::new_archive_path(&backups_dir, created_at, reason);
+        Self::write_archive(&archive_path, &campaign_dir, &manifest)?;
+
+        let info = Self::read_info(&archive_path)?;
+        Self::prune(campaign_id)?;
+        Ok(info)
+    }
+
+    /// Archive path not used by any other backup
+    fn new_archive_path(backups_dir: &Path, created_at: DateTime<Utc>, reason: BackupReason) -> PathBuf {
+        let stem = format!("{}_{}", created_at.format("%Y%m%d_%H%M%S%3f"), reason.tag());
+        let mut path = backups_dir.join(format!("{}.{}", stem, ARCHIVE_EXTENSION));
+        let mut counter = 1;
+        while path.exists() {
+            path = backups_dir.join(format!("{}-{}.{}", stem, counter, ARCHIVE_EXTENSION));
+            counter += 1;
+        }
+        path
+    }
+
+    /// Write the archive to a temporary file and move it into place once complete
+    fn write_archive(archive_path: &Path, campaign_dir: &Path, manifest: &BackupManifest) -> DmResult<()> {
+        let parent = archive_path.parent().unwrap_or(Path::new("."));
+        let temp_file = tempfile::NamedTempFile::new_in(parent)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to create backup file: {}", e)))?;
+        let archive_error = |e: zip::result::ZipError| DmAssistantError::storage(&format!("Failed to write backup: {}", e));
+        let io_error = |e: std::io::Error| DmAssistantError::storage(&format!("Failed to write backup: {}", e));
+
+        let mut zip = ZipWriter::new(temp_file.reopen().map_err(io_error)?);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file(MANIFEST_ENTRY, options).map_err(archive_error)?;
+        zip.write_all(&serde_json::to_vec_pretty(manifest)?).map_err(io_error)?;
+        for name in &manifest.files {
+            let data = fs::read(campaign_dir.join(name))
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read {}: {}", name, e)))?;
+            zip.start_file(format!("{}{}", FILES_PREFIX, name), options).map_err(archive_error)?;
+            zip.write_all(&data).map_err(io_error)?;
+        }
+        zip.finish().map_err(archive_error)?.sync_all().map_err(io_error)?;
+
+        temp_file.persist(archive_path)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to write backup: {}", e.error)))?;
+        Ok(())
+    }
+
+    fn open_archive(archive_path: &Path) -> DmResult<ZipArchive<File>> {
+        let file = File::open(archive_path)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to open backup: {}", e)))?;
+        ZipArchive::new(file)
+            .map_err(|e| DmAssistantError::storage(&format!("Backup {} is damaged: {}", archive_path.display(), e)))
+    }
+
+    fn read_manifest(archive: &mut ZipArchive<File>) -> DmResult<BackupManifest> {
+        let entry = archive.by_name(MANIFEST_ENTRY)
+            .map_err(|e| DmAssistantError::storage(&format!("Backup has no manifest: {}", e)))?;
+        serde_json::from_reader(entry)
+            .map_err(|e| DmAssistantError::json(&format!("Invalid backup manifest: {}", e)))
+    }
+
+    /// Describe a backup archive
+    fn read_info(archive_path: &Path) -> DmResult<BackupInfo> {
+        let manifest = Self::read_manifest(&mut Self::open_archive(archive_path)?)?;
+        let size_bytes = fs::metadata(archive_path)
+            .map(|metadata| metadata.len())
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read backup: {}", e)))?;
+
+        Ok(BackupInfo {
+            id: archive_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+            campaign_id: manifest.campaign_id,
+            campaign_name: manifest.campaign_name,
+            reason: manifest.reason,
+            session_number: manifest.session_number,
+            created_at: manifest.created_at,
+            size_bytes,
+            file_count: manifest.files.len(),
+            path: archive_path.display().to_string(),
+        })
+    }
+
+    /// Read the campaign files stored in an archive
+    fn read_files(archive_path: &Path) -> DmResult<FileContents> {
+        let mut archive = Self::open_archive(archive_path)?;
+        let mut files = FileContents::new();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read backup: {}", e)))?;
+            // Never trust paths from an archive to stay inside the campaign directory
+            let Some(relative) = entry.enclosed_name().and_then(|path| {
+                path.strip_prefix(FILES_PREFIX.trim_end_matches('/')).ok().map(portable_path)
+            }) else {
+                continue;
+            };
+            if entry.is_dir() {
+                continue;
+            }
+
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read backup: {}", e)))?;
+            files.insert(relative, data);
+        }
+        Ok(files)
+    }
+
+    /// Read the current files of a campaign
+    fn read_campaign_files(campaign_dir: &Path) -> DmResult<FileContents> {
+        let mut files = FileContents::new();
+        for relative in list_files_recursive(campaign_dir)? {
+            if is_storage_artifact(&relative) {
+                continue;
+            }
+            let data = fs::read(campaign_dir.join(&relative))
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read file: {}", e)))?;
+            files.insert(portable_path(&relative), data);
+        }
+        Ok(files)
+    }
+
+    /// List the backups of a campaign, or of every campaign (including deleted ones), newest first
+    pub fn list_backups(campaign_id: Option<&Uuid>) -> DmResult<Vec<BackupInfo>> {
+        let dirs = match campaign_id {
+            Some(campaign_id) => vec![Self::backups_dir(campaign_id)?],
+            None => {
+                let root = get_app_data_dir()?.join("backups").join("campaigns");
+                if !dir_exists(&root) {
+                    return Ok(Vec::new());
+                }
+                fs::read_dir(&root)
+                    .map_err(|e| DmAssistantError::storage(&format!("Failed to read backups directory: {}", e)))?
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| {
+                        path.is_dir()
+                            && path.file_name().and_then(|n| n.to_str()).is_some_and(|n| Uuid::parse_str(n).is_ok())
+                    })
+                    .collect()
+            }
+        };
+
+        let mut backups = Vec::new();
+        for dir in dirs {
+            if !dir_exists(&dir) {
+                continue;
+            }
+            let entries = fs::read_dir(&dir)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read backups directory: {}", e)))?;
+            for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+                if path.extension().is_none_or(|ext| ext != ARCHIVE_EXTENSION) {
+                    continue;
+                }
+                match Self::read_info(&path) {
+                    Ok(info) => backups.push(info),
+                    // One damaged archive shouldn't hide the others
+                    Err(e) => eprintln!("Skipping unreadable backup {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(backups)
+    }
+
+    /// Delete the backups of a campaign the retention policy doesn't keep
+    pub fn prune(campaign_id: &Uuid) -> DmResult<Vec<BackupInfo>> {
+        let policy = Self::retention_policy()?;
+        let backups = Self::list_backups(Some(campaign_id))?;
+        let created: Vec<DateTime<Utc>> = backups.iter().map(|b| b.created_at).collect();
+        let keep = retained(&created, &policy, Utc::now());
+
+        let mut removed = Vec::new();
+        for (backup, keep) in backups.into_iter().zip(keep) {
+            if keep {
+                continue;
+            }
+            fs::remove_file(&backup.path)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to remove backup: {}", e)))?;
+            removed.push(backup);
+        }
+        Ok(removed)
+    }
+
+    /// Show what restoring a backup would change, without changing anything
+    pub fn preview_restore(campaign_id: &Uuid, backup_id: &str) -> DmResult<RestorePreview> {
+        let archive_path = Self::archive_path(campaign_id, backup_id)?;
+        let backup = Self::read_info(&archive_path)?;
+        let restored = Self::read_files(&archive_path)?;
+        let current = Self::read_campaign_files(&CampaignStorageService::get_campaign_dir_path(campaign_id)?)?;
+        Ok(Self::compare(backup, &current, &restored))
+    }
+
+    /// Changes between the current files and the restored ones
+    fn compare(backup: BackupInfo, current: &FileContents, restored: &FileContents) -> RestorePreview {
+        let mut files = Vec::new();
+        let mut entities = Vec::new();
+        let mut unchanged_files = 0;
+
+        let paths: std::collections::BTreeSet<&String> = current.keys().chain(restored.keys()).collect();
+        for path in paths {
+            let change = match (current.get(path), restored.get(path)) {
+                (None, Some(_)) => RestoreChange::Added,
+                (Some(_), None) => RestoreChange::Removed,
+                (Some(before), Some(after)) if before != after => RestoreChange::Modified,
+                _ => {
+                    unchanged_files += 1;
+                    continue;
+                }
+            };
+            files.push(FileRestoreChange { path: path.clone(), change });
+            entities.extend(Self::compare_collection(path, current.get(path), restored.get(path)));
+        }
+
+        RestorePreview { backup, files, entities, unchanged_files }
+    }
+
+    /// Entity changes in a collection file: a top-level `<name>.json` holding
+    /// its entities under `<name>`, keyed by ID
+    fn compare_collection(path: &str, before: Option<&Vec<u8>>, after: Option<&Vec<u8>>) -> Vec<EntityRestoreChange> {
+        let Some(collection) = path.strip_suffix(".json").filter(|name| !name.contains('/')) else {
+            return Vec::new();
+        };
+        let entities_of = |data: Option<&Vec<u8>>| -> BTreeMap<Uuid, Value> {
+            data.and_then(|data| serde_json::from_slice::<Value>(data).ok())
+                .and_then(|document| match document.get(collection) {
+                    Some(Value::Object(map)) => Some(map.iter()
+                        .filter_map(|(key, value)| Some((Uuid::parse_str(key).ok()?, value.clone())))
+                        .collect()),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
+        let before = entities_of(before);
+        let after = entities_of(after);
+
+        let ids: std::collections::BTreeSet<&Uuid> = before.keys().chain(after.keys()).collect();
+        ids.into_iter().filter_map(|id| {
+            let (change, entity) = match (before.get(id), after.get(id)) {
+                (None, Some(entity)) => (RestoreChange::Added, entity),
+                (Some(entity), None) => (RestoreChange::Removed, entity),
+                (Some(old), Some(new)) if old != new => (RestoreChange::Modified, new),
+                _ => return None,
+            };
+            Some(EntityRestoreChange {
+                collection: collection.to_string(),
+                id: *id,
+                name: entity.get("name").and_then(Value::as_str).map(str::to_string),
+                change,
+            })
+        }).collect()
+    }
+
+    /// Restore a campaign to a backup.
+    ///
+    /// The current state is backed up first, so a restore can itself be undone.
+    /// Deleted campaigns can be restored from their last backup too.
+    pub fn restore_backup(campaign_id: &Uuid, backup_id: &str) -> DmResult<RestoreResult> {
+        let _lock = lock_campaign(campaign_id)?;
+        let changes = Self::preview_restore(campaign_id, backup_id)?;
+        let restored = Self::read_files(&Self::archive_path(campaign_id, backup_id)?)?;
+
+        // Unpack next to the campaigns first: a failure leaves the campaign untouched
+        let tmp_dir = get_app_data_dir()?.join("tmp");
+        let staging_dir = tmp_dir.join(format!("restore-{}", Uuid::new_v4()));
+        let staged = (|| -> DmResult<()> {
+            ensure_dir_exists(&staging_dir)?;
+            for (relative, data) in &restored {
+                let target = staging_dir.join(relative);
+                if let Some(parent) = target.parent() {
+                    ensure_dir_exists(parent)?;
+                }
+                fs::write(&target, data)
+                    .map_err(|e| DmAssistantError::storage(&format!("Failed to restore {}: {}", relative, e)))?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = staged {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        let safety_backup = if dir_exists(&campaign_dir) {
+            match Self::create_backup(campaign_id, BackupReason::BeforeRestore) {
+                Ok(backup) => Some(backup),
+                Err(e) => {
+                    let _ = fs::remove_dir_all(&staging_dir);
+                    return Err(e);
+                }
+            }
+        } else {
+            None
+        };
+
+        // Swap the directories, putting the old one back if the swap fails
+        let old_dir = tmp_dir.join(format!("replaced-{}", Uuid::new_v4()));
+        if dir_exists(&campaign_dir) {
+            fs::rename(&campaign_dir, &old_dir)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to replace campaign: {}", e)))?;
+        }
+        if let Err(e) = fs::rename(&staging_dir, &campaign_dir) {
+            if dir_exists(&old_dir) {
+                let _ = fs::rename(&old_dir, &campaign_dir);
+            }
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(DmAssistantError::storage(&format!("Failed to restore campaign: {}", e)));
+        }
+        if dir_exists(&old_dir) {
+            let _ = fs::remove_dir_all(&old_dir);
+        }
+
+        CampaignStore::global().invalidate_dir(&campaign_dir);
+        EntityIndexService::unregister_campaign(campaign_id)?;
+        EntityIndexService::register_campaign(campaign_id)?;
+        EventService::campaign_reloaded(campaign_id, ReloadReason::Restored);
+
+        Ok(RestoreResult { changes, safety_backup })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, PlayerCharacter};
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::utils::test_support::{create_test_campaign, MockEmitter};
+    use chrono::TimeZone;
+
+    fn new_character(campaign_id: Uuid, name: &str) -> PlayerCharacter {
+        CharacterStorageService::create_character(PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: "Halfling".to_string(),
+            class: "Rogue".to_string(),
+            level: 1,
+            max_hp: 9,
+            background: String::new(),
+            notes: None,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_retention_policy() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 28, 12, 0, 0).unwrap();
+        let policy = RetentionPolicy { keep_last: 2, daily_days: 7, weekly_weeks: 4 };
+        let created = vec![
+            now - Duration::hours(1),  // Kept: last
+            now - Duration::hours(2),  // Kept: last
+            now - Duration::hours(3),  // Same day as the newest: dropped
+            now - Duration::days(1),   // Kept: daily
+            now - Duration::days(1) - Duration::hours(1), // Dropped
+            now - Duration::days(10),  // Kept: weekly (Tuesday 18 June, week 25)
+            now - Duration::days(11),  // Same week: dropped
+            now - Duration::days(40),  // Too old: dropped
+        ];
+
+        assert_eq!(
+            retained(&created, &policy, now),
+            vec![true, true, false, true, false, true, false, false]
+        );
+
+        // Keeping many recent backups keeps everything
+        let keep_all = RetentionPolicy { keep_last: 100, ..policy };
+        assert!(retained(&created, &keep_all, now).into_iter().all(|keep| keep));
+    }
+
+    #[test]
+    fn test_backup_preview_and_restore() {
+        let campaign_id = create_test_campaign();
+        let kept = new_character(campaign_id, "Pip");
+        let backup = BackupService::create_backup(&campaign_id, BackupReason::Manual).unwrap();
+        assert_eq!(backup.campaign_name, "Test Campaign");
+        assert_eq!(backup.file_count, 3); // Manifest, characters and history
+        assert!(backup.size_bytes > 0);
+        assert!(backup.path.ends_with("_manual.zip"));
+
+        // Change the campaign after the backup
+        let added = new_character(campaign_id, "Tam");
+        CharacterStorageService::modify_character(&campaign_id, &kept.id, |c| {
+            c.level = 2;
+            Ok(())
+        }).unwrap();
+
+        let preview = BackupService::preview_restore(&campaign_id, &backup.id).unwrap();
+        let changed: Vec<&str> = preview.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(changed, vec!["characters.json", "history.json"]);
+        assert!(preview.files.iter().all(|f| f.change == RestoreChange::Modified));
+        assert_eq!(preview.unchanged_files, 1);
+        let change_of = |id: Uuid| preview.entities.iter().find(|e| e.id == id).map(|e| e.change);
+        assert_eq!(change_of(kept.id), Some(RestoreChange::Modified));
+        assert_eq!(change_of(added.id), Some(RestoreChange::Removed));
+
+        // Previewing changes nothing
+        assert_eq!(CharacterStorageService::get_characters_by_campaign(&campaign_id).unwrap().len(), 2);
+
+        let result = BackupService::restore_backup(&campaign_id, &backup.id).unwrap();
+        assert_eq!(result.safety_backup.as_ref().unwrap().reason, BackupReason::BeforeRestore);
+        let characters = CharacterStorageService::get_characters_by_campaign(&campaign_id).unwrap();
+        assert_eq!(characters.len(), 1);
+        assert_eq!(characters[0].level, 1);
+        assert!(CharacterStorageService::find_character(&added.id).unwrap().is_none());
+
+        // The restore can be undone from the safety backup
+        let safety = result.safety_backup.unwrap();
+        BackupService::restore_backup(&campaign_id, &safety.id).unwrap();
+        assert_eq!(CharacterStorageService::find_character(&added.id).unwrap().unwrap().name, "Tam");
+
+        let listed = BackupService::list_backups(Some(&campaign_id)).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert!(listed.windows(2).all(|pair| pair[0].created_at >= pair[1].created_at));
+    }
+
+    #[test]
+    fn test_restore_reloads_the_campaign() {
+        let campaign_id = create_test_campaign();
+        new_character(campaign_id, "Merric");
+        let backup = BackupService::create_backup(&campaign_id, BackupReason::Manual).unwrap();
+        new_character(campaign_id, "Jillian");
+
+        let events = MockEmitter::subscribe(campaign_id);
+        BackupService::restore_backup(&campaign_id, &backup.id).unwrap();
+        let reloads = events.take_reloads();
+        assert_eq!(reloads.len(), 1);
+        assert_eq!(reloads[0].reason, ReloadReason::Restored);
+        // The whole campaign is reloaded, entity by entity events aren't sent
+        assert!(events.take().is_empty());
+    }
+
+    #[test]
+    fn test_restore_deleted_campaign() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Wren");
+        let archive_path = CampaignStorageService::delete_campaign(&campaign_id).unwrap();
+        assert!(!CampaignStorageService::campaign_exists(&campaign_id));
+
+        let backup = BackupService::list_backups(None).unwrap()
+            .into_iter()
+            .find(|b| b.campaign_id == campaign_id)
+            .unwrap();
+        assert_eq!(backup.reason, BackupReason::BeforeDelete);
+        assert_eq!(PathBuf::from(&backup.path), archive_path);
+
+        let result = BackupService::restore_backup(&campaign_id, &backup.id).unwrap();
+        assert!(result.safety_backup.is_none());
+        assert!(result.changes.files.iter().all(|f| f.change == RestoreChange::Added));
+        assert!(CampaignStorageService::campaign_exists(&campaign_id));
+        assert_eq!(CharacterStorageService::find_character(&character.id).unwrap().unwrap().campaign_id, campaign_id);
+    }
+
+    #[test]
+    fn test_invalid_backup_ids() {
+        let campaign_id = create_test_campaign();
+        assert!(matches!(BackupService::preview_restore(&campaign_id, "../../app_state"), Err(DmAssistantError::ValidationError(_))));
+        assert!(matches!(BackupService::preview_restore(&campaign_id, "20240101_000000000_manual"), Err(DmAssistantError::NotFound(_))));
+    }
+}
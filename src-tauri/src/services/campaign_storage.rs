@@ -0,0 +1,789 @@
+// src-tauri/src/services/campaign_storage.rs
+
+use crate::core::models::backup::{BackupInfo, BackupReason, RetentionPolicy};
+use crate::core::models::campaign::Campaign;
+use crate::core::models::character::PlayerCharacter;
+use crate::core::models::common::SCHEMA_VERSION;
+#[cfg(feature = "sqlite")]
+use crate::core::models::event::ReloadReason;
+use crate::core::models::item::Item;
+use crate::core::models::monster::Monster;
+use crate::core::models::npc::Npc;
+use crate::core::models::spell::Spell;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{
+    get_app_data_dir, get_campaigns_dir, save_json, load_json, file_exists, dir_exists,
+    ensure_dir_exists, list_files_recursive, is_storage_artifact,
+};
+use crate::services::backup::BackupService;
+use crate::services::entity_index::EntityIndexService;
+#[cfg(feature = "sqlite")]
+use crate::services::events::EventService;
+use crate::services::history::HISTORY_FILE;
+use crate::services::migrations::load_versioned;
+use crate::services::storage::{
+    CampaignRepository, CampaignStore, Entity, Repository, StorageBackend, StorageConversion, SQLITE_FILE,
+};
+#[cfg(feature = "sqlite")]
+use crate::services::storage::JsonRepository;
+#[cfg(feature = "sqlite")]
+use crate::services::sqlite_storage::SqliteRepository;
+use crate::utils::id_generator::IdRemapper;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Name of the manifest file stored in every campaign directory
+pub const CAMPAIGN_MANIFEST: &str = "campaign.json";
+
+/// Files that belong to a campaign but aren't entities, so they aren't duplicated.
+/// The database holds entities, but they're copied through the repositories.
+const NON_ENTITY_FILES: &[&str] = &["roll_history.json", HISTORY_FILE, SQLITE_FILE];
+
+/// Collections stored through the repositories, one per entity type
+#[cfg(feature = "sqlite")]
+const ENTITY_COLLECTIONS: &[&str] = &[
+    PlayerCharacter::COLLECTION,
+    Npc::COLLECTION,
+    Monster::COLLECTION,
+    Spell::COLLECTION,
+    Item::COLLECTION,
+];
+
+/// Application state persisted across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppState {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub active_campaign_id: Option<Uuid>,
+    #[serde(default)]
+    pub backup_retention: RetentionPolicy,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            active_campaign_id: None,
+            backup_retention: RetentionPolicy::default(),
+        }
+    }
+}
+
+/// File-based campaign storage service
+pub struct CampaignStorageService;
+
+impl CampaignStorageService {
+    /// Get campaign directory path without creating it
+    pub fn get_campaign_dir_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        Ok(get_campaigns_dir()?.join(campaign_id.to_string()))
+    }
+
+    /// Get manifest path for a campaign
+    fn get_manifest_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        Ok(Self::get_campaign_dir_path(campaign_id)?.join(CAMPAIGN_MANIFEST))
+    }
+
+    /// Get app state file path
+    fn get_app_state_path() -> DmResult<PathBuf> {
+        Ok(get_app_data_dir()?.join("app_state.json"))
+    }
+
+    /// Check if a campaign exists (has a manifest)
+    pub fn campaign_exists(campaign_id: &Uuid) -> bool {
+        Self::get_manifest_path(campaign_id)
+            .map(|path| file_exists(&path))
+            .unwrap_or(false)
+    }
+
+    /// Fail with NotFound if the campaign doesn't exist
+    pub fn ensure_exists(campaign_id: &Uuid) -> DmResult<()> {
+        if Self::campaign_exists(campaign_id) {
+            Ok(())
+        } else {
+            Err(DmAssistantError::not_found("Campaign", &campaign_id.to_string()))
+        }
+    }
+
+    /// Fail if the campaign doesn't exist or is archived (read-only)
+    pub fn ensure_writable(campaign_id: &Uuid) -> DmResult<()> {
+        let campaign = Self::require_campaign(campaign_id)?;
+        if campaign.is_archived {
+            return Err(DmAssistantError::campaign(&format!("Campaign '{}' is archived and read-only", campaign.name)));
+        }
+        Ok(())
+    }
+
+    /// Save campaign manifest
+    pub fn save_campaign(campaign: &Campaign) -> DmResult<()> {
+        let campaign_dir = Self::get_campaign_dir_path(&campaign.id)?;
+        ensure_dir_exists(&campaign_dir)?;
+        CampaignStore::global().save(campaign, &campaign_dir.join(CAMPAIGN_MANIFEST))
+    }
+
+    /// Create a new campaign
+    pub fn create_campaign(campaign: Campaign) -> DmResult<Campaign> {
+        if Self::campaign_exists(&campaign.id) {
+            return Err(DmAssistantError::campaign(&format!("Campaign with ID {} already exists", campaign.id)));
+        }
+
+        Self::save_campaign(&campaign)?;
+        Ok(campaign)
+    }
+
+    /// Get campaign by ID
+    pub fn get_campaign(campaign_id: &Uuid) -> DmResult<Option<Campaign>> {
+        let manifest_path = Self::get_manifest_path(campaign_id)?;
+        CampaignStore::global().load(&manifest_path)
+    }
+
+    /// Get campaign by ID, failing if it doesn't exist
+    pub fn require_campaign(campaign_id: &Uuid) -> DmResult<Campaign> {
+        Self::get_campaign(campaign_id)?
+            .ok_or_else(|| DmAssistantError::not_found("Campaign", &campaign_id.to_string()))
+    }
+
+    /// List the IDs of every campaign directory, with or without a manifest
+    pub fn list_campaign_ids() -> DmResult<Vec<Uuid>> {
+        let campaigns_dir = get_campaigns_dir()?;
+        let entries = fs::read_dir(&campaigns_dir)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read campaigns directory: {}", e)))?;
+
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory entry: {}", e)))?;
+            if let Some(id) = entry.file_name().to_str().and_then(|name| Uuid::parse_str(name).ok()) {
+                ids.push(id);
+            }
+        }
+
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// List campaigns, most recently updated first
+    pub fn list_campaigns(include_archived: bool) -> DmResult<Vec<Campaign>> {
+        let campaigns_dir = get_campaigns_dir()?;
+        let entries = fs::read_dir(&campaigns_dir)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read campaigns directory: {}", e)))?;
+
+        let mut campaigns = Vec::new();
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory entry: {}", e)))?;
+            let manifest_path = entry.path().join(CAMPAIGN_MANIFEST);
+
+            match CampaignStore::global().load::<Campaign>(&manifest_path) {
+                Ok(None) => {}
+                Ok(Some(campaign)) if campaign.is_archived && !include_archived => {}
+                Ok(Some(campaign)) => campaigns.push(campaign),
+                // A single broken manifest shouldn't hide every other campaign
+                Err(e) => eprintln!("Skipping unreadable campaign manifest {}: {}", manifest_path.display(), e),
+            }
+        }
+
+        campaigns.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+        Ok(campaigns)
+    }
+
+    /// Modify campaign in place
+    pub fn modify_campaign<F>(campaign_id: &Uuid, modifier: F) -> DmResult<Campaign>
+    where
+        F: FnOnce(&mut Campaign) -> DmResult<()>,
+    {
+        let _lock = lock_campaign(campaign_id)?;
+        let mut campaign = Self::require_campaign(campaign_id)?;
+        if campaign.is_archived {
+            return Err(DmAssistantError::campaign(&format!("Campaign '{}' is archived and read-only", campaign.name)));
+        }
+
+        modifier(&mut campaign)?;
+        campaign.updated_at = chrono::Utc::now();
+
+        Self::save_campaign(&campaign)?;
+        Ok(campaign)
+    }
+
+    /// Archive campaign: it becomes read-only and hidden from the active list
+    pub fn archive_campaign(campaign_id: &Uuid) -> DmResult<Campaign> {
+        let _lock = lock_campaign(campaign_id)?;
+        let mut campaign = Self::require_campaign(campaign_id)?;
+        if campaign.is_archived {
+            return Ok(campaign);
+        }
+
+        let now = chrono::Utc::now();
+        campaign.is_archived = true;
+        campaign.archived_at = Some(now);
+        campaign.updated_at = now;
+        Self::save_campaign(&campaign)?;
+        Self::clear_active_campaign(campaign_id)?;
+
+        Ok(campaign)
+    }
+
+    /// Restore an archived campaign
+    pub fn restore_campaign(campaign_id: &Uuid) -> DmResult<Campaign> {
+        let _lock = lock_campaign(campaign_id)?;
+        let mut campaign = Self::require_campaign(campaign_id)?;
+        if !campaign.is_archived {
+            return Ok(campaign);
+        }
+
+        campaign.is_archived = false;
+        campaign.archived_at = None;
+        campaign.updated_at = chrono::Utc::now();
+        Self::save_campaign(&campaign)?;
+
+        Ok(campaign)
+    }
+
+    /// Modify campaign, snapshotting it if the change moves it on to a later session.
+    /// Returns the session start backup along with the campaign, if one was taken.
+    pub fn modify_campaign_with_session_backup<F>(campaign_id: &Uuid, modifier: F) -> DmResult<(Campaign, Option<BackupInfo>)>
+    where
+        F: FnOnce(&mut Campaign) -> DmResult<()>,
+    {
+        let _lock = lock_campaign(campaign_id)?;
+        let previous_session = Self::require_campaign(campaign_id)?.current_session;
+        let campaign = Self::modify_campaign(campaign_id, modifier)?;
+
+        let backup = if campaign.current_session > previous_session {
+            Some(BackupService::create_backup(campaign_id, BackupReason::SessionStart)?)
+        } else {
+            None
+        };
+        Ok((campaign, backup))
+    }
+
+    /// Start a session, optionally moving the campaign to the given session number.
+    /// The campaign is snapshotted as it is when the session begins.
+    pub fn start_session(campaign_id: &Uuid, session_number: Option<u32>) -> DmResult<BackupInfo> {
+        let _lock = lock_campaign(campaign_id)?;
+        if let Some(session_number) = session_number {
+            let (_, backup) = Self::modify_campaign_with_session_backup(campaign_id, |campaign| {
+                campaign.current_session = session_number;
+                Ok(())
+            })?;
+            if let Some(backup) = backup {
+                return Ok(backup);
+            }
+        } else {
+            Self::ensure_exists(campaign_id)?;
+        }
+
+        BackupService::create_backup(campaign_id, BackupReason::SessionStart)
+    }
+
+    /// Delete campaign and all of its data, after backing it up.
+    /// Returns the path of the backup archive.
+    pub fn delete_campaign(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        let _lock = lock_campaign(campaign_id)?;
+        let campaign_dir = Self::get_campaign_dir_path(campaign_id)?;
+        if !dir_exists(&campaign_dir) {
+            return Err(DmAssistantError::not_found("Campaign", &campaign_id.to_string()));
+        }
+
+        // Never delete without a backup
+        let backup = BackupService::create_backup(campaign_id, BackupReason::BeforeDelete)?;
+
+        fs::remove_dir_all(&campaign_dir)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to delete campaign directory: {}", e)))?;
+        CampaignStore::global().invalidate_dir(&campaign_dir);
+        Self::clear_active_campaign(campaign_id)?;
+        EntityIndexService::unregister_campaign(campaign_id)?;
+
+        Ok(PathBuf::from(backup.path))
+    }
+
+    /// Duplicate a campaign as a new one.
+    /// Every entity is copied with a new ID and references between entities
+    /// of the campaign are rewritten to the new IDs.
+    pub fn duplicate_campaign(campaign_id: &Uuid, new_name: Option<String>) -> DmResult<Campaign> {
+        // Hold the source still while it's copied
+        let _lock = lock_campaign(campaign_id)?;
+        let source = Self::require_campaign(campaign_id)?;
+        let source_dir = Self::get_campaign_dir_path(campaign_id)?;
+
+        let mut remapper = IdRemapper::new();
+        let new_id = remapper.remap(*campaign_id);
+
+        // First pass: load entity files and mint a new ID for every entity
+        let mut documents = Vec::new();
+        let mut assets = Vec::new();
+        let backend = StorageBackend::of(campaign_id)?;
+        if backend == StorageBackend::Sqlite {
+            // Copied as JSON files, converted back once the copy is in place
+            documents.push(Self::collection_document::<PlayerCharacter>(campaign_id)?);
+            documents.push(Self::collection_document::<Npc>(campaign_id)?);
+            documents.push(Self::collection_document::<Monster>(campaign_id)?);
+            documents.push(Self::collection_document::<Spell>(campaign_id)?);
+            documents.push(Self::collection_document::<Item>(campaign_id)?);
+            for (_, document) in &documents {
+                remapper.collect_ids(document);
+            }
+        }
+        for relative in list_files_recursive(&source_dir)? {
+            if !Self::is_entity_file(&relative) {
+                continue;
+            }
+            if relative.extension().map(|ext| ext == "json").unwrap_or(false) {
+                let document: serde_json::Value = load_json(&source_dir.join(&relative))?;
+                remapper.collect_ids(&document);
+                documents.push((relative, document));
+            } else {
+                assets.push(relative);
+            }
+        }
+
+        // Second pass: write into a staging directory, so a failure never
+        // leaves a half-copied campaign in the campaigns list
+        let staging_dir = get_app_data_dir()?.join("tmp").join(new_id.to_string());
+        let staged = (|| -> DmResult<()> {
+            for (relative, mut document) in documents {
+                remapper.rewrite(&mut document);
+                save_json(&document, &staging_dir.join(relative))?;
+            }
+            for relative in assets {
+                let target = staging_dir.join(&relative);
+                if let Some(parent) = target.parent() {
+                    ensure_dir_exists(parent)?;
+                }
+                fs::copy(source_dir.join(&relative), &target)
+                    .map_err(|e| DmAssistantError::storage(&format!("Failed to copy file: {}", e)))?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = staged {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+
+        fs::rename(&staging_dir, Self::get_campaign_dir_path(&new_id)?)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to create campaign directory: {}", e)))?;
+
+        let mut campaign = Self::require_campaign(&new_id)?;
+        let now = chrono::Utc::now();
+        campaign.name = new_name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| format!("{} (copy)", source.name));
+        campaign.is_archived = false;
+        campaign.archived_at = None;
+        campaign.created_at = now;
+        campaign.updated_at = now;
+        Self::save_campaign(&campaign)?;
+        if backend == StorageBackend::Sqlite {
+            Self::convert_storage(&new_id, StorageBackend::Sqlite)?;
+        }
+        EntityIndexService::register_campaign(&new_id)?;
+
+        Ok(campaign)
+    }
+
+    /// Collection of a campaign as the document of its JSON file
+    pub(crate) fn collection_document<T: Entity>(campaign_id: &Uuid) -> DmResult<(PathBuf, serde_json::Value)> {
+        let collection = CampaignRepository::<T>::new().load_collection(campaign_id)?;
+        Ok((PathBuf::from(format!("{}.json", T::COLLECTION)), serde_json::to_value(&collection)?))
+    }
+
+    /// Convert a campaign's entities to another storage backend.
+    ///
+    /// The campaign directory is backed up first, and every collection is read
+    /// back from the new backend and compared with the original before the old
+    /// data is removed, so nothing is lost or silently changed.
+    #[cfg(feature = "sqlite")]
+    pub fn convert_storage(campaign_id: &Uuid, target: StorageBackend) -> DmResult<StorageConversion> {
+        let _lock = lock_campaign(campaign_id)?;
+        Self::ensure_writable(campaign_id)?;
+        let from = StorageBackend::of(campaign_id)?;
+        if from == target {
+            return Ok(StorageConversion { from, to: target, entities: 0, backup_path: None });
+        }
+
+        let backup = BackupService::create_backup(campaign_id, BackupReason::BeforeConversion)?;
+        let converted = Self::convert_collection::<PlayerCharacter>(campaign_id, target)
+            .and_then(|characters| Ok(characters + Self::convert_collection::<Npc>(campaign_id, target)?))
+            .and_then(|entities| Ok(entities + Self::convert_collection::<Monster>(campaign_id, target)?))
+            .and_then(|entities| Ok(entities + Self::convert_collection::<Spell>(campaign_id, target)?))
+            .and_then(|entities| Ok(entities + Self::convert_collection::<Item>(campaign_id, target)?));
+
+        // Remove the data of whichever backend isn't in use anymore
+        let campaign_dir = Self::get_campaign_dir_path(campaign_id)?;
+        let remove_json = || -> DmResult<()> {
+            for collection in ENTITY_COLLECTIONS {
+                let file_path = campaign_dir.join(format!("{}.json", collection));
+                for path in [crate::utils::file_system::backup_path_for(&file_path), file_path] {
+                    if file_exists(&path) {
+                        fs::remove_file(&path)
+                            .map_err(|e| DmAssistantError::storage(&format!("Failed to remove file: {}", e)))?;
+                    }
+                }
+            }
+            CampaignStore::global().invalidate_dir(&campaign_dir);
+            Ok(())
+        };
+        let remove_database = || -> DmResult<()> {
+            fs::remove_file(campaign_dir.join(SQLITE_FILE))
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to remove database: {}", e)))
+        };
+        let entities = match (converted, target) {
+            (Ok(entities), StorageBackend::Sqlite) => { remove_json()?; entities }
+            (Ok(entities), StorageBackend::Json) => { remove_database()?; entities }
+            (Err(e), StorageBackend::Sqlite) => { let _ = remove_database(); return Err(e); }
+            (Err(e), StorageBackend::Json) => { let _ = remove_json(); return Err(e); }
+        };
+        EventService::campaign_reloaded(campaign_id, ReloadReason::StorageConverted);
+
+        Ok(StorageConversion {
+            from,
+            to: target,
+            entities,
+            backup_path: Some(backup.path),
+        })
+    }
+
+    /// Copy one collection to the target backend, checking the copy is identical
+    #[cfg(feature = "sqlite")]
+    fn convert_collection<T: Entity>(campaign_id: &Uuid, target: StorageBackend) -> DmResult<usize> {
+        let json = JsonRepository::<T>::new();
+        let sqlite = SqliteRepository::<T>::new();
+        let (source, destination): (&dyn Repository<T>, &dyn Repository<T>) = match target {
+            StorageBackend::Sqlite => (&json, &sqlite),
+            StorageBackend::Json => (&sqlite, &json),
+        };
+
+        let collection = source.load_collection(campaign_id)?;
+        destination.save_collection(&collection)?;
+
+        let written = destination.load_collection(campaign_id)?;
+        if serde_json::to_value(&written)? != serde_json::to_value(&collection)? {
+            return Err(DmAssistantError::storage(&format!(
+                "Converting {} of campaign {} changed the data", T::COLLECTION, campaign_id
+            )));
+        }
+        Ok(collection.len())
+    }
+
+    /// Without the `sqlite` feature campaigns can only be stored as JSON
+    #[cfg(not(feature = "sqlite"))]
+    pub fn convert_storage(campaign_id: &Uuid, target: StorageBackend) -> DmResult<StorageConversion> {
+        let from = StorageBackend::of(campaign_id)?;
+        if from == target {
+            return Ok(StorageConversion { from, to: target, entities: 0, backup_path: None });
+        }
+        Err(DmAssistantError::storage("This build doesn't support SQLite storage"))
+    }
+
+    /// Check if a file of the campaign directory holds entities (or assets)
+    fn is_entity_file(relative: &Path) -> bool {
+        let name = relative.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        !NON_ENTITY_FILES.contains(&name) && !is_storage_artifact(relative)
+    }
+
+    /// Unset the active campaign if it's the given one
+    fn clear_active_campaign(campaign_id: &Uuid) -> DmResult<()> {
+        let mut state = Self::load_app_state()?;
+        if state.active_campaign_id == Some(*campaign_id) {
+            state.active_campaign_id = None;
+            Self::save_app_state(&state)?;
+        }
+        Ok(())
+    }
+
+    /// Load app state
+    pub fn load_app_state() -> DmResult<AppState> {
+        let path = Self::get_app_state_path()?;
+
+        if file_exists(&path) {
+            load_versioned(&path)
+        } else {
+            Ok(AppState::default())
+        }
+    }
+
+    /// Save app state
+    pub fn save_app_state(state: &AppState) -> DmResult<()> {
+        save_json(state, &Self::get_app_state_path()?)
+    }
+
+    /// Set the active campaign
+    pub fn set_active_campaign(campaign_id: &Uuid) -> DmResult<Campaign> {
+        let campaign = Self::require_campaign(campaign_id)?;
+        if campaign.is_archived {
+            return Err(DmAssistantError::campaign("Archived campaigns can't be set as active; restore it first"));
+        }
+
+        let mut state = Self::load_app_state()?;
+        state.active_campaign_id = Some(campaign.id);
+        Self::save_app_state(&state)?;
+
+        Ok(campaign)
+    }
+
+    /// Get the active campaign, if any
+    pub fn get_active_campaign() -> DmResult<Option<Campaign>> {
+        match Self::load_app_state()?.active_campaign_id {
+            Some(campaign_id) => Self::get_campaign(&campaign_id),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::campaign::{CreateCampaignRequest, UpdateCampaignRequest};
+    use crate::core::models::character::{CreateCharacterRequest, PlayerCharacter};
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::utils::test_support::init_test_data_dir;
+
+    fn new_campaign(name: &str) -> Campaign {
+        Campaign::new(CreateCampaignRequest {
+            name: name.to_string(),
+            description: None,
+            setting: Some("Forgotten Realms".to_string()),
+            ruleset: None,
+            party_level: Some(3),
+            in_game_date: None,
+        })
+    }
+
+    fn new_character(campaign_id: Uuid) -> PlayerCharacter {
+        PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: "Aria".to_string(),
+            race: "Elf".to_string(),
+            class: "Ranger".to_string(),
+            level: 3,
+            max_hp: 24,
+            background: String::new(),
+            notes: None,
+        })
+    }
+
+    #[test]
+    fn test_campaign_crud() {
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Lost Mine")).unwrap();
+        assert!(CampaignStorageService::campaign_exists(&campaign.id));
+        assert!(CampaignStorageService::list_campaigns(false).unwrap().iter().any(|c| c.id == campaign.id));
+
+        let updated = CampaignStorageService::modify_campaign(&campaign.id, |c| {
+            c.update(UpdateCampaignRequest {
+                name: None,
+                description: None,
+                setting: None,
+                ruleset: None,
+                current_session: Some(4),
+                in_game_date: Some("3rd of Mirtul".to_string()),
+                party_level: None,
+            });
+            Ok(())
+        }).unwrap();
+        assert_eq!(updated.current_session, 4);
+        assert_eq!(CampaignStorageService::require_campaign(&campaign.id).unwrap().current_session, 4);
+
+        let backup = CampaignStorageService::delete_campaign(&campaign.id).unwrap();
+        assert!(file_exists(&backup));
+        assert!(!CampaignStorageService::campaign_exists(&campaign.id));
+        assert!(CampaignStorageService::get_campaign(&campaign.id).unwrap().is_none());
+        assert!(CampaignStorageService::delete_campaign(&campaign.id).is_err());
+    }
+
+    #[test]
+    fn test_moving_to_a_later_session_takes_one_backup() {
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Curse of Strahd")).unwrap();
+        let session_backups = || BackupService::list_backups(Some(&campaign.id)).unwrap()
+            .into_iter()
+            .filter(|backup| backup.reason == BackupReason::SessionStart)
+            .count();
+
+        let (_, backup) = CampaignStorageService::modify_campaign_with_session_backup(&campaign.id, |c| {
+            c.in_game_date = Some("1st of Hammer".to_string());
+            Ok(())
+        }).unwrap();
+        assert!(backup.is_none());
+
+        let (updated, backup) = CampaignStorageService::modify_campaign_with_session_backup(&campaign.id, |c| {
+            c.current_session = 2;
+            Ok(())
+        }).unwrap();
+        assert_eq!(backup.unwrap().session_number, Some(2));
+        assert_eq!(updated.current_session, 2);
+
+        // Starting the same session again is still snapshotted, once
+        CampaignStorageService::start_session(&campaign.id, Some(2)).unwrap();
+        assert_eq!(session_backups(), 2);
+        CampaignStorageService::start_session(&campaign.id, Some(3)).unwrap();
+        assert_eq!(session_backups(), 3);
+    }
+
+    #[test]
+    fn test_archive_and_restore() {
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Out of the Abyss")).unwrap();
+
+        CampaignStorageService::archive_campaign(&campaign.id).unwrap();
+        assert!(!CampaignStorageService::list_campaigns(false).unwrap().iter().any(|c| c.id == campaign.id));
+        assert!(CampaignStorageService::list_campaigns(true).unwrap().iter().any(|c| c.id == campaign.id));
+        assert!(CampaignStorageService::ensure_writable(&campaign.id).is_err());
+        assert!(CampaignStorageService::modify_campaign(&campaign.id, |_| Ok(())).is_err());
+        assert!(CharacterStorageService::create_character(new_character(campaign.id)).is_err());
+
+        let restored = CampaignStorageService::restore_campaign(&campaign.id).unwrap();
+        assert!(!restored.is_archived);
+        assert!(CharacterStorageService::create_character(new_character(campaign.id)).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_rewrites_ids() {
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Rime of the Frostmaiden")).unwrap();
+        let npc_id = Uuid::new_v4();
+        let external_quest_id = Uuid::new_v4();
+
+        let mut character = new_character(campaign.id);
+        character.relationships.push(crate::core::models::character::CharacterRelationship {
+            id: Uuid::new_v4(),
+            character_id: character.id,
+            npc_id,
+            relationship_type: crate::core::models::character::RelationshipType::Ally,
+            notes: String::new(),
+            last_interaction: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        });
+        let character = CharacterStorageService::create_character(character).unwrap();
+
+        // Entity files are duplicated generically, whatever their shape
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign.id).unwrap();
+        save_json(
+            &serde_json::json!({ "npcs": [{ "id": npc_id, "questId": external_quest_id }] }),
+            &campaign_dir.join("npcs.json"),
+        ).unwrap();
+        fs::write(campaign_dir.join("roll_history.json"), "{}").unwrap();
+
+        let copy = CampaignStorageService::duplicate_campaign(&campaign.id, None).unwrap();
+        assert_ne!(copy.id, campaign.id);
+        assert_eq!(copy.name, "Rime of the Frostmaiden (copy)");
+
+        let copied = CharacterStorageService::get_characters_by_campaign(&copy.id).unwrap();
+        assert_eq!(copied.len(), 1);
+        let copied = &copied[0];
+        assert_ne!(copied.id, character.id);
+        assert_eq!(copied.campaign_id, copy.id);
+        assert_eq!(copied.relationships[0].character_id, copied.id);
+        let new_npc_id = copied.relationships[0].npc_id;
+        assert_ne!(new_npc_id, npc_id);
+
+        let copy_dir = CampaignStorageService::get_campaign_dir_path(&copy.id).unwrap();
+        let npcs: serde_json::Value = load_json(&copy_dir.join("npcs.json")).unwrap();
+        assert_eq!(npcs["npcs"][0]["id"], new_npc_id.to_string().as_str());
+        assert_eq!(npcs["npcs"][0]["questId"], external_quest_id.to_string().as_str());
+        assert!(!file_exists(&copy_dir.join("roll_history.json")));
+
+        // The original is untouched
+        let original = CharacterStorageService::get_character(&campaign.id, &character.id).unwrap().unwrap();
+        assert_eq!(original.relationships[0].npc_id, npc_id);
+    }
+
+    #[test]
+    fn test_active_campaign() {
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Curse of Strahd")).unwrap();
+
+        assert!(CampaignStorageService::set_active_campaign(&Uuid::new_v4()).is_err());
+        CampaignStorageService::set_active_campaign(&campaign.id).unwrap();
+        assert_eq!(CampaignStorageService::get_active_campaign().unwrap().unwrap().id, campaign.id);
+
+        CampaignStorageService::delete_campaign(&campaign.id).unwrap();
+        assert!(CampaignStorageService::get_active_campaign().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_character_creation_requires_campaign() {
+        init_test_data_dir();
+        let result = CharacterStorageService::create_character(new_character(Uuid::new_v4()));
+        assert!(matches!(result, Err(DmAssistantError::NotFound(_))));
+
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Tomb of Annihilation")).unwrap();
+        assert!(CharacterStorageService::create_character(new_character(campaign.id)).is_ok());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_convert_storage_round_trip() {
+        use crate::core::models::npc::CreateNpcRequest;
+        use crate::services::npc_storage::NpcStorageService;
+
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Out of the Abyss")).unwrap();
+        let character = CharacterStorageService::create_character(new_character(campaign.id)).unwrap();
+        let npc = NpcStorageService::create_npc(Npc::new(CreateNpcRequest {
+            campaign_id: campaign.id,
+            name: "Sarith".to_string(),
+            race: Some("Drow".to_string()),
+            role: None,
+            location_id: None,
+            faction: None,
+            attitude: None,
+            voice: None,
+            mannerisms: None,
+            secrets: Some(vec!["Infected by a spore".to_string()]),
+            portrait_path: None,
+            notes: None,
+        })).unwrap();
+        let characters = serde_json::to_value(CharacterStorageService::load_characters(&campaign.id).unwrap()).unwrap();
+        let npcs = serde_json::to_value(NpcStorageService::load_npcs(&campaign.id).unwrap()).unwrap();
+
+        let conversion = CampaignStorageService::convert_storage(&campaign.id, StorageBackend::Sqlite).unwrap();
+        assert_eq!(conversion.entities, 2);
+        assert!(conversion.backup_path.is_some());
+        assert_eq!(StorageBackend::of(&campaign.id).unwrap(), StorageBackend::Sqlite);
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign.id).unwrap();
+        assert!(!file_exists(&campaign_dir.join("characters.json")));
+        assert_eq!(CharacterStorageService::find_character(&character.id).unwrap().unwrap().name, "Aria");
+        assert_eq!(NpcStorageService::get_npc(&campaign.id, &npc.id).unwrap().unwrap().secrets, npc.secrets);
+
+        // Already there: nothing to do
+        assert_eq!(CampaignStorageService::convert_storage(&campaign.id, StorageBackend::Sqlite).unwrap().entities, 0);
+
+        // A copy of an SQLite campaign is stored in SQLite too
+        let copy = CampaignStorageService::duplicate_campaign(&campaign.id, None).unwrap();
+        assert_eq!(StorageBackend::of(&copy.id).unwrap(), StorageBackend::Sqlite);
+        let copied = CharacterStorageService::get_characters_by_campaign(&copy.id).unwrap();
+        assert_eq!(copied.len(), 1);
+        assert_ne!(copied[0].id, character.id);
+        assert_eq!(copied[0].campaign_id, copy.id);
+
+        CampaignStorageService::convert_storage(&campaign.id, StorageBackend::Json).unwrap();
+        assert_eq!(StorageBackend::of(&campaign.id).unwrap(), StorageBackend::Json);
+        assert_eq!(serde_json::to_value(CharacterStorageService::load_characters(&campaign.id).unwrap()).unwrap(), characters);
+        assert_eq!(serde_json::to_value(NpcStorageService::load_npcs(&campaign.id).unwrap()).unwrap(), npcs);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_convert_storage_reloads_the_campaign() {
+        use crate::utils::test_support::MockEmitter;
+
+        init_test_data_dir();
+        let campaign = CampaignStorageService::create_campaign(new_campaign("Princes of the Apocalypse")).unwrap();
+        CharacterStorageService::create_character(new_character(campaign.id)).unwrap();
+
+        let events = MockEmitter::subscribe(campaign.id);
+        CampaignStorageService::convert_storage(&campaign.id, StorageBackend::Sqlite).unwrap();
+        let reloads = events.take_reloads();
+        assert_eq!(reloads.len(), 1);
+        assert_eq!(reloads[0].reason, ReloadReason::StorageConverted);
+
+        // Nothing to convert, nothing to reload
+        CampaignStorageService::convert_storage(&campaign.id, StorageBackend::Sqlite).unwrap();
+        assert!(events.take_reloads().is_empty());
+    }
+}
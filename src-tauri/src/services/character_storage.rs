@@ -1,159 +1,103 @@
 // src-tauri/src/services/character_storage.rs
 
-use crate::core::models::character::PlayerCharacter;
+use crate::core::models::character::{
+    PlayerCharacter, NpcRelationshipFilter, NpcRelationshipMatch, CampaignNpcRelationships,
+};
+use crate::core::models::common::EntityKind;
+use crate::core::models::relationship::GraphNode;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::history::HistoryService;
+use crate::services::relationship_graph::RelationshipGraphService;
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Query, Repository};
+use crate::utils::campaign_lock::lock_campaign;
 use crate::utils::error::{DmAssistantError, DmResult};
-use crate::utils::file_system::{get_campaign_dir, save_json, load_json, file_exists};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Character collection for a campaign
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CharacterCollection {
-    pub campaign_id: Uuid,
-    pub characters: HashMap<Uuid, PlayerCharacter>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
+pub type CharacterCollection = EntityCollection<PlayerCharacter>;
 
-impl CharacterCollection {
-    pub fn new(campaign_id: Uuid) -> Self {
-        let now = chrono::Utc::now();
-        Self {
-            campaign_id,
-            characters: HashMap::new(),
-            created_at: now,
-            updated_at: now,
-        }
-    }
-    
-    pub fn add_character(&mut self, character: PlayerCharacter) {
-        self.characters.insert(character.id, character);
-        self.updated_at = chrono::Utc::now();
-    }
-    
-    pub fn update_character(&mut self, character: PlayerCharacter) -> bool {
-        if self.characters.contains_key(&character.id) {
-            self.characters.insert(character.id, character);
-            self.updated_at = chrono::Utc::now();
-            true
-        } else {
-            false
-        }
-    }
-    
-    pub fn remove_character(&mut self, character_id: &Uuid) -> bool {
-        if self.characters.remove(character_id).is_some() {
-            self.updated_at = chrono::Utc::now();
-            true
-        } else {
-            false
-        }
-    }
-    
-    pub fn get_character(&self, character_id: &Uuid) -> Option<&PlayerCharacter> {
-        self.characters.get(character_id)
-    }
-    
-    pub fn get_character_mut(&mut self, character_id: &Uuid) -> Option<&mut PlayerCharacter> {
-        self.characters.get_mut(character_id)
-    }
-    
-    pub fn get_all_characters(&self) -> Vec<&PlayerCharacter> {
-        self.characters.values().collect()
-    }
-    
-    pub fn get_active_characters(&self) -> Vec<&PlayerCharacter> {
-        self.characters.values().filter(|c| c.is_active).collect()
-    }
+impl Entity for PlayerCharacter {
+    const KIND: EntityKind = EntityKind::Character;
+    const COLLECTION: &'static str = "characters";
+    const LABEL: &'static str = "Character";
+
+    fn id(&self) -> Uuid { self.id }
+    fn campaign_id(&self) -> Uuid { self.campaign_id }
+    fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+    fn touch(&mut self) { self.updated_at = Utc::now(); }
 }
 
+static REPOSITORY: CampaignRepository<PlayerCharacter> = CampaignRepository::new();
+
 /// File-based character storage service
 pub struct CharacterStorageService;
 
 impl CharacterStorageService {
-    /// Get file path for campaign characters
-    fn get_characters_file_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
-        let campaign_dir = get_campaign_dir(&campaign_id.to_string())?;
-        Ok(campaign_dir.join("characters.json"))
+    /// Repository holding the characters
+    pub fn repository() -> &'static dyn Repository<PlayerCharacter> {
+        &REPOSITORY
     }
-    
+
     /// Load characters for a campaign
     pub fn load_characters(campaign_id: &Uuid) -> DmResult<CharacterCollection> {
-        let file_path = Self::get_characters_file_path(campaign_id)?;
-        
-        if file_exists(&file_path) {
-            load_json(&file_path)
-        } else {
-            // Create new collection if file doesn't exist
-            let collection = CharacterCollection::new(*campaign_id);
-            Self::save_characters(&collection)?;
-            Ok(collection)
-        }
+        REPOSITORY.load_collection(campaign_id)
     }
     
     /// Save characters for a campaign
     pub fn save_characters(collection: &CharacterCollection) -> DmResult<()> {
-        let file_path = Self::get_characters_file_path(&collection.campaign_id)?;
-        save_json(collection, &file_path)
+        REPOSITORY.save_collection(collection)
     }
     
     /// Create a new character
     pub fn create_character(character: PlayerCharacter) -> DmResult<PlayerCharacter> {
-        let mut collection = Self::load_characters(&character.campaign_id)?;
-        
-        // Check if character already exists
-        if collection.characters.contains_key(&character.id) {
-            return Err(DmAssistantError::character(&format!("Character with ID {} already exists", character.id)));
-        }
-        
-        collection.add_character(character.clone());
-        Self::save_characters(&collection)?;
-        
-        Ok(character)
+        // Characters can only belong to an existing, writable campaign
+        Self::repository().create(character)
     }
     
     /// Get character by ID
     pub fn get_character(campaign_id: &Uuid, character_id: &Uuid) -> DmResult<Option<PlayerCharacter>> {
-        let collection = Self::load_characters(campaign_id)?;
-        Ok(collection.get_character(character_id).cloned())
+        Self::repository().get(campaign_id, character_id)
+    }
+    
+    /// Find a character by ID alone, using the entity index
+    pub fn find_character(character_id: &Uuid) -> DmResult<Option<PlayerCharacter>> {
+        Self::repository().find(character_id)
+    }
+
+    /// Get the campaign a character belongs to, failing if the character doesn't exist
+    pub fn require_campaign_of(character_id: &Uuid) -> DmResult<Uuid> {
+        Self::find_character(character_id)?
+            .map(|character| character.campaign_id)
+            .ok_or_else(|| DmAssistantError::not_found("Character", &character_id.to_string()))
     }
     
     /// Get all characters for campaign
     pub fn get_characters_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<PlayerCharacter>> {
-        let collection = Self::load_characters(campaign_id)?;
-        Ok(collection.get_all_characters().into_iter().cloned().collect())
+        Self::repository().list(campaign_id)
     }
     
     /// Get active characters for campaign
     pub fn get_active_characters_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<PlayerCharacter>> {
-        let collection = Self::load_characters(campaign_id)?;
-        Ok(collection.get_active_characters().into_iter().cloned().collect())
+        Self::repository().query(campaign_id, &Query::new().filter(|c: &PlayerCharacter| c.is_active))
     }
     
     /// Update character
     pub fn update_character(character: PlayerCharacter) -> DmResult<PlayerCharacter> {
-        let mut collection = Self::load_characters(&character.campaign_id)?;
-        
-        if !collection.update_character(character.clone()) {
-            return Err(DmAssistantError::not_found("Character", &character.id.to_string()));
-        }
-        
-        Self::save_characters(&collection)?;
-        Ok(character)
+        Self::repository().update(character)
     }
     
     /// Delete character
     pub fn delete_character(campaign_id: &Uuid, character_id: &Uuid) -> DmResult<bool> {
-        let mut collection = Self::load_characters(campaign_id)?;
-        
-        let removed = collection.remove_character(character_id);
-        if removed {
-            Self::save_characters(&collection)?;
-        }
-        
-        Ok(removed)
+        // Undone as a whole, relationships included
+        HistoryService::batch(campaign_id, || {
+            let removed = Self::repository().delete(campaign_id, character_id)?;
+            if removed {
+                RelationshipGraphService::remove_node(campaign_id, &GraphNode::Character(*character_id))?;
+            }
+            Ok(removed)
+        })
     }
     
     /// Modify character in place (for operations like add achievement, update relationship)
@@ -161,40 +105,202 @@ impl CharacterStorageService {
     where
         F: FnOnce(&mut PlayerCharacter) -> DmResult<()>,
     {
-        let mut collection = Self::load_characters(campaign_id)?;
-        
-        let character = collection.get_character_mut(character_id)
-            .ok_or_else(|| DmAssistantError::not_found("Character", &character_id.to_string()))?;
-        
-        modifier(character)?;
-        character.updated_at = chrono::Utc::now();
-        
-        let result = character.clone();
-        Self::save_characters(&collection)?;
-        
-        Ok(result)
+        Self::repository().modify(campaign_id, character_id, Box::new(modifier))
     }
     
-    /// Get characters that have relationship with specific NPC
-    pub fn get_characters_by_npc_relationship(npc_id: &Uuid) -> DmResult<Vec<PlayerCharacter>> {
-        // This is more complex as we need to search across all campaigns
-        // For now, we'll search in a specific campaign
-        // TODO: Implement cross-campaign search or require campaign_id parameter
-        Err(DmAssistantError::internal("Cross-campaign NPC relationship search not implemented yet"))
+    /// Get characters that have a relationship with a specific NPC, across
+    /// every campaign, grouped by campaign
+    pub fn get_characters_by_npc_relationship(
+        npc_id: &Uuid,
+        filter: &NpcRelationshipFilter,
+    ) -> DmResult<Vec<CampaignNpcRelationships>> {
+        let mut groups = Vec::new();
+
+        for campaign_id in CampaignStorageService::list_campaign_ids()? {
+            let characters = match Self::repository().list(&campaign_id) {
+                Ok(characters) => characters,
+                // A single broken campaign shouldn't hide every other one
+                Err(e) => {
+                    eprintln!("Skipping unreadable characters of campaign {}: {}", campaign_id, e);
+                    continue;
+                }
+            };
+
+            let mut matches: Vec<NpcRelationshipMatch> = characters.into_iter()
+                .filter_map(|character| {
+                    let relationship = character.get_relationship(*npc_id)
+                        .filter(|r| filter.matches(r))
+                        .cloned()?;
+                    Some(NpcRelationshipMatch { character, relationship })
+                })
+                .collect();
+            if matches.is_empty() {
+                continue;
+            }
+            matches.sort_by(|a, b| a.character.name.cmp(&b.character.name));
+
+            let campaign_name = CampaignStorageService::get_campaign(&campaign_id)
+                .ok()
+                .flatten()
+                .map(|campaign| campaign.name);
+            groups.push(CampaignNpcRelationships { campaign_id, campaign_name, matches });
+        }
+
+        Ok(groups)
     }
     
-    /// Backup characters file
-    pub fn backup_characters(campaign_id: &Uuid) -> DmResult<PathBuf> {
-        let file_path = Self::get_characters_file_path(campaign_id)?;
-        crate::utils::file_system::backup_file(&file_path)
+    /// Write the characters of a campaign back to storage in the current format.
+    /// Returns how many were saved.
+    pub fn resave_characters(campaign_id: &Uuid) -> DmResult<usize> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let collection = REPOSITORY.load_collection(campaign_id)?;
+        REPOSITORY.save_collection(&collection)?;
+        Ok(collection.len())
     }
     
     /// Check if characters file exists for campaign
     pub fn characters_file_exists(campaign_id: &Uuid) -> bool {
-        if let Ok(file_path) = Self::get_characters_file_path(campaign_id) {
-            file_exists(&file_path)
-        } else {
-            false
+        REPOSITORY.json().file_exists(campaign_id)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{
+        CreateCharacterRequest, RelationshipType, UpdateRelationshipRequest, AddAchievementRequest, AchievementType,
+    };
+    use crate::utils::test_support::create_test_campaign;
+    use chrono::{Duration, Utc};
+
+    fn character_related_to(campaign_id: Uuid, name: &str, npc_id: Uuid, relationship_type: RelationshipType) -> PlayerCharacter {
+        let mut character = PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: "Human".to_string(),
+            class: "Bard".to_string(),
+            level: 2,
+            max_hp: 14,
+            background: String::new(),
+            notes: None,
+        });
+        character.update_relationship(UpdateRelationshipRequest {
+            character_id: character.id,
+            npc_id,
+            relationship_type,
+            notes: None,
+        });
+        CharacterStorageService::create_character(character).unwrap()
+    }
+
+    #[test]
+    fn test_reads_do_not_write() {
+        let campaign_id = create_test_campaign();
+        assert!(CharacterStorageService::get_characters_by_campaign(&campaign_id).unwrap().is_empty());
+        assert!(!CharacterStorageService::characters_file_exists(&campaign_id));
+
+        // Unknown campaigns don't get a directory either
+        let unknown = Uuid::new_v4();
+        assert!(CharacterStorageService::get_characters_by_campaign(&unknown).unwrap().is_empty());
+        assert!(!CampaignStorageService::get_campaign_dir_path(&unknown).unwrap().exists());
+    }
+
+    #[test]
+    fn test_concurrent_modifications_are_not_lost() {
+        let campaign_id = create_test_campaign();
+        let character = character_related_to(campaign_id, "Rin", Uuid::new_v4(), RelationshipType::Neutral);
+
+        let handles: Vec<_> = (0..16).map(|i| {
+            let character_id = character.id;
+            std::thread::spawn(move || {
+                for j in 0..5 {
+                    CharacterStorageService::modify_character(&campaign_id, &character_id, |c| {
+                        c.add_achievement(AddAchievementRequest {
+                            character_id,
+                            title: format!("Deed {}-{}", i, j),
+                            description: String::new(),
+                            quest_id: None,
+                            session_date: None,
+                            achievement_type: AchievementType::Roleplay,
+                        });
+                        Ok(())
+                    }).unwrap();
+                    // Interleave another kind of update on the same file
+                    CharacterStorageService::modify_character(&campaign_id, &character_id, |c| {
+                        c.is_active = !c.is_active;
+                        Ok(())
+                    }).unwrap();
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
         }
+
+        let character = CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap();
+        assert_eq!(character.achievements.len(), 16 * 5);
+        // An even number of toggles
+        assert!(character.is_active);
+    }
+
+    #[test]
+    fn test_npc_relationship_search_across_campaigns() {
+        let first_campaign = create_test_campaign();
+        let second_campaign = create_test_campaign();
+        let npc_id = Uuid::new_v4();
+
+        character_related_to(first_campaign, "Zed", npc_id, RelationshipType::Ally);
+        character_related_to(first_campaign, "Ann", npc_id, RelationshipType::Hostile);
+        character_related_to(first_campaign, "Bob", Uuid::new_v4(), RelationshipType::Ally);
+        character_related_to(second_campaign, "Cid", npc_id, RelationshipType::Ally);
+
+        let groups = CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &NpcRelationshipFilter::default()).unwrap();
+        assert_eq!(groups.len(), 2);
+        let first = groups.iter().find(|g| g.campaign_id == first_campaign).unwrap();
+        let names: Vec<&str> = first.matches.iter().map(|m| m.character.name.as_str()).collect();
+        assert_eq!(names, vec!["Ann", "Zed"]);
+        assert!(first.matches.iter().all(|m| m.relationship.npc_id == npc_id));
+        assert_eq!(first.campaign_name.as_deref(), Some("Test Campaign"));
+
+        let allies = NpcRelationshipFilter {
+            relationship_types: Some(vec![RelationshipType::Ally]),
+            ..Default::default()
+        };
+        let groups = CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &allies).unwrap();
+        let total: usize = groups.iter().map(|g| g.matches.len()).sum();
+        assert_eq!(total, 2);
+        assert!(groups.iter().flat_map(|g| &g.matches).all(|m| m.relationship.relationship_type == RelationshipType::Ally));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_npc_relationship_interaction_range() {
+        let campaign_id = create_test_campaign();
+        let npc_id = Uuid::new_v4();
+        let character = character_related_to(campaign_id, "Dara", npc_id, RelationshipType::Friendly);
+        let last_interaction = character.relationships[0].last_interaction.unwrap();
+
+        let around = NpcRelationshipFilter {
+            interacted_after: Some(last_interaction - Duration::hours(1)),
+            interacted_before: Some(last_interaction + Duration::hours(1)),
+            ..Default::default()
+        };
+        assert_eq!(CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &around).unwrap().len(), 1);
+
+        let later = NpcRelationshipFilter {
+            interacted_after: Some(Utc::now() + Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &later).unwrap().is_empty());
+
+        // Relationships never interacted with don't match a date range
+        CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+            c.relationships[0].last_interaction = None;
+            Ok(())
+        }).unwrap();
+        assert!(CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &around).unwrap().is_empty());
+        assert_eq!(
+            CharacterStorageService::get_characters_by_npc_relationship(&npc_id, &NpcRelationshipFilter::default()).unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,334 @@
+// src-tauri/src/services/compendium.rs
+
+use crate::core::models::compendium::{
+    CompendiumEntry, CompendiumKind, CompendiumListing, CompendiumQuery, ContentOrigin, ContentPack,
+    ContentSource, CopiedEntity, CopyCompendiumEntryRequest,
+};
+use crate::core::models::item::{CreateItemRequest, Item, ItemDetails};
+use crate::core::models::monster::{CreateMonsterRequest, Monster, StatBlock};
+use crate::core::models::spell::{CreateSpellRequest, Spell, SpellDetails};
+use crate::services::item_storage::ItemStorageService;
+use crate::services::monster_storage::MonsterStorageService;
+use crate::services::spell_storage::SpellStorageService;
+use crate::utils::error::{DmAssistantError, DmResult};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Key of the System Reference Document 5.1 pack
+pub const SRD_PACK: &str = "srd-5.1";
+
+/// Data files of the SRD pack, compiled in: file name and content
+const SRD_MONSTERS: (&str, &str) = ("monsters.json", include_str!("../../data/srd/monsters.json"));
+const SRD_SPELLS: (&str, &str) = ("spells.json", include_str!("../../data/srd/spells.json"));
+const SRD_EQUIPMENT: (&str, &str) = ("equipment.json", include_str!("../../data/srd/equipment.json"));
+const SRD_MAGIC_ITEMS: (&str, &str) = ("magic_items.json", include_str!("../../data/srd/magic_items.json"));
+
+const SRD_ATTRIBUTION: &str = "This work includes material taken from the System Reference Document 5.1 \
+    (\"SRD 5.1\") by Wizards of the Coast LLC and available at https://dnd.wizards.com/resources/systems-reference-document. \
+    The SRD 5.1 is licensed under the Creative Commons Attribution 4.0 International License available at \
+    https://creativecommons.org/licenses/by/4.0/legalcode.";
+
+/// Entry of a content pack: its key and content
+#[derive(Debug, Clone, Deserialize)]
+struct PackEntry<T> {
+    key: String,
+    #[serde(flatten)]
+    content: T,
+}
+
+/// Content pack parsed from the data compiled into the app
+struct Pack {
+    key: &'static str,
+    name: &'static str,
+    license: &'static str,
+    attribution: &'static str,
+    monsters: Vec<PackEntry<StatBlock>>,
+    spells: Vec<PackEntry<SpellDetails>>,
+    items: Vec<PackEntry<ItemDetails>>, // Equipment, then magic items
+}
+
+fn parse<T: DeserializeOwned>((file, data): (&str, &str)) -> DmResult<Vec<PackEntry<T>>> {
+    serde_json::from_str(data)
+        .map_err(|e| DmAssistantError::json(&format!("Invalid compendium data in {}: {}", file, e)))
+}
+
+/// Entries of a data file. The files are checked by the tests; should one be
+/// invalid anyway, the pack goes without its entries rather than crashing the app.
+fn load<T: DeserializeOwned>(file: (&str, &str)) -> Vec<PackEntry<T>> {
+    parse(file).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        Vec::new()
+    })
+}
+
+fn srd() -> &'static Pack {
+    static SRD: OnceLock<Pack> = OnceLock::new();
+    SRD.get_or_init(|| {
+        let mut items = load(SRD_EQUIPMENT);
+        items.extend(load(SRD_MAGIC_ITEMS));
+        Pack {
+            key: SRD_PACK,
+            name: "System Reference Document 5.1",
+            license: "CC-BY-4.0",
+            attribution: SRD_ATTRIBUTION,
+            monsters: load(SRD_MONSTERS),
+            spells: load(SRD_SPELLS),
+            items,
+        }
+    })
+}
+
+fn packs() -> [&'static Pack; 1] {
+    [srd()]
+}
+
+fn find_pack(key: &str) -> DmResult<&'static Pack> {
+    packs().into_iter()
+        .find(|pack| pack.key == key)
+        .ok_or_else(|| DmAssistantError::not_found("Content pack", key))
+}
+
+fn find_entry<'a, T>(entries: &'a [PackEntry<T>], kind: &str, key: &str) -> DmResult<&'a T> {
+    entries.iter()
+        .find(|entry| entry.key == key)
+        .map(|entry| &entry.content)
+        .ok_or_else(|| DmAssistantError::not_found(kind, key))
+}
+
+fn monster_summary(stat_block: &StatBlock) -> String {
+    let kind = format!("{:?} {:?}", stat_block.size, stat_block.creature_type).to_lowercase();
+    let kind = kind[..1].to_uppercase() + &kind[1..];
+    format!("{}, CR {}", kind, stat_block.challenge_rating)
+}
+
+/// Read-only compendium of content compiled into the app, browsed along with
+/// the homebrew of a campaign.
+///
+/// Compendium entries can't be edited; copying one into a campaign makes a
+/// homebrew entity that remembers the entry it came from. The SRD pack holds
+/// the monsters, spells, magic items and equipment of the SRD 5.1.
+pub struct CompendiumService;
+
+impl CompendiumService {
+    /// Content packs compiled into the app
+    pub fn list_packs() -> Vec<ContentPack> {
+        packs().into_iter()
+            .map(|pack| ContentPack {
+                key: pack.key.to_string(),
+                name: pack.name.to_string(),
+                license: pack.license.to_string(),
+                attribution: pack.attribution.to_string(),
+                monsters: pack.monsters.len(),
+                spells: pack.spells.len(),
+                items: pack.items.len(),
+            })
+            .collect()
+    }
+
+    /// Search the compendium and, if a campaign is given, its homebrew.
+    /// Results are sorted by name, homebrew first when names are equal.
+    pub fn search(query: &CompendiumQuery) -> DmResult<Vec<CompendiumListing>> {
+        let wanted = |kind: CompendiumKind| query.kind.is_none_or(|wanted| wanted == kind);
+        let text = query.text.as_deref().map(str::trim).unwrap_or_default().to_lowercase();
+        let mut listings = Vec::new();
+
+        for pack in packs() {
+            let compendium = |kind, key: &String, name: &String, summary| CompendiumListing {
+                kind,
+                origin: ContentOrigin::Compendium,
+                key: key.clone(),
+                name: name.clone(),
+                summary,
+                source: None,
+            };
+            if wanted(CompendiumKind::Monster) {
+                listings.extend(pack.monsters.iter().map(|entry| {
+                    compendium(CompendiumKind::Monster, &entry.key, &entry.content.name, monster_summary(&entry.content))
+                }));
+            }
+            if wanted(CompendiumKind::Spell) {
+                listings.extend(pack.spells.iter().map(|entry| {
+                    compendium(CompendiumKind::Spell, &entry.key, &entry.content.name, entry.content.level_and_school())
+                }));
+            }
+            if wanted(CompendiumKind::Item) {
+                listings.extend(pack.items.iter().map(|entry| {
+                    compendium(CompendiumKind::Item, &entry.key, &entry.content.name, entry.content.summary())
+                }));
+            }
+        }
+
+        if let Some(campaign_id) = &query.campaign_id {
+            let homebrew = |kind, id: String, name: &String, summary, source: &Option<ContentSource>| CompendiumListing {
+                kind,
+                origin: ContentOrigin::Homebrew,
+                key: id,
+                name: name.clone(),
+                summary,
+                source: source.clone(),
+            };
+            if wanted(CompendiumKind::Monster) {
+                listings.extend(MonsterStorageService::get_monsters_by_campaign(campaign_id)?.iter().map(|monster| {
+                    let summary = monster_summary(&monster.stat_block);
+                    homebrew(CompendiumKind::Monster, monster.id.to_string(), &monster.stat_block.name, summary, &monster.source)
+                }));
+            }
+            if wanted(CompendiumKind::Spell) {
+                listings.extend(SpellStorageService::get_spells_by_campaign(campaign_id)?.iter().map(|spell| {
+                    let summary = spell.details.level_and_school();
+                    homebrew(CompendiumKind::Spell, spell.id.to_string(), &spell.details.name, summary, &spell.source)
+                }));
+            }
+            if wanted(CompendiumKind::Item) {
+                listings.extend(ItemStorageService::get_items_by_campaign(campaign_id)?.iter().map(|item| {
+                    let summary = item.details.summary();
+                    homebrew(CompendiumKind::Item, item.id.to_string(), &item.details.name, summary, &item.source)
+                }));
+            }
+        }
+
+        listings.retain(|listing| listing.name.to_lowercase().contains(&text));
+        listings.sort_by(|a, b| {
+            a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                .then_with(|| (a.origin == ContentOrigin::Compendium).cmp(&(b.origin == ContentOrigin::Compendium)))
+        });
+        Ok(listings)
+    }
+
+    /// Full content of a compendium entry
+    pub fn get_entry(pack: &str, kind: CompendiumKind, key: &str) -> DmResult<CompendiumEntry> {
+        let pack = find_pack(pack)?;
+        Ok(match kind {
+            CompendiumKind::Monster => CompendiumEntry::Monster(Box::new(find_entry(&pack.monsters, "Monster", key)?.clone())),
+            CompendiumKind::Spell => CompendiumEntry::Spell(find_entry(&pack.spells, "Spell", key)?.clone()),
+            CompendiumKind::Item => CompendiumEntry::Item(find_entry(&pack.items, "Item", key)?.clone()),
+        })
+    }
+
+    /// Copy a compendium entry into a campaign as a homebrew entity, which
+    /// can be edited and remembers the entry it was copied from
+    pub fn copy_to_campaign(req: CopyCompendiumEntryRequest) -> DmResult<CopiedEntity> {
+        let mut entry = Self::get_entry(&req.pack, req.kind, &req.key)?;
+        if let Some(name) = req.name.filter(|name| !name.trim().is_empty()) {
+            match &mut entry {
+                CompendiumEntry::Monster(stat_block) => stat_block.name = name,
+                CompendiumEntry::Spell(details) => details.name = name,
+                CompendiumEntry::Item(details) => details.name = name,
+            }
+        }
+        let source = Some(ContentSource::new(&req.pack, &req.key));
+
+        Ok(match entry {
+            CompendiumEntry::Monster(stat_block) => {
+                let mut monster = Monster::new(CreateMonsterRequest { campaign_id: req.campaign_id, stat_block: *stat_block, notes: None });
+                monster.source = source;
+                CopiedEntity::Monster(Box::new(MonsterStorageService::create_monster(monster)?))
+            }
+            CompendiumEntry::Spell(details) => {
+                let mut spell = Spell::new(CreateSpellRequest { campaign_id: req.campaign_id, details, notes: None });
+                spell.source = source;
+                CopiedEntity::Spell(SpellStorageService::create_spell(spell)?)
+            }
+            CompendiumEntry::Item(details) => {
+                let mut item = Item::new(CreateItemRequest { campaign_id: req.campaign_id, details, notes: None });
+                item.source = source;
+                CopiedEntity::Item(ItemStorageService::create_item(item)?)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::monster::ChallengeRating;
+    use crate::core::models::spell::UpdateSpellRequest;
+    use crate::core::rules::challenge_rating;
+    use crate::services::history::HistoryService;
+    use crate::utils::test_support::create_test_campaign;
+
+    #[test]
+    fn test_every_bundled_file_loads() {
+        assert!(!parse::<StatBlock>(SRD_MONSTERS).unwrap().is_empty());
+        assert!(!parse::<SpellDetails>(SRD_SPELLS).unwrap().is_empty());
+        assert!(!parse::<ItemDetails>(SRD_EQUIPMENT).unwrap().is_empty());
+        assert!(!parse::<ItemDetails>(SRD_MAGIC_ITEMS).unwrap().is_empty());
+
+        let error = parse::<StatBlock>(("broken.json", "[{\"key\": ")).err().unwrap();
+        assert!(error.to_string().contains("broken.json"));
+    }
+
+    #[test]
+    fn test_srd_data_is_valid() {
+        let srd = srd();
+        assert!(!srd.monsters.is_empty() && !srd.spells.is_empty() && !srd.items.is_empty());
+
+        let mut keys = std::collections::HashSet::new();
+        for entry in &srd.monsters {
+            assert!(keys.insert(("monster", &entry.key)), "duplicate key {}", entry.key);
+            MonsterStorageService::validate_stat_block(&entry.content).unwrap();
+            challenge_rating::calculate(&entry.content).unwrap();
+        }
+        for entry in &srd.spells {
+            assert!(keys.insert(("spell", &entry.key)), "duplicate key {}", entry.key);
+            SpellStorageService::validate_details(&entry.content).unwrap();
+        }
+        for entry in &srd.items {
+            assert!(keys.insert(("item", &entry.key)), "duplicate key {}", entry.key);
+            ItemStorageService::validate_details(&entry.content).unwrap();
+        }
+
+        let CompendiumEntry::Monster(goblin) = CompendiumService::get_entry(SRD_PACK, CompendiumKind::Monster, "goblin").unwrap() else {
+            panic!("expected a monster");
+        };
+        assert_eq!(goblin.challenge_rating, "1/4".parse::<ChallengeRating>().unwrap());
+        assert!(CompendiumService::get_entry(SRD_PACK, CompendiumKind::Spell, "goblin").is_err());
+        assert!(CompendiumService::get_entry("unknown", CompendiumKind::Monster, "goblin").is_err());
+    }
+
+    #[test]
+    fn test_copy_entry_into_campaign() {
+        let campaign_id = create_test_campaign();
+        let copied = CompendiumService::copy_to_campaign(CopyCompendiumEntryRequest {
+            campaign_id,
+            pack: SRD_PACK.to_string(),
+            kind: CompendiumKind::Spell,
+            key: "fireball".to_string(),
+            name: None,
+        }).unwrap();
+        let CopiedEntity::Spell(spell) = copied else { panic!("expected a spell") };
+        assert_eq!(spell.details.name, "Fireball");
+        assert_eq!(spell.source.as_ref().unwrap().key, "fireball");
+
+        // The copy is homebrew: editable without touching the compendium
+        let mut details = spell.details.clone();
+        details.name = "Frostball".to_string();
+        SpellStorageService::update_spell(&campaign_id, &spell.id, UpdateSpellRequest { details: Some(details), notes: None }).unwrap();
+
+        let listings = CompendiumService::search(&CompendiumQuery {
+            campaign_id: Some(campaign_id),
+            kind: Some(CompendiumKind::Spell),
+            text: Some("BALL".to_string()),
+        }).unwrap();
+        let names: Vec<(&str, ContentOrigin)> = listings.iter().map(|l| (l.name.as_str(), l.origin)).collect();
+        assert_eq!(names, vec![
+            ("Delayed Blast Fireball", ContentOrigin::Compendium),
+            ("Fireball", ContentOrigin::Compendium),
+            ("Frostball", ContentOrigin::Homebrew),
+        ]);
+        assert_eq!(listings[2].source.as_ref().unwrap().pack, SRD_PACK);
+
+        // Copies are in the history like any other entity
+        CompendiumService::copy_to_campaign(CopyCompendiumEntryRequest {
+            campaign_id,
+            pack: SRD_PACK.to_string(),
+            kind: CompendiumKind::Monster,
+            key: "owlbear".to_string(),
+            name: Some("Moonfang".to_string()),
+        }).unwrap();
+        assert_eq!(MonsterStorageService::get_monsters_by_campaign(&campaign_id).unwrap()[0].stat_block.name, "Moonfang");
+        HistoryService::undo(&campaign_id).unwrap();
+        assert!(MonsterStorageService::get_monsters_by_campaign(&campaign_id).unwrap().is_empty());
+    }
+}
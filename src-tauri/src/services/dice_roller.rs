@@ -0,0 +1,261 @@
+// src-tauri/src/services/dice_roller.rs
+
+use crate::core::models::common::{
+    DiceRoll, RollDiceRequest, RollHistoryFilter, RollRecord, RollVerification, SCHEMA_VERSION,
+};
+use crate::core::rules::dice;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::services::storage::CampaignStore;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Roll produced by the dice roller, with the seed needed to reproduce it
+#[derive(Debug, Clone)]
+pub struct SeededRoll {
+    pub seed: u64,
+    pub roll: DiceRoll,
+}
+
+/// Dice roller with an injectable seed source.
+///
+/// Every roll draws a fresh seed from the seed source and evaluates the
+/// expression with a ChaCha8 RNG seeded from it, so the same expression and
+/// seed always produce the same dice.
+pub struct DiceRoller<R: RngCore = ChaCha8Rng> {
+    seeds: R,
+}
+
+impl DiceRoller {
+    /// Create a roller seeded from OS entropy
+    pub fn new() -> Self {
+        Self { seeds: ChaCha8Rng::from_entropy() }
+    }
+
+    /// Create a deterministic roller (for tests and replays)
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seeds: ChaCha8Rng::seed_from_u64(seed) }
+    }
+}
+
+impl Default for DiceRoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: RngCore> DiceRoller<R> {
+    /// Create a roller drawing seeds from the given RNG
+    pub fn with_rng(seeds: R) -> Self {
+        Self { seeds }
+    }
+
+    /// Roll a dice expression
+    pub fn roll(&mut self, expression: &str) -> DmResult<SeededRoll> {
+        // Parse first so invalid expressions don't consume a seed
+        let parsed = dice::parse(expression)?;
+        let seed = self.seeds.next_u64();
+        let roll = parsed.roll(expression, &mut ChaCha8Rng::seed_from_u64(seed))?;
+        Ok(SeededRoll { seed, roll })
+    }
+}
+
+/// Replay a dice expression from a stored seed
+pub fn roll_from_seed(expression: &str, seed: u64) -> DmResult<DiceRoll> {
+    dice::roll_with_rng(expression, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+/// Roll history for a campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollHistory {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub campaign_id: Uuid,
+    pub rolls: Vec<RollRecord>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RollHistory {
+    pub fn new(campaign_id: Uuid) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            campaign_id,
+            rolls: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn add_roll(&mut self, record: RollRecord) {
+        self.rolls.push(record);
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn get_roll(&self, roll_id: &Uuid) -> Option<&RollRecord> {
+        self.rolls.iter().find(|r| r.id == *roll_id)
+    }
+
+    /// Get rolls matching the filter, newest first
+    pub fn filter(&self, filter: &RollHistoryFilter) -> Vec<&RollRecord> {
+        let rolls = self.rolls.iter().rev().filter(|r| filter.matches(r));
+        match filter.limit {
+            Some(limit) => rolls.take(limit).collect(),
+            None => rolls.collect(),
+        }
+    }
+}
+
+/// File-based roll history storage
+pub struct RollHistoryService;
+
+impl RollHistoryService {
+    /// Get file path for campaign roll history
+    fn get_history_file_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        Ok(campaign_dir.join("roll_history.json"))
+    }
+
+    /// Load roll history for a campaign
+    pub fn load_history(campaign_id: &Uuid) -> DmResult<RollHistory> {
+        let file_path = Self::get_history_file_path(campaign_id)?;
+
+        CampaignStore::global().load_or_else(&file_path, || RollHistory::new(*campaign_id))
+    }
+
+    /// Save roll history for a campaign
+    pub fn save_history(history: &RollHistory) -> DmResult<()> {
+        let file_path = Self::get_history_file_path(&history.campaign_id)?;
+        CampaignStore::global().save(history, &file_path)
+    }
+
+    /// Roll dice and record the result in the campaign history
+    pub fn roll<R: RngCore>(
+        roller: &mut DiceRoller<R>,
+        campaign_id: &Uuid,
+        req: RollDiceRequest,
+    ) -> DmResult<RollRecord> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let SeededRoll { seed, roll } = roller.roll(&req.expression)?;
+
+        let record = RollRecord {
+            id: Uuid::new_v4(),
+            campaign_id: *campaign_id,
+            expression: roll.expression.clone(),
+            seed,
+            result: roll,
+            roller: req.roller.filter(|r| !r.trim().is_empty()),
+            session_number: req.session_number,
+            label: req.label.filter(|l| !l.trim().is_empty()),
+            rolled_at: chrono::Utc::now(),
+        };
+
+        let mut history = Self::load_history(campaign_id)?;
+        history.add_roll(record.clone());
+        Self::save_history(&history)?;
+
+        Ok(record)
+    }
+
+    /// Get rolls matching a filter, newest first
+    pub fn get_history(campaign_id: &Uuid, filter: &RollHistoryFilter) -> DmResult<Vec<RollRecord>> {
+        let history = Self::load_history(campaign_id)?;
+        Ok(history.filter(filter).into_iter().cloned().collect())
+    }
+
+    /// Replay a recorded roll from its seed and compare with the stored result
+    pub fn verify_roll(campaign_id: &Uuid, roll_id: &Uuid) -> DmResult<RollVerification> {
+        let history = Self::load_history(campaign_id)?;
+        let record = history.get_roll(roll_id)
+            .ok_or_else(|| DmAssistantError::not_found("Roll", &roll_id.to_string()))?;
+
+        let replayed = roll_from_seed(&record.expression, record.seed)?;
+        Ok(RollVerification {
+            roll_id: record.id,
+            verified: replayed == record.result,
+            recorded: record.result.clone(),
+            replayed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::create_test_campaign;
+
+    fn request(expression: &str, roller: &str, session: u32) -> RollDiceRequest {
+        RollDiceRequest {
+            expression: expression.to_string(),
+            roller: Some(roller.to_string()),
+            session_number: Some(session),
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_seeded_roller_is_reproducible() {
+        let mut first = DiceRoller::with_seed(1234);
+        let mut second = DiceRoller::with_seed(1234);
+
+        for _ in 0..20 {
+            let a = first.roll("4d6kh3 + 1d20!").unwrap();
+            let b = second.roll("4d6kh3 + 1d20!").unwrap();
+            assert_eq!(a.seed, b.seed);
+            assert_eq!(a.roll, b.roll);
+            assert_eq!(roll_from_seed("4d6kh3 + 1d20!", a.seed).unwrap(), a.roll);
+        }
+    }
+
+    #[test]
+    fn test_invalid_expression_is_rejected() {
+        let mut roller = DiceRoller::with_seed(1);
+        assert!(matches!(roller.roll("2d"), Err(DmAssistantError::DiceError { .. })));
+    }
+
+    #[test]
+    fn test_history_filters_and_verification() {
+        let campaign_id = create_test_campaign();
+        let mut roller = DiceRoller::with_seed(99);
+
+        RollHistoryService::roll(&mut roller, &campaign_id, request("1d20 + 5", "Aria", 1)).unwrap();
+        RollHistoryService::roll(&mut roller, &campaign_id, request("2d6", "DM", 1)).unwrap();
+        let last = RollHistoryService::roll(&mut roller, &campaign_id, request("1D20+2", "aria", 2)).unwrap();
+
+        let by_roller = RollHistoryFilter { roller: Some("ARIA".to_string()), ..Default::default() };
+        let rolls = RollHistoryService::get_history(&campaign_id, &by_roller).unwrap();
+        assert_eq!(rolls.len(), 2);
+        assert_eq!(rolls[0].id, last.id);
+
+        let by_session = RollHistoryFilter { session_number: Some(1), ..Default::default() };
+        assert_eq!(RollHistoryService::get_history(&campaign_id, &by_session).unwrap().len(), 2);
+
+        let by_expression = RollHistoryFilter { expression: Some("1d20".to_string()), limit: Some(1), ..Default::default() };
+        let rolls = RollHistoryService::get_history(&campaign_id, &by_expression).unwrap();
+        assert_eq!(rolls.len(), 1);
+        assert_eq!(rolls[0].id, last.id);
+
+        let verification = RollHistoryService::verify_roll(&campaign_id, &last.id).unwrap();
+        assert!(verification.verified);
+    }
+
+    #[test]
+    fn test_tampered_roll_fails_verification() {
+        let campaign_id = create_test_campaign();
+        let mut roller = DiceRoller::with_seed(5);
+        let record = RollHistoryService::roll(&mut roller, &campaign_id, request("3d6", "DM", 1)).unwrap();
+
+        let mut history = RollHistoryService::load_history(&campaign_id).unwrap();
+        history.rolls[0].result.total += 1;
+        RollHistoryService::save_history(&history).unwrap();
+
+        let verification = RollHistoryService::verify_roll(&campaign_id, &record.id).unwrap();
+        assert!(!verification.verified);
+    }
+}
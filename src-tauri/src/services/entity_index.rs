@@ -0,0 +1,310 @@
+// src-tauri/src/services/entity_index.rs
+
+use crate::core::models::common::{EntityKind, EntityLocation, SCHEMA_VERSION};
+use crate::services::campaign_storage::CampaignStorageService;
+#[cfg(feature = "sqlite")]
+use crate::services::sqlite_storage::list_entity_ids;
+#[cfg(feature = "sqlite")]
+use crate::services::storage::SQLITE_FILE;
+use crate::utils::error::DmResult;
+use crate::utils::file_system::{get_app_data_dir, get_campaigns_dir, save_json, load_json, file_exists};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Entity files of a campaign that are indexed: kind, file name and the
+/// field holding the entities (a map keyed by ID or a list of objects with an `id`)
+const INDEXED_FILES: &[(EntityKind, &str, &str)] = &[
+    (EntityKind::Character, "characters.json", "characters"),
+    (EntityKind::Npc, "npcs.json", "npcs"),
+    (EntityKind::Monster, "monsters.json", "monsters"),
+    (EntityKind::Spell, "spells.json", "spells"),
+    (EntityKind::Item, "items.json", "items"),
+];
+
+/// Serializes read-modify-write cycles on the index file
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// Global index of every entity: entity ID -> campaign and kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityIndex {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub entries: HashMap<Uuid, EntityLocation>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl EntityIndex {
+    pub fn new() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            entries: HashMap::new(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn insert(&mut self, entity_id: Uuid, location: EntityLocation) {
+        self.entries.insert(entity_id, location);
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn remove(&mut self, entity_id: &Uuid) -> bool {
+        let removed = self.entries.remove(entity_id).is_some();
+        if removed {
+            self.updated_at = chrono::Utc::now();
+        }
+        removed
+    }
+
+    /// Remove every entity of a campaign
+    pub fn remove_campaign(&mut self, campaign_id: &Uuid) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, location| location.campaign_id != *campaign_id);
+        let removed = before - self.entries.len();
+        if removed > 0 {
+            self.updated_at = chrono::Utc::now();
+        }
+        removed
+    }
+}
+
+impl Default for EntityIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Entity index maintained by the storage services.
+///
+/// The index is derived data: if it's missing or unreadable it's rebuilt by
+/// scanning the campaign directories.
+pub struct EntityIndexService;
+
+impl EntityIndexService {
+    /// Get index file path
+    fn get_index_file_path() -> DmResult<PathBuf> {
+        Ok(get_app_data_dir()?.join("index.json"))
+    }
+
+    /// Load the index, rebuilding it if it's missing or corrupt. Caller must hold the lock.
+    fn load_index_locked() -> DmResult<EntityIndex> {
+        let file_path = Self::get_index_file_path()?;
+
+        if file_exists(&file_path) {
+            match load_json::<EntityIndex>(&file_path) {
+                Ok(index) if index.schema_version == SCHEMA_VERSION => return Ok(index),
+                Ok(index) => eprintln!("Entity index has schema version {}, rebuilding it", index.schema_version),
+                Err(e) => eprintln!("Entity index is unreadable, rebuilding it: {}", e),
+            }
+        }
+        Self::rebuild_locked()
+    }
+
+    /// Scan every campaign and rewrite the index. Caller must hold the lock.
+    fn rebuild_locked() -> DmResult<EntityIndex> {
+        let mut index = EntityIndex::new();
+        for campaign_id in CampaignStorageService::list_campaign_ids()? {
+            for (entity_id, kind) in Self::scan_campaign(&campaign_id)? {
+                index.insert(entity_id, EntityLocation { campaign_id, kind });
+            }
+        }
+
+        save_json(&index, &Self::get_index_file_path()?)?;
+        Ok(index)
+    }
+
+    /// Find every entity stored in a campaign directory
+    pub fn scan_campaign(campaign_id: &Uuid) -> DmResult<Vec<(Uuid, EntityKind)>> {
+        let campaign_dir = get_campaigns_dir()?.join(campaign_id.to_string());
+
+        let mut found = Vec::new();
+        for (kind, file_name, field) in INDEXED_FILES {
+            let file_path = campaign_dir.join(file_name);
+            if !file_exists(&file_path) {
+                continue;
+            }
+            let document: serde_json::Value = match load_json(&file_path) {
+                Ok(document) => document,
+                // A single broken file shouldn't prevent indexing everything else
+                Err(e) => {
+                    eprintln!("Skipping unreadable file {}: {}", file_path.display(), e);
+                    continue;
+                }
+            };
+
+            let ids: Vec<Uuid> = match document.get(field) {
+                Some(serde_json::Value::Object(map)) => map.keys()
+                    .filter_map(|key| Uuid::parse_str(key).ok())
+                    .collect(),
+                Some(serde_json::Value::Array(items)) => items.iter()
+                    .filter_map(|item| item.get("id")?.as_str())
+                    .filter_map(|id| Uuid::parse_str(id).ok())
+                    .collect(),
+                _ => Vec::new(),
+            };
+            found.extend(ids.into_iter().map(|id| (id, *kind)));
+        }
+
+        #[cfg(feature = "sqlite")]
+        {
+            let database = campaign_dir.join(SQLITE_FILE);
+            if file_exists(&database) {
+                match list_entity_ids(&database) {
+                    Ok(ids) => found.extend(ids.into_iter().filter_map(|(collection, id)| {
+                        INDEXED_FILES.iter()
+                            .find(|(_, _, field)| *field == collection)
+                            .map(|(kind, _, _)| (id, *kind))
+                    })),
+                    Err(e) => eprintln!("Skipping unreadable database {}: {}", database.display(), e),
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Rebuild the whole index from disk, returning the number of indexed entities
+    pub fn rebuild() -> DmResult<usize> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(Self::rebuild_locked()?.entries.len())
+    }
+
+    /// Apply a change to the index and save it
+    fn update<F>(modifier: F) -> DmResult<()>
+    where
+        F: FnOnce(&mut EntityIndex),
+    {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = Self::load_index_locked()?;
+        modifier(&mut index);
+        save_json(&index, &Self::get_index_file_path()?)
+    }
+
+    /// Record an entity in the index
+    pub fn register(entity_id: &Uuid, campaign_id: &Uuid, kind: EntityKind) -> DmResult<()> {
+        Self::update(|index| index.insert(*entity_id, EntityLocation { campaign_id: *campaign_id, kind }))
+    }
+
+    /// Remove an entity from the index
+    pub fn unregister(entity_id: &Uuid) -> DmResult<()> {
+        Self::update(|index| { index.remove(entity_id); })
+    }
+
+    /// Index every entity of a campaign (e.g. after it's been copied or imported)
+    pub fn register_campaign(campaign_id: &Uuid) -> DmResult<()> {
+        let found = Self::scan_campaign(campaign_id)?;
+        Self::update(|index| {
+            for (entity_id, kind) in found {
+                index.insert(entity_id, EntityLocation { campaign_id: *campaign_id, kind });
+            }
+        })
+    }
+
+    /// Remove every entity of a campaign from the index
+    pub fn unregister_campaign(campaign_id: &Uuid) -> DmResult<()> {
+        Self::update(|index| { index.remove_campaign(campaign_id); })
+    }
+
+    /// Find where an entity lives
+    pub fn lookup(entity_id: &Uuid) -> DmResult<Option<EntityLocation>> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(Self::load_index_locked()?.entries.get(entity_id).copied())
+    }
+
+    /// Find where an entity lives, rebuilding the index once if `is_valid`
+    /// says the entry is stale. An ID missing from the index is unknown: the
+    /// index is rebuilt when its file is lost, not for every lookup of a
+    /// deleted entity.
+    pub fn resolve<F>(entity_id: &Uuid, is_valid: F) -> DmResult<Option<EntityLocation>>
+    where
+        F: Fn(&EntityLocation) -> DmResult<bool>,
+    {
+        match Self::lookup(entity_id)? {
+            Some(location) if is_valid(&location)? => return Ok(Some(location)),
+            Some(_) => {}
+            None => return Ok(None),
+        }
+
+        Self::rebuild()?;
+        match Self::lookup(entity_id)? {
+            Some(location) if is_valid(&location)? => Ok(Some(location)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, PlayerCharacter};
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::utils::test_support::create_test_campaign;
+
+    fn new_character(campaign_id: Uuid) -> PlayerCharacter {
+        PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: "Vex".to_string(),
+            race: "Half-Elf".to_string(),
+            class: "Ranger".to_string(),
+            level: 5,
+            max_hp: 40,
+            background: String::new(),
+            notes: None,
+        })
+    }
+
+    #[test]
+    fn test_index_follows_create_and_delete() {
+        let campaign_id = create_test_campaign();
+        let character = CharacterStorageService::create_character(new_character(campaign_id)).unwrap();
+
+        let location = EntityIndexService::lookup(&character.id).unwrap().unwrap();
+        assert_eq!(location.campaign_id, campaign_id);
+        assert_eq!(location.kind, EntityKind::Character);
+        assert_eq!(CharacterStorageService::find_character(&character.id).unwrap().unwrap().id, character.id);
+
+        CharacterStorageService::delete_character(&campaign_id, &character.id).unwrap();
+        assert!(EntityIndexService::lookup(&character.id).unwrap().is_none());
+        assert!(CharacterStorageService::find_character(&character.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stale_entries_are_rebuilt() {
+        let campaign_id = create_test_campaign();
+        let character = CharacterStorageService::create_character(new_character(campaign_id)).unwrap();
+
+        // A missing entry is an unknown ID, until the index is rebuilt
+        EntityIndexService::unregister(&character.id).unwrap();
+        assert!(CharacterStorageService::find_character(&character.id).unwrap().is_none());
+        assert!(EntityIndexService::lookup(&character.id).unwrap().is_none());
+        EntityIndexService::rebuild().unwrap();
+        let found = CharacterStorageService::find_character(&character.id).unwrap().unwrap();
+        assert_eq!(found.campaign_id, campaign_id);
+
+        // Point the entry to the wrong campaign
+        EntityIndexService::register(&character.id, &Uuid::new_v4(), EntityKind::Character).unwrap();
+        let found = CharacterStorageService::find_character(&character.id).unwrap().unwrap();
+        assert_eq!(found.campaign_id, campaign_id);
+    }
+
+    #[test]
+    fn test_scan_campaign_finds_entities() {
+        let campaign_id = create_test_campaign();
+        let first = CharacterStorageService::create_character(new_character(campaign_id)).unwrap();
+        let second = CharacterStorageService::create_character(new_character(campaign_id)).unwrap();
+
+        let mut found: Vec<Uuid> = EntityIndexService::scan_campaign(&campaign_id).unwrap()
+            .into_iter()
+            .map(|(id, kind)| {
+                assert_eq!(kind, EntityKind::Character);
+                id
+            })
+            .collect();
+        found.sort();
+        let mut expected = vec![first.id, second.id];
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,223 @@
+// src-tauri/src/services/events.rs
+
+use crate::core::models::event::{CampaignReloadedEvent, EntityEvent, ReloadReason};
+use crate::core::models::history::EntityChange;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+/// Receiver of the entity events sent by the storage layer
+pub trait ChangeEmitter: Send + Sync {
+    fn emit(&self, event: &EntityEvent);
+    fn emit_reloaded(&self, event: &CampaignReloadedEvent);
+}
+
+/// Sends entity events to the frontend as Tauri events
+pub struct TauriEmitter {
+    app: AppHandle,
+}
+
+impl TauriEmitter {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl ChangeEmitter for TauriEmitter {
+    fn emit(&self, event: &EntityEvent) {
+        if let Err(e) = self.app.emit(event.event_name(), event.clone()) {
+            eprintln!("Failed to emit {} for {}: {}", event.event_name(), event.id, e);
+        }
+    }
+
+    fn emit_reloaded(&self, event: &CampaignReloadedEvent) {
+        if let Err(e) = self.app.emit(CampaignReloadedEvent::EVENT_NAME, event.clone()) {
+            eprintln!("Failed to emit {} for {}: {}", CampaignReloadedEvent::EVENT_NAME, event.campaign_id, e);
+        }
+    }
+}
+
+/// ID of a subscribed emitter, to unsubscribe it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscriptionId(u64);
+
+type Subscribers = RwLock<Vec<(SubscriptionId, Arc<dyn ChangeEmitter>)>>;
+
+fn subscribers() -> &'static Subscribers {
+    static SUBSCRIBERS: OnceLock<Subscribers> = OnceLock::new();
+    SUBSCRIBERS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Stream of entity changes to the frontend.
+///
+/// The storage layer sends an event for every change it makes to an entity,
+/// including undo and redo, so the frontend stores stay in sync without
+/// polling. Edges mirrored from character relationships change with their
+/// character, and have no events of their own. Operations replacing a whole
+/// campaign (restoring, importing, converting its storage) send a single
+/// `campaign_reloaded` event instead.
+pub struct EventService;
+
+impl EventService {
+    /// Send the events of the app to an emitter
+    pub fn subscribe(emitter: Arc<dyn ChangeEmitter>) -> SubscriptionId {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = SubscriptionId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        subscribers().write().unwrap_or_else(|e| e.into_inner()).push((id, emitter));
+        id
+    }
+
+    /// Stop sending events to an emitter. Returns false if it wasn't subscribed.
+    pub fn unsubscribe(id: SubscriptionId) -> bool {
+        let mut subscribers = subscribers().write().unwrap_or_else(|e| e.into_inner());
+        let before = subscribers.len();
+        subscribers.retain(|(subscription, _)| *subscription != id);
+        subscribers.len() != before
+    }
+
+    /// Emitters are called without holding the lock, so they can subscribe or unsubscribe
+    fn emitters() -> Vec<Arc<dyn ChangeEmitter>> {
+        subscribers().read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(_, emitter)| emitter.clone())
+            .collect()
+    }
+
+    /// Send an event to every subscribed emitter
+    pub fn emit(event: &EntityEvent) {
+        for emitter in Self::emitters() {
+            emitter.emit(event);
+        }
+    }
+
+    /// Tell every subscribed emitter that a campaign was replaced as a whole
+    pub fn campaign_reloaded(campaign_id: &Uuid, reason: ReloadReason) {
+        let event = CampaignReloadedEvent { campaign_id: *campaign_id, reason };
+        for emitter in Self::emitters() {
+            emitter.emit_reloaded(&event);
+        }
+    }
+
+    /// Send the event of a change made to an entity
+    pub fn entity_changed(campaign_id: &Uuid, change: &EntityChange) {
+        Self::emit(&EntityEvent::new(*campaign_id, &change.collection, change.entity_id, &change.operation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, PlayerCharacter, RelationshipType};
+    use crate::core::models::event::ChangeType;
+    use crate::core::models::relationship::{CreateEdgeRequest, GraphNode};
+    use crate::core::models::npc::{CreateNpcRequest, Npc};
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::services::history::HistoryService;
+    use crate::services::npc_storage::NpcStorageService;
+    use crate::services::relationship_graph::RelationshipGraphService;
+    use crate::utils::json_diff::ValueDiff;
+    use crate::utils::test_support::{create_test_campaign, MockEmitter};
+    use serde_json::json;
+
+    fn new_character(campaign_id: Uuid) -> PlayerCharacter {
+        CharacterStorageService::create_character(PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: "Elowen".to_string(),
+            race: "Elf".to_string(),
+            class: "Wizard".to_string(),
+            level: 1,
+            max_hp: 6,
+            background: String::new(),
+            notes: None,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_entity_events() {
+        let campaign_id = create_test_campaign();
+        let events = MockEmitter::subscribe(campaign_id);
+
+        let character = new_character(campaign_id);
+        let created = events.take();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].event_name(), "entity_created");
+        assert_eq!(created[0].kind, "characters");
+        assert_eq!(created[0].id, character.id);
+        assert_eq!(created[0].campaign_id, campaign_id);
+
+        CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+            c.level = 2;
+            Ok(())
+        }).unwrap();
+        let updated = events.take();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].change_type, ChangeType::Updated);
+        // The diff carries the changed fields only
+        let ValueDiff::Object { changed, .. } = &updated[0].diff else { panic!("expected an object diff") };
+        assert_eq!(changed["level"], ValueDiff::Replace { before: json!(1), after: json!(2) });
+        assert!(!changed.contains_key("name"));
+
+        CharacterStorageService::delete_character(&campaign_id, &character.id).unwrap();
+        let deleted = events.take();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].event_name(), "entity_deleted");
+        assert!(matches!(&deleted[0].diff, ValueDiff::Replace { after, .. } if after.is_null()));
+
+        // Undoing is a change like any other
+        HistoryService::undo(&campaign_id).unwrap();
+        let restored = events.take();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].change_type, ChangeType::Created);
+        assert_eq!(restored[0].id, character.id);
+    }
+
+    #[test]
+    fn test_relationship_events_and_unsubscribe() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id);
+        let npc = NpcStorageService::create_npc(Npc::new(CreateNpcRequest {
+            campaign_id,
+            name: "Mira".to_string(),
+            race: None,
+            role: None,
+            location_id: None,
+            faction: None,
+            attitude: None,
+            voice: None,
+            mannerisms: None,
+            secrets: None,
+            portrait_path: None,
+            notes: None,
+        })).unwrap();
+
+        let events = MockEmitter::subscribe(campaign_id);
+        let edge = RelationshipGraphService::add_edge(&campaign_id, CreateEdgeRequest {
+            from: GraphNode::Npc(npc.id),
+            to: GraphNode::Character(character.id),
+            relationship_type: RelationshipType::Ally,
+            mutual: None,
+            notes: None,
+        }).unwrap();
+        let added = events.take();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].kind, "relationships");
+        assert_eq!(added[0].id, edge.id);
+
+        // Deleting the NPC deletes its edge too
+        NpcStorageService::delete_npc(&campaign_id, &npc.id, true).unwrap();
+        let deleted: Vec<(String, Uuid)> = events.take().into_iter()
+            .inspect(|event| assert_eq!(event.change_type, ChangeType::Deleted))
+            .map(|event| (event.kind, event.id))
+            .collect();
+        assert_eq!(deleted.len(), 2);
+        assert!(deleted.contains(&("relationships".to_string(), edge.id)));
+        assert!(deleted.contains(&("npcs".to_string(), npc.id)));
+
+        let emitter = events.emitter.clone();
+        drop(events);
+        HistoryService::undo(&campaign_id).unwrap();
+        assert!(emitter.take().is_empty());
+    }
+}
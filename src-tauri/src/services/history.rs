@@ -0,0 +1,461 @@
+// src-tauri/src/services/history.rs
+
+use crate::core::models::character::PlayerCharacter;
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::core::models::event::EntityEvent;
+use crate::core::models::history::{ChangeOperation, EntityChange, HistoryEntry, HistoryList, HistorySummary};
+use crate::core::models::item::Item;
+use crate::core::models::monster::Monster;
+use crate::core::models::npc::Npc;
+use crate::core::models::spell::Spell;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::events::EventService;
+use crate::services::relationship_graph::RelationshipGraphService;
+use crate::services::storage::{CampaignRepository, CampaignStore, Entity};
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// File of the campaign directory holding its history
+pub const HISTORY_FILE: &str = "history.json";
+
+/// Entries kept in the undo stack of a campaign; older ones are dropped
+pub const MAX_HISTORY_ENTRIES: usize = 100;
+
+/// Collection name of relationship edges in the history
+pub const EDGE_COLLECTION: &str = "relationships";
+
+/// Undo and redo stacks of a campaign, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryJournal {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub campaign_id: Uuid,
+    pub undo: Vec<HistoryEntry>,
+    pub redo: Vec<HistoryEntry>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HistoryJournal {
+    pub fn new(campaign_id: Uuid) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            campaign_id,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+/// Changes collected while a batch runs on this thread
+struct Batch {
+    campaign_id: Uuid,
+    label: Option<String>, // Description of the first change
+    changes: Vec<EntityChange>,
+}
+
+thread_local! {
+    static BATCH: RefCell<Option<Batch>> = const { RefCell::new(None) };
+}
+
+/// Ends the batch of this thread, even if the operation panics
+struct BatchGuard;
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        BATCH.with(|batch| batch.borrow_mut().take());
+    }
+}
+
+/// Per-campaign journal of entity changes, for undo and redo.
+///
+/// The storage layer records every change: the state of created and deleted
+/// entities, and what changed in updated ones. Undoing applies the reverse
+/// change, redoing the change again.
+/// The journal lives in the campaign directory, so it survives restarts and
+/// is backed up and restored with the campaign.
+pub struct HistoryService;
+
+impl HistoryService {
+    fn journal_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        Ok(CampaignStorageService::get_campaign_dir_path(campaign_id)?.join(HISTORY_FILE))
+    }
+
+    /// Load the journal of a campaign
+    pub fn load_journal(campaign_id: &Uuid) -> DmResult<HistoryJournal> {
+        CampaignStore::global().load_or_else(&Self::journal_path(campaign_id)?, || HistoryJournal::new(*campaign_id))
+    }
+
+    fn save_journal(journal: &HistoryJournal) -> DmResult<()> {
+        CampaignStore::global().save(journal, &Self::journal_path(&journal.campaign_id)?)
+    }
+
+    /// Record a change made by the storage layer, which holds the campaign lock.
+    /// `label` describes it, e.g. "Update Character 'Brann'".
+    ///
+    /// A failure to record is reported but doesn't fail the change itself,
+    /// which has already been made.
+    pub fn record(campaign_id: &Uuid, label: &str, change: EntityChange) {
+        let mut change = Some(change);
+        BATCH.with(|batch| {
+            if let Some(batch) = batch.borrow_mut().as_mut().filter(|b| b.campaign_id == *campaign_id) {
+                batch.label.get_or_insert_with(|| label.to_string());
+                batch.changes.extend(change.take());
+            }
+        });
+        let Some(change) = change else {
+            return;
+        };
+
+        let entry = HistoryEntry {
+            id: Uuid::new_v4(),
+            label: label.to_string(),
+            changes: vec![change],
+            recorded_at: Utc::now(),
+        };
+        if let Err(e) = Self::push(campaign_id, entry) {
+            eprintln!("Failed to record history of campaign {}: {}", campaign_id, e);
+        }
+    }
+
+    /// Run an operation as a single history entry: undoing it reverts every
+    /// change it made. Batches started inside another one join it.
+    pub fn batch<R, F>(campaign_id: &Uuid, operation: F) -> DmResult<R>
+    where
+        F: FnOnce() -> DmResult<R>,
+    {
+        let _lock = lock_campaign(campaign_id)?;
+        if BATCH.with(|batch| batch.borrow().is_some()) {
+            return operation();
+        }
+
+        BATCH.with(|batch| {
+            *batch.borrow_mut() = Some(Batch { campaign_id: *campaign_id, label: None, changes: Vec::new() });
+        });
+        let _guard = BatchGuard;
+        let result = operation();
+
+        // Changes made before a failure are recorded too, so they can be undone
+        if let Some(batch) = BATCH.with(|batch| batch.borrow_mut().take()).filter(|b| !b.changes.is_empty()) {
+            let entry = HistoryEntry {
+                id: Uuid::new_v4(),
+                label: batch.label.unwrap_or_default(),
+                changes: batch.changes,
+                recorded_at: Utc::now(),
+            };
+            if let Err(e) = Self::push(campaign_id, entry) {
+                eprintln!("Failed to record history of campaign {}: {}", campaign_id, e);
+            }
+        }
+        result
+    }
+
+    /// Add an entry to the undo stack. A new change makes the redo stack obsolete.
+    fn push(campaign_id: &Uuid, entry: HistoryEntry) -> DmResult<()> {
+        let mut journal = Self::load_journal(campaign_id)?;
+        journal.undo.push(entry);
+        journal.redo.clear();
+        let excess = journal.undo.len().saturating_sub(MAX_HISTORY_ENTRIES);
+        journal.undo.drain(..excess);
+        journal.updated_at = Utc::now();
+        Self::save_journal(&journal)
+    }
+
+    /// Undo and redo stacks of a campaign
+    pub fn list_history(campaign_id: &Uuid) -> DmResult<HistoryList> {
+        CampaignStorageService::ensure_exists(campaign_id)?;
+        let journal = Self::load_journal(campaign_id)?;
+        Ok(HistoryList {
+            undo: journal.undo.iter().rev().map(HistorySummary::from).collect(),
+            redo: journal.redo.iter().rev().map(HistorySummary::from).collect(),
+        })
+    }
+
+    /// Undo the last entry. Returns None if there's nothing to undo.
+    pub fn undo(campaign_id: &Uuid) -> DmResult<Option<HistorySummary>> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let mut journal = Self::load_journal(campaign_id)?;
+        let Some(entry) = journal.undo.pop() else {
+            return Ok(None);
+        };
+
+        let steps = entry.changes.iter().rev()
+            .map(|change| (change, change.operation.reversed()))
+            .collect();
+        Self::apply_all(campaign_id, steps)?;
+
+        let summary = HistorySummary::from(&entry);
+        journal.redo.push(entry);
+        journal.updated_at = Utc::now();
+        Self::save_journal(&journal)?;
+        Ok(Some(summary))
+    }
+
+    /// Redo the last undone entry. Returns None if there's nothing to redo.
+    pub fn redo(campaign_id: &Uuid) -> DmResult<Option<HistorySummary>> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let mut journal = Self::load_journal(campaign_id)?;
+        let Some(entry) = journal.redo.pop() else {
+            return Ok(None);
+        };
+
+        let steps = entry.changes.iter()
+            .map(|change| (change, change.operation.clone()))
+            .collect();
+        Self::apply_all(campaign_id, steps)?;
+
+        let summary = HistorySummary::from(&entry);
+        journal.undo.push(entry);
+        journal.updated_at = Utc::now();
+        Self::save_journal(&journal)?;
+        Ok(Some(summary))
+    }
+
+    /// Apply operations to the changed entities in order, reverting the ones
+    /// already applied if one fails
+    fn apply_all(campaign_id: &Uuid, steps: Vec<(&EntityChange, ChangeOperation)>) -> DmResult<()> {
+        for (i, (change, operation)) in steps.iter().enumerate() {
+            if let Err(e) = Self::apply(campaign_id, change, operation) {
+                for (change, operation) in steps[..i].iter().rev() {
+                    if let Err(e) = Self::apply(campaign_id, change, &operation.reversed()) {
+                        eprintln!("Failed to roll back history of campaign {}: {}", campaign_id, e);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(campaign_id: &Uuid, change: &EntityChange, operation: &ChangeOperation) -> DmResult<()> {
+        match change.collection.as_str() {
+            PlayerCharacter::COLLECTION => Self::apply_entity::<PlayerCharacter>(campaign_id, &change.entity_id, operation)?,
+            Npc::COLLECTION => Self::apply_entity::<Npc>(campaign_id, &change.entity_id, operation)?,
+            Monster::COLLECTION => Self::apply_entity::<Monster>(campaign_id, &change.entity_id, operation)?,
+            Spell::COLLECTION => Self::apply_entity::<Spell>(campaign_id, &change.entity_id, operation)?,
+            Item::COLLECTION => Self::apply_entity::<Item>(campaign_id, &change.entity_id, operation)?,
+            EDGE_COLLECTION => Self::apply_edge(campaign_id, &change.entity_id, operation)?,
+            other => return Err(DmAssistantError::storage(&format!("Unknown collection '{}' in history", other))),
+        }
+        EventService::emit(&EntityEvent::new(*campaign_id, &change.collection, change.entity_id, operation));
+        Ok(())
+    }
+
+    /// State of an entity after the operation, given its current state.
+    /// Fails if the entity isn't in the state the operation starts from,
+    /// because it was changed outside the history.
+    fn target_state(label: &str, id: &Uuid, current: Option<Value>, operation: &ChangeOperation) -> DmResult<Option<Value>> {
+        let changed_outside = || DmAssistantError::campaign(&format!(
+            "{} {} was changed outside the history and can't be restored", label, id
+        ));
+
+        match (current, operation) {
+            (None, ChangeOperation::Create { state }) => Ok(Some(state.clone())),
+            (Some(current), ChangeOperation::Delete { state }) if current == *state => Ok(None),
+            (Some(mut current), ChangeOperation::Update { diff }) => {
+                if diff.apply(&mut current) {
+                    Ok(Some(current))
+                } else {
+                    Err(changed_outside())
+                }
+            }
+            _ => Err(changed_outside()),
+        }
+    }
+
+    /// Apply an operation to an entity. Goes to the campaign's backend
+    /// directly, so the change isn't recorded again.
+    fn apply_entity<T: Entity>(campaign_id: &Uuid, id: &Uuid, operation: &ChangeOperation) -> DmResult<()> {
+        let repository = CampaignRepository::<T>::new();
+        let backend = repository.backend(campaign_id)?;
+        let current = backend.get(campaign_id, id)?.map(|entity| serde_json::to_value(&entity)).transpose()?;
+        let exists = current.is_some();
+
+        match Self::target_state(T::LABEL, id, current, operation)? {
+            None => {
+                backend.delete(campaign_id, id)?;
+            }
+            Some(state) => {
+                let entity: T = serde_json::from_value(state)?;
+                if exists {
+                    backend.update(entity)?;
+                } else {
+                    backend.create(entity)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply an operation to a relationship edge
+    fn apply_edge(campaign_id: &Uuid, id: &Uuid, operation: &ChangeOperation) -> DmResult<()> {
+        let mut graph = RelationshipGraphService::load_graph(campaign_id)?;
+        let position = graph.edges.iter().position(|edge| edge.id == *id);
+        let current = position.map(|i| serde_json::to_value(&graph.edges[i])).transpose()?;
+
+        match (position, Self::target_state("Relationship", id, current, operation)?) {
+            (Some(i), None) => {
+                graph.edges.remove(i);
+            }
+            (Some(i), Some(state)) => graph.edges[i] = serde_json::from_value(state)?,
+            (None, Some(state)) => graph.edges.push(serde_json::from_value(state)?),
+            (None, None) => {}
+        }
+        graph.updated_at = Utc::now();
+        RelationshipGraphService::save_graph(&graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, RelationshipType};
+    use crate::core::models::npc::CreateNpcRequest;
+    use crate::core::models::relationship::{CreateEdgeRequest, GraphNode};
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::services::npc_storage::NpcStorageService;
+    use crate::services::storage::{JsonRepository, Repository};
+    use crate::utils::test_support::create_test_campaign;
+
+    fn new_character(campaign_id: Uuid, name: &str) -> PlayerCharacter {
+        CharacterStorageService::create_character(PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: "Elf".to_string(),
+            class: "Wizard".to_string(),
+            level: 1,
+            max_hp: 6,
+            background: String::new(),
+            notes: None,
+        })).unwrap()
+    }
+
+    fn new_npc(campaign_id: Uuid, name: &str) -> Npc {
+        NpcStorageService::create_npc(Npc::new(CreateNpcRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: None,
+            role: None,
+            location_id: None,
+            faction: None,
+            attitude: None,
+            voice: None,
+            mannerisms: None,
+            secrets: None,
+            portrait_path: None,
+            notes: None,
+        })).unwrap()
+    }
+
+    fn level_up(campaign_id: &Uuid, character_id: &Uuid) -> PlayerCharacter {
+        CharacterStorageService::modify_character(campaign_id, character_id, |c| {
+            c.level += 1;
+            Ok(())
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_undo_and_redo_updates() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Elowen");
+        level_up(&campaign_id, &character.id);
+
+        let history = HistoryService::list_history(&campaign_id).unwrap();
+        assert_eq!(history.undo.len(), 2);
+        assert_eq!(history.undo[0].label, "Update Character 'Elowen'");
+        assert_eq!(history.undo[1].label, "Create Character 'Elowen'");
+
+        let undone = HistoryService::undo(&campaign_id).unwrap().unwrap();
+        assert_eq!(undone.label, "Update Character 'Elowen'");
+        assert_eq!(CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap().level, 1);
+
+        HistoryService::redo(&campaign_id).unwrap().unwrap();
+        assert_eq!(CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap().level, 2);
+
+        // A new change after an undo drops the redo stack
+        HistoryService::undo(&campaign_id).unwrap();
+        level_up(&campaign_id, &character.id);
+        assert!(HistoryService::redo(&campaign_id).unwrap().is_none());
+
+        // Undoing the creation deletes the character
+        HistoryService::undo(&campaign_id).unwrap();
+        HistoryService::undo(&campaign_id).unwrap();
+        assert!(CharacterStorageService::find_character(&character.id).unwrap().is_none());
+        assert!(HistoryService::undo(&campaign_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_delete_restores_relationships() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Elowen");
+        let npc = new_npc(campaign_id, "Mira");
+        let edge = RelationshipGraphService::add_edge(&campaign_id, CreateEdgeRequest {
+            from: GraphNode::Npc(npc.id),
+            to: GraphNode::Character(character.id),
+            relationship_type: RelationshipType::Ally,
+            mutual: None,
+            notes: None,
+        }).unwrap();
+
+        CharacterStorageService::delete_character(&campaign_id, &character.id).unwrap();
+        assert!(RelationshipGraphService::load_graph(&campaign_id).unwrap().get_edge(&edge.id).is_none());
+        let history = HistoryService::list_history(&campaign_id).unwrap();
+        assert_eq!(history.undo[0].label, "Delete Character 'Elowen'");
+        assert_eq!(history.undo[0].change_count, 2);
+
+        // One undo brings back the character and its relationship
+        HistoryService::undo(&campaign_id).unwrap();
+        assert_eq!(CharacterStorageService::find_character(&character.id).unwrap().unwrap().name, "Elowen");
+        assert!(RelationshipGraphService::load_graph(&campaign_id).unwrap().get_edge(&edge.id).is_some());
+
+        HistoryService::redo(&campaign_id).unwrap();
+        assert!(CharacterStorageService::find_character(&character.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_history_survives_restart_and_is_capped() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Elowen");
+        for _ in 0..MAX_HISTORY_ENTRIES {
+            CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+                c.max_hp += 1;
+                Ok(())
+            }).unwrap();
+        }
+
+        // Forget everything cached in memory, as after a restart
+        CampaignStore::global().invalidate_dir(&CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap());
+        let history = HistoryService::list_history(&campaign_id).unwrap();
+        assert_eq!(history.undo.len(), MAX_HISTORY_ENTRIES);
+        // The creation was the oldest entry: dropped
+        assert!(history.undo.iter().all(|entry| entry.label.starts_with("Update")));
+
+        HistoryService::undo(&campaign_id).unwrap();
+        let restored = CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap();
+        assert_eq!(restored.max_hp, character.max_hp + MAX_HISTORY_ENTRIES as u16 - 1);
+    }
+
+    #[test]
+    fn test_undo_refuses_changes_made_outside_the_history() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Elowen");
+        level_up(&campaign_id, &character.id);
+
+        // Written without going through the history
+        JsonRepository::<PlayerCharacter>::new().modify(&campaign_id, &character.id, Box::new(|c| {
+            c.level = 5;
+            Ok(())
+        })).unwrap();
+
+        assert!(matches!(HistoryService::undo(&campaign_id), Err(DmAssistantError::CampaignError(_))));
+        assert_eq!(HistoryService::list_history(&campaign_id).unwrap().undo.len(), 2);
+    }
+}
@@ -0,0 +1,603 @@
+// src-tauri/src/services/import_export/bestiary.rs
+
+use super::{five_etools, open5e, ImportExportService};
+use crate::core::models::archive::{BestiaryFormat, BestiaryImportResult, ImportedMonster, UnmappedField};
+use crate::core::models::common::Ability;
+use crate::core::models::monster::{
+    ActionUsage, Attack, AttackKind, CreateMonsterRequest, Damage, DamageDefense, DamageType, Monster,
+    MonsterAction, Senses, SavingThrowEffect, Size, SpellFrequency, SpellList, Spellcasting, StatBlock,
+};
+use crate::core::rules::dice;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::history::HistoryService;
+use crate::services::monster_storage::MonsterStorageService;
+use crate::utils::error::{DmAssistantError, DmResult};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Monster converted from a bestiary, before it's saved
+pub(super) struct Conversion {
+    pub stat_block: StatBlock,
+    pub notes: String,
+    pub unmapped: Vec<UnmappedField>,
+}
+
+impl Conversion {
+    pub fn new(name: &str) -> Self {
+        Self {
+            stat_block: StatBlock { name: name.trim().to_string(), ..StatBlock::default() },
+            notes: String::new(),
+            unmapped: Vec::new(),
+        }
+    }
+
+    /// Record data that couldn't be converted
+    pub fn unmapped(&mut self, field: &str, value: &Value) {
+        let value = match value {
+            Value::String(text) => text.clone(),
+            value => value.to_string(),
+        };
+        self.unmapped.push(UnmappedField { field: field.to_string(), value });
+    }
+}
+
+impl ImportExportService {
+    /// Import the monsters of a 5etools or Open5e bestiary into a campaign.
+    /// The format is detected if not given. Monsters that can't be converted
+    /// are reported without stopping the import, as is the data of converted
+    /// monsters that has no place in the stat block.
+    pub fn import_bestiary(campaign_id: &Uuid, path: &Path, format: Option<BestiaryFormat>) -> DmResult<BestiaryImportResult> {
+        CampaignStorageService::require_campaign(campaign_id)?;
+        let document: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let format = match format {
+            Some(format) => format,
+            None => detect_format(&document)?,
+        };
+        let conversions = convert_bestiary(&document, format)?;
+
+        // A single history entry, so the whole import can be undone
+        let monsters = HistoryService::batch(campaign_id, || {
+            Ok(conversions.into_iter()
+                .map(|(name, conversion)| Self::save_conversion(campaign_id, name, conversion))
+                .collect::<Vec<_>>())
+        })?;
+
+        let imported = monsters.iter().filter(|monster| monster.monster_id.is_some()).count();
+        Ok(BestiaryImportResult { format, imported, failed: monsters.len() - imported, monsters })
+    }
+
+    fn save_conversion(campaign_id: &Uuid, name: String, conversion: DmResult<Conversion>) -> ImportedMonster {
+        let conversion = match conversion {
+            Ok(conversion) => conversion,
+            Err(e) => return ImportedMonster { name, monster_id: None, error: Some(e.to_string()), unmapped: Vec::new() },
+        };
+
+        let monster = Monster::new(CreateMonsterRequest {
+            campaign_id: *campaign_id,
+            stat_block: conversion.stat_block,
+            notes: Some(conversion.notes),
+        });
+        let (monster_id, error) = match MonsterStorageService::create_monster(monster) {
+            Ok(monster) => (Some(monster.id), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        ImportedMonster { name, monster_id, error, unmapped: conversion.unmapped }
+    }
+}
+
+/// Tell a 5etools bestiary from an Open5e one
+fn detect_format(document: &Value) -> DmResult<BestiaryFormat> {
+    if document.get("monster").is_some_and(Value::is_array) {
+        return Ok(BestiaryFormat::FiveEtools);
+    }
+    if document.get("results").is_some_and(Value::is_array) || document.get("slug").is_some() {
+        return Ok(BestiaryFormat::Open5e);
+    }
+    match document.as_array().and_then(|entries| entries.first()) {
+        Some(entry) if entry.get("slug").is_some() || entry.get("challenge_rating").is_some() => Ok(BestiaryFormat::Open5e),
+        Some(entry) if entry.get("cr").is_some() || entry.get("_copy").is_some() => Ok(BestiaryFormat::FiveEtools),
+        _ => Err(DmAssistantError::invalid_input("Not a 5etools or Open5e bestiary")),
+    }
+}
+
+/// Convert each monster of a bestiary, along with its name
+fn convert_bestiary(document: &Value, format: BestiaryFormat) -> DmResult<Vec<(String, DmResult<Conversion>)>> {
+    let entries = match format {
+        BestiaryFormat::FiveEtools => document.get("monster").unwrap_or(document),
+        BestiaryFormat::Open5e => document.get("results").unwrap_or(document),
+    };
+    let entries = match entries {
+        Value::Array(entries) => entries.iter().collect::<Vec<_>>(),
+        Value::Object(_) => vec![entries], // A single monster
+        _ => return Err(DmAssistantError::invalid_input("The bestiary holds no monsters")),
+    };
+
+    Ok(entries.into_iter().enumerate()
+        .map(|(i, entry)| {
+            let name = entry.get("name").and_then(Value::as_str)
+                .filter(|name| !name.trim().is_empty())
+                .map_or_else(|| format!("Monster #{}", i + 1), |name| name.trim().to_string());
+            let conversion = match format {
+                BestiaryFormat::FiveEtools => five_etools::convert(entry),
+                BestiaryFormat::Open5e => open5e::convert(entry),
+            };
+            (name, conversion)
+        })
+        .collect())
+}
+
+/// Parse a name into one of the model's camelCase enums, e.g. "slashing",
+/// "Poisoned" or "sleight of hand"
+pub(super) fn parse_name<T: DeserializeOwned>(text: &str) -> Option<T> {
+    let mut words = text.split(|c: char| c.is_whitespace() || c == '_').filter(|word| !word.is_empty());
+    let mut name = words.next()?.to_lowercase();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    serde_json::from_value(Value::String(name)).ok()
+}
+
+/// Ability from its name or abbreviation, e.g. "Dexterity" or "dex"
+pub(super) fn parse_ability(text: &str) -> Option<Ability> {
+    match text.get(..3)?.to_lowercase().as_str() {
+        "str" => Some(Ability::Strength),
+        "dex" => Some(Ability::Dexterity),
+        "con" => Some(Ability::Constitution),
+        "int" => Some(Ability::Intelligence),
+        "wis" => Some(Ability::Wisdom),
+        "cha" => Some(Ability::Charisma),
+        _ => None,
+    }
+}
+
+/// Size from its name or initial, e.g. "Small" or "S"
+pub(super) fn parse_size(text: &str) -> Option<Size> {
+    match text.trim().to_lowercase().as_str() {
+        "t" | "tiny" => Some(Size::Tiny),
+        "s" | "small" => Some(Size::Small),
+        "m" | "medium" => Some(Size::Medium),
+        "l" | "large" => Some(Size::Large),
+        "h" | "huge" => Some(Size::Huge),
+        "g" | "gargantuan" => Some(Size::Gargantuan),
+        _ => None,
+    }
+}
+
+/// Leading number of a text, e.g. 60 for "60 ft."
+fn leading_number(text: &str) -> Option<u32> {
+    let digits: String = text.trim_start().chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Number following the first occurrence of a label, e.g. "reach " in "reach 10 ft."
+fn number_after(text: &str, label: &str) -> Option<u32> {
+    text.match_indices(label).find_map(|(start, _)| leading_number(&text[start + label.len()..]))
+}
+
+/// Set a sense such as "darkvision 60 ft." Returns false if it isn't one of the model's senses.
+pub(super) fn apply_sense(senses: &mut Senses, text: &str) -> bool {
+    let text = text.trim();
+    let Some((name, range)) = text.split_once(char::is_whitespace) else { return false };
+    let Some(range) = leading_number(range) else { return false };
+    let sense = match name.to_lowercase().as_str() {
+        "blindsight" => &mut senses.blindsight,
+        "darkvision" => &mut senses.darkvision,
+        "tremorsense" => &mut senses.tremorsense,
+        "truesight" => &mut senses.truesight,
+        _ => return false,
+    };
+    *sense = Some(range);
+    true
+}
+
+/// Check if a sense is the passive Perception, which is derived from the stat block
+pub(super) fn is_passive_perception(text: &str) -> bool {
+    text.trim().to_lowercase().starts_with("passive perception")
+}
+
+/// Add a language, or set the telepathy range for e.g. "telepathy 120 ft."
+pub(super) fn apply_language(stat_block: &mut StatBlock, text: &str) {
+    let text = text.trim();
+    if text.is_empty() || text == "—" || text == "-" {
+        return;
+    }
+    match text.strip_prefix("telepathy ").and_then(leading_number) {
+        Some(range) => stat_block.telepathy = Some(range),
+        None => stat_block.languages.push(text.to_string()),
+    }
+}
+
+/// Parse damage defenses as printed, e.g. "fire; bludgeoning, piercing, and
+/// slashing from nonmagical attacks". The groups that name no damage type are
+/// returned apart.
+pub(super) fn parse_damage_defenses(text: &str) -> (Vec<DamageDefense>, Vec<String>) {
+    let mut defenses = Vec::new();
+    let mut unknown = Vec::new();
+    for group in text.split(';').map(str::trim).filter(|group| !group.is_empty()) {
+        let words: Vec<&str> = group.split(|c: char| c == ',' || c.is_whitespace()).filter(|word| !word.is_empty()).collect();
+        let mut types = Vec::new();
+        let mut position = 0;
+        for word in &words {
+            if *word != "and" && *word != "or" {
+                match parse_name::<DamageType>(word) {
+                    Some(damage_type) => types.push(damage_type),
+                    None => break,
+                }
+            }
+            position += 1;
+        }
+
+        if types.is_empty() {
+            unknown.push(group.to_string());
+            continue;
+        }
+        let condition = (position < words.len()).then(|| words[position..].join(" "));
+        defenses.extend(types.into_iter().map(|damage_type| DamageDefense { damage_type, condition: condition.clone() }));
+    }
+    (defenses, unknown)
+}
+
+/// Split the usage limit off a feature name, e.g. "Fire Breath (Recharge 5–6)"
+/// or "Legendary Resistance (3/Day)"
+pub(super) fn split_usage(name: &str) -> (String, Option<ActionUsage>) {
+    let name = name.trim();
+    let Some((base, note)) = split_parenthetical(name) else { return (name.to_string(), None) };
+
+    let note = note.to_lowercase();
+    let usage = if note.starts_with("recharges after") {
+        Some(ActionUsage::RechargeAfterRest)
+    } else if let Some(roll) = note.strip_prefix("recharge") {
+        // "Recharge 5–6", or "Recharge 6"
+        let min = leading_number(roll).unwrap_or(6);
+        u8::try_from(min).ok().map(|min| ActionUsage::Recharge { min })
+    } else if let Some((uses, _)) = note.split_once("/day") {
+        uses.trim().parse().ok().map(|uses| ActionUsage::PerDay { uses })
+    } else {
+        None
+    };
+
+    match usage {
+        Some(usage) => (base.to_string(), Some(usage)),
+        None => (name.to_string(), None),
+    }
+}
+
+/// Split the cost off a legendary action name, e.g. "Wing Attack (Costs 2 Actions)"
+pub(super) fn split_cost(name: &str) -> (String, u8) {
+    let name = name.trim();
+    split_parenthetical(name)
+        .and_then(|(base, note)| {
+            let cost = note.to_lowercase().strip_prefix("costs ").and_then(leading_number)?;
+            Some((base.to_string(), u8::try_from(cost).ok()?))
+        })
+        .unwrap_or_else(|| (name.to_string(), 1))
+}
+
+/// Split a trailing parenthetical note off a name
+fn split_parenthetical(name: &str) -> Option<(&str, &str)> {
+    let inner = name.strip_suffix(')')?;
+    let open = inner.rfind('(')?;
+    Some((inner[..open].trim(), &inner[open + 1..]))
+}
+
+const ATTACK_KINDS: [(&str, AttackKind); 5] = [
+    ("Melee or Ranged Weapon Attack:", AttackKind::MeleeOrRangedWeapon),
+    ("Melee Weapon Attack:", AttackKind::MeleeWeapon),
+    ("Ranged Weapon Attack:", AttackKind::RangedWeapon),
+    ("Melee Spell Attack:", AttackKind::MeleeSpell),
+    ("Ranged Spell Attack:", AttackKind::RangedSpell),
+];
+
+/// Parse an action from its name and plain text, as printed in the books:
+/// "Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 5 (1d6 + 2)
+/// slashing damage."
+pub(super) fn parse_action(name: &str, description: &str) -> MonsterAction {
+    let (name, usage) = split_usage(name);
+    let description = description.trim();
+    let attack = parse_attack(description);
+    let damage_text = match (&attack, description.find("Hit:")) {
+        (Some(_), Some(hit)) => &description[hit..],
+        _ => description,
+    };
+    MonsterAction {
+        name,
+        description: description.to_string(),
+        attack,
+        saving_throw: parse_saving_throw(description),
+        damage: parse_damage(damage_text),
+        usage,
+    }
+}
+
+fn parse_attack(text: &str) -> Option<Attack> {
+    let (kind, rest) = ATTACK_KINDS.iter()
+        .find_map(|(label, kind)| text.find(label).map(|start| (*kind, &text[start + label.len()..])))?;
+    let to_hit = rest.split_whitespace().next()?.parse().ok()?;
+    let rest = rest.split("Hit:").next().unwrap_or(rest);
+
+    // "range 80/320 ft."
+    let (range, long_range) = rest.match_indices("range ")
+        .find_map(|(start, _)| {
+            let distances = rest[start + 6..].split_whitespace().next()?;
+            let (range, long_range) = match distances.split_once('/') {
+                Some((range, long_range)) => (range, Some(long_range)),
+                None => (distances, None),
+            };
+            Some((range.parse().ok()?, long_range.and_then(|long_range| long_range.parse().ok())))
+        })
+        .map_or((None, None), |(range, long_range)| (Some(range), long_range));
+
+    Some(Attack { kind, to_hit, reach: number_after(rest, "reach "), range, long_range })
+}
+
+/// "DC 13 Dexterity saving throw"
+fn parse_saving_throw(text: &str) -> Option<SavingThrowEffect> {
+    text.match_indices("DC ").find_map(|(start, _)| {
+        let mut words = text[start + 3..].split_whitespace();
+        let dc = u8::try_from(leading_number(words.next()?)?).ok()?;
+        let ability = parse_ability(words.next()?)?;
+        words.next().filter(|word| word.starts_with("saving"))?;
+        Some(SavingThrowEffect { ability, dc, half_on_success: text.contains("half as much damage") })
+    })
+}
+
+/// Damage as printed, e.g. "5 (1d6 + 2) slashing damage plus 3 (1d6) fire damage".
+/// Alternatives such as "or 8 (1d10 + 3) slashing damage if used with two hands"
+/// are left out.
+fn parse_damage(text: &str) -> Vec<Damage> {
+    let mut damage = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('(') {
+        let Some(close) = rest[open..].find(')').map(|length| open + length) else { break };
+        let expression: String = rest[open + 1..close].chars().filter(|c| !c.is_whitespace()).collect();
+        let damage_type = rest[close + 1..].split_whitespace().next().and_then(parse_name::<DamageType>);
+        let alternative = !damage.is_empty() && rest[..open].contains(" or ");
+        if let Some(damage_type) = damage_type {
+            if !alternative && expression.contains('d') && dice::parse(&expression).is_ok() {
+                damage.push(Damage { dice: expression, damage_type });
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+
+    // Flat damage, e.g. "Hit: 1 piercing damage."
+    if damage.is_empty() {
+        if let Some(start) = text.find("Hit: ") {
+            let mut words = text[start + 5..].split_whitespace();
+            if let (Some(amount), Some(damage_type)) = (words.next(), words.next().and_then(parse_name::<DamageType>)) {
+                if amount.parse::<u32>().is_ok() {
+                    damage.push(Damage { dice: amount.to_string(), damage_type });
+                }
+            }
+        }
+    }
+    damage
+}
+
+/// Parse a spellcasting trait from its plain text: the ability, save DC and
+/// attack bonus of the header, and the spell lists that follow it, one per
+/// line, e.g. "1st level (4 slots): detect magic, shield". None if the text
+/// doesn't name a spellcasting ability.
+pub(super) fn parse_spellcasting(name: &str, text: &str) -> Option<Spellcasting> {
+    let ability = text.find("ability is ").and_then(|start| parse_ability(&text[start + 11..]))?;
+    let attack_bonus = text.find(" to hit with spell attacks")
+        .and_then(|end| text[..end].split_whitespace().last()?.parse().ok());
+    let caster_level = text.find("-level spellcaster")
+        .and_then(|end| leading_number(text[..end].split_whitespace().last()?))
+        .and_then(|level| u8::try_from(level).ok());
+
+    let mut description = Vec::new();
+    let mut spells = Vec::new();
+    for line in text.lines().map(|line| line.trim_start_matches(['*', '-', '•', ' ']).trim()).filter(|line| !line.is_empty()) {
+        match line.split_once(':').and_then(|(label, names)| Some((parse_spell_frequency(label)?, names))) {
+            Some((frequency, names)) => spells.push(SpellList { frequency, spells: split_spell_names(names) }),
+            None => description.push(line),
+        }
+    }
+
+    Some(Spellcasting {
+        name: name.trim().to_string(),
+        ability,
+        save_dc: number_after(text, "save DC ").and_then(|dc| u8::try_from(dc).ok()),
+        attack_bonus,
+        caster_level,
+        description: (!description.is_empty()).then(|| description.join("\n")),
+        spells,
+    })
+}
+
+/// Frequency of a spell list, e.g. "Cantrips (at will)", "3/day each" or "1st level (4 slots)"
+fn parse_spell_frequency(label: &str) -> Option<SpellFrequency> {
+    let label = label.trim().to_lowercase();
+    if label.starts_with("cantrips") {
+        Some(SpellFrequency::Cantrip)
+    } else if label.starts_with("at will") {
+        Some(SpellFrequency::AtWill)
+    } else if let Some((uses, _)) = label.split_once("/day") {
+        uses.trim().parse().ok().map(|uses| SpellFrequency::PerDay { uses })
+    } else if label.contains(" level") {
+        let level = u8::try_from(leading_number(&label)?).ok()?;
+        let slots = label.find('(').and_then(|open| leading_number(&label[open + 1..])).unwrap_or(0);
+        Some(SpellFrequency::Level { level, slots: u8::try_from(slots).ok()? })
+    } else {
+        None
+    }
+}
+
+/// Spell names of a comma-separated list, without emphasis markers
+pub(super) fn split_spell_names(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|name| name.trim().trim_matches(['*', '_', '.']).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::common::Skill;
+    use crate::core::models::monster::{ChallengeRating, CreatureType};
+    use crate::utils::test_support::create_test_campaign;
+    use tempfile::tempdir;
+
+    const FIVE_ETOOLS_BESTIARY: &str = include_str!("../../../tests/fixtures/5etools_bestiary.json");
+    const OPEN5E_MONSTERS: &str = include_str!("../../../tests/fixtures/open5e_monsters.json");
+
+    fn convert(json: &str) -> (BestiaryFormat, Vec<(String, DmResult<Conversion>)>) {
+        let document: Value = serde_json::from_str(json).unwrap();
+        let format = detect_format(&document).unwrap();
+        (format, convert_bestiary(&document, format).unwrap())
+    }
+
+    fn converted<'a>(conversions: &'a [(String, DmResult<Conversion>)], name: &str) -> &'a Conversion {
+        conversions.iter().find(|(n, _)| n == name).unwrap().1.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_parse_action_text() {
+        let action = parse_action(
+            "Longsword",
+            "Melee Weapon Attack: +5 to hit, reach 5 ft., one target. Hit: 7 (1d8 + 3) slashing damage, \
+             or 8 (1d10 + 3) slashing damage if used with two hands, plus 3 (1d6) fire damage.",
+        );
+        let attack = action.attack.unwrap();
+        assert_eq!((attack.kind, attack.to_hit, attack.reach), (AttackKind::MeleeWeapon, 5, Some(5)));
+        assert_eq!(action.damage, vec![
+            Damage { dice: "1d8+3".to_string(), damage_type: DamageType::Slashing },
+            Damage { dice: "1d6".to_string(), damage_type: DamageType::Fire },
+        ]);
+
+        let breath = parse_action(
+            "Poison Breath (Recharge 5–6)",
+            "The dragon exhales poisonous gas in a 15-foot cone. Each creature in that area must make a DC 11 \
+             Constitution saving throw, taking 21 (6d6) poison damage on a failed save, or half as much damage on a successful one.",
+        );
+        assert_eq!(breath.name, "Poison Breath");
+        assert_eq!(breath.usage, Some(ActionUsage::Recharge { min: 5 }));
+        assert_eq!(breath.saving_throw, Some(SavingThrowEffect { ability: Ability::Constitution, dc: 11, half_on_success: true }));
+        assert_eq!(breath.damage, vec![Damage { dice: "6d6".to_string(), damage_type: DamageType::Poison }]);
+
+        let (defenses, unknown) = parse_damage_defenses("fire; bludgeoning, piercing, and slashing from nonmagical attacks; see below");
+        assert_eq!(defenses.len(), 4);
+        assert_eq!(defenses[3].condition.as_deref(), Some("from nonmagical attacks"));
+        assert_eq!(unknown, vec!["see below"]);
+    }
+
+    #[test]
+    fn test_convert_5etools_bestiary() {
+        let (format, conversions) = convert(FIVE_ETOOLS_BESTIARY);
+        assert_eq!(format, BestiaryFormat::FiveEtools);
+        assert_eq!(conversions.len(), 4);
+
+        let goblin = converted(&conversions, "Goblin");
+        assert!(goblin.unmapped.is_empty(), "{:?}", goblin.unmapped);
+        let stat_block = &goblin.stat_block;
+        assert_eq!((stat_block.size, stat_block.creature_type), (Size::Small, CreatureType::Humanoid));
+        assert_eq!(stat_block.subtypes, vec!["goblinoid"]);
+        assert_eq!(stat_block.alignment, "neutral evil");
+        assert_eq!(stat_block.armor_class.source.as_deref(), Some("leather armor, shield"));
+        assert_eq!(stat_block.skills.get(&Skill::Stealth), Some(&6));
+        assert_eq!(stat_block.senses.darkvision, Some(60));
+        assert_eq!(stat_block.challenge_rating, "1/4".parse::<ChallengeRating>().unwrap());
+        assert_eq!(stat_block.traits[0].description,
+            "The goblin can take the Disengage or Hide action as a bonus action on each of its turns.");
+        let shortbow = &stat_block.actions[1];
+        assert_eq!(shortbow.description,
+            "Ranged Weapon Attack: +4 to hit, range 80/320 ft., one target. Hit: 5 (1d6 + 2) piercing damage.");
+        let attack = shortbow.attack.as_ref().unwrap();
+        assert_eq!((attack.kind, attack.to_hit, attack.range, attack.long_range), (AttackKind::RangedWeapon, 4, Some(80), Some(320)));
+        assert_eq!(shortbow.damage[0].dice, "1d6+2");
+
+        let mage = converted(&conversions, "Mage");
+        assert_eq!(mage.unmapped.iter().map(|field| field.field.as_str()).collect::<Vec<_>>(), vec!["ac"]);
+        let spellcasting = &mage.stat_block.spellcasting[0];
+        assert_eq!((spellcasting.ability, spellcasting.save_dc, spellcasting.attack_bonus, spellcasting.caster_level),
+            (Ability::Intelligence, Some(14), Some(6), Some(9)));
+        assert_eq!(spellcasting.spells.len(), 6);
+        assert_eq!(spellcasting.spells[3].frequency, SpellFrequency::Level { level: 3, slots: 3 });
+        assert_eq!(spellcasting.spells[3].spells, vec!["counterspell", "fireball", "fly"]);
+        assert_eq!(mage.stat_block.actions[0].attack.as_ref().unwrap().kind, AttackKind::MeleeOrRangedWeapon);
+
+        let dragon = converted(&conversions, "Adult Black Dragon");
+        let mut unmapped: Vec<_> = dragon.unmapped.iter().map(|field| field.field.as_str()).collect();
+        unmapped.sort();
+        assert_eq!(unmapped, vec!["cr.lair", "legendaryGroup"]);
+        let stat_block = &dragon.stat_block;
+        assert_eq!(stat_block.challenge_rating, ChallengeRating::whole(14).unwrap());
+        assert_eq!(stat_block.speed.fly, Some(80));
+        assert_eq!(stat_block.damage_immunities[0].damage_type, DamageType::Acid);
+        assert_eq!(stat_block.saving_throws.get(&Ability::Dexterity), Some(&7));
+        let bite = &stat_block.actions[1];
+        assert_eq!(bite.damage.iter().map(|damage| damage.damage_type).collect::<Vec<_>>(), vec![DamageType::Piercing, DamageType::Acid]);
+        let breath = &stat_block.actions[3];
+        assert_eq!((breath.name.as_str(), breath.usage), ("Acid Breath", Some(ActionUsage::Recharge { min: 5 })));
+        assert_eq!(breath.saving_throw.as_ref().map(|save| save.dc), Some(18));
+        let legendary = stat_block.legendary_actions.as_ref().unwrap();
+        assert_eq!(legendary.per_round, 3);
+        assert_eq!((legendary.actions[2].action.name.as_str(), legendary.actions[2].cost), ("Wing Attack", 2));
+        assert_eq!(legendary.actions[2].action.damage[0].dice, "2d6+6");
+        assert_eq!(stat_block.traits[1].usage, Some(ActionUsage::PerDay { uses: 3 }));
+
+        // Copies of another monster can't be resolved from a single file
+        let copy = &conversions.iter().find(|(name, _)| name == "Goblin Boss").unwrap().1;
+        assert!(copy.is_err());
+    }
+
+    #[test]
+    fn test_convert_open5e_monsters() {
+        let (format, conversions) = convert(OPEN5E_MONSTERS);
+        assert_eq!(format, BestiaryFormat::Open5e);
+        assert_eq!(conversions.len(), 4);
+
+        let goblin = converted(&conversions, "Goblin");
+        assert!(goblin.unmapped.is_empty(), "{:?}", goblin.unmapped);
+        assert_eq!(goblin.stat_block.subtypes, vec!["goblinoid"]);
+        assert_eq!(goblin.stat_block.languages, vec!["Common", "Goblin"]);
+        assert_eq!(goblin.stat_block.actions[0].damage[0].dice, "1d6+2");
+
+        let mage = converted(&conversions, "Mage");
+        assert_eq!(mage.unmapped.iter().map(|field| field.field.as_str()).collect::<Vec<_>>(), vec!["armor_desc"]);
+        assert_eq!(mage.stat_block.saving_throws.get(&Ability::Intelligence), Some(&6));
+        let spellcasting = &mage.stat_block.spellcasting[0];
+        assert_eq!(spellcasting.spells[0].frequency, SpellFrequency::Cantrip);
+        assert_eq!(spellcasting.spells[5].spells, vec!["cone of cold"]);
+        assert!(mage.stat_block.traits.is_empty());
+
+        // Both formats give the same stat block
+        let (_, five_etools) = convert(FIVE_ETOOLS_BESTIARY);
+        let dragon = converted(&conversions, "Adult Black Dragon");
+        let expected = &converted(&five_etools, "Adult Black Dragon").stat_block;
+        assert_eq!(dragon.stat_block.actions, expected.actions);
+        assert_eq!(dragon.stat_block.legendary_actions.as_ref().unwrap().actions,
+            expected.legendary_actions.as_ref().unwrap().actions);
+        assert_eq!(dragon.stat_block.damage_immunities, expected.damage_immunities);
+        assert!(dragon.notes.starts_with("Black dragons"));
+
+        assert!(conversions[3].1.is_err());
+    }
+
+    #[test]
+    fn test_import_bestiary_reports_failures() {
+        let campaign_id = create_test_campaign();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bestiary.json");
+        fs::write(&path, FIVE_ETOOLS_BESTIARY).unwrap();
+
+        let result = ImportExportService::import_bestiary(&campaign_id, &path, None).unwrap();
+        assert_eq!((result.imported, result.failed), (3, 1));
+        let boss = result.monsters.iter().find(|monster| monster.name == "Goblin Boss").unwrap();
+        assert!(boss.monster_id.is_none() && boss.error.is_some());
+
+        let monsters = MonsterStorageService::get_monsters_by_campaign(&campaign_id).unwrap();
+        assert_eq!(monsters.len(), 3);
+        assert_eq!(HistoryService::undo(&campaign_id).unwrap().map(|entry| entry.change_count), Some(3));
+        assert!(MonsterStorageService::get_monsters_by_campaign(&campaign_id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,589 @@
+// src-tauri/src/services/import_export/five_etools.rs
+
+use super::bestiary::{
+    apply_language, apply_sense, is_passive_perception, parse_ability, parse_action, parse_damage_defenses,
+    parse_name, parse_size, parse_spellcasting, split_cost, split_usage, Conversion,
+};
+use crate::core::models::monster::{
+    Condition, CreatureType, DamageDefense, LegendaryAction, LegendaryActions, MonsterAction, MonsterFeature,
+    SpellFrequency, SpellList, Spellcasting,
+};
+use crate::utils::error::{DmAssistantError, DmResult};
+use serde_json::{Map, Value};
+
+/// Fields describing the entry rather than the monster: sources, tags used
+/// by the 5etools filters, tokens and art
+const METADATA_FIELDS: [&str; 34] = [
+    "source", "page", "srd", "basicRules", "otherSources", "reprintedAs", "additionalSources", "environment",
+    "soundClip", "hasToken", "hasFluff", "hasFluffImages", "tokenUrl", "altArt", "traitTags", "actionTags",
+    "languageTags", "senseTags", "damageTags", "damageTagsSpell", "damageTagsLegendary", "spellcastingTags",
+    "miscTags", "conditionInflict", "conditionInflictSpell", "conditionInflictLegendary", "savingThrowForced",
+    "savingThrowForcedSpell", "savingThrowForcedLegendary", "dragonCastingColor", "dragonAge", "isNpc",
+    "isNamedCreature", "familiar",
+];
+
+/// Convert a monster of a 5etools bestiary
+pub(super) fn convert(entry: &Value) -> DmResult<Conversion> {
+    let fields = entry.as_object()
+        .ok_or_else(|| DmAssistantError::invalid_input("Monster entry is not an object"))?;
+    if let Some(copy) = fields.get("_copy") {
+        let original = copy.get("name").and_then(Value::as_str).unwrap_or("another monster");
+        return Err(DmAssistantError::invalid_input(&format!(
+            "Copy of {}: export the bestiary with copies resolved to import it", original
+        )));
+    }
+    let name = fields.get("name").and_then(Value::as_str).filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| DmAssistantError::invalid_input("Monster has no name"))?;
+
+    let mut conversion = Conversion::new(&resolve_tags(name));
+    for (key, value) in fields {
+        match key.as_str() {
+            // Read along with other fields, or derived from the stat block
+            "name" | "passive" | "legendaryActions" | "legendaryHeader" => {}
+            "size" => convert_size(value, &mut conversion),
+            "type" => convert_type(value, &mut conversion),
+            "alignment" => match alignment_text(value) {
+                Some(alignment) => conversion.stat_block.alignment = alignment,
+                None => conversion.unmapped(key, value),
+            },
+            "ac" => convert_armor_class(value, &mut conversion),
+            "hp" => convert_hit_points(value, &mut conversion),
+            "speed" => convert_speed(value, &mut conversion),
+            "str" | "dex" | "con" | "int" | "wis" | "cha" => convert_score(key, value, &mut conversion),
+            "save" | "skill" => convert_bonuses(key, value, &mut conversion),
+            "senses" => convert_senses(value, &mut conversion),
+            "languages" => {
+                for language in strings(value) {
+                    apply_language(&mut conversion.stat_block, &resolve_tags(language));
+                }
+            }
+            "cr" => convert_challenge_rating(value, &mut conversion),
+            "vulnerable" | "resist" | "immune" => {
+                let defenses = convert_defenses(key, value, &mut conversion);
+                let stat_block = &mut conversion.stat_block;
+                match key.as_str() {
+                    "vulnerable" => stat_block.damage_vulnerabilities = defenses,
+                    "resist" => stat_block.damage_resistances = defenses,
+                    _ => stat_block.damage_immunities = defenses,
+                }
+            }
+            "conditionImmune" => convert_condition_immunities(value, &mut conversion),
+            "trait" => {
+                let traits = convert_features(key, value, &mut conversion);
+                conversion.stat_block.traits = traits;
+            }
+            "action" | "bonus" | "reaction" => {
+                let actions = convert_actions(key, value, &mut conversion);
+                let stat_block = &mut conversion.stat_block;
+                match key.as_str() {
+                    "action" => stat_block.actions = actions,
+                    "bonus" => stat_block.bonus_actions = actions,
+                    _ => stat_block.reactions = actions,
+                }
+            }
+            "legendary" => convert_legendary_actions(fields, value, &mut conversion),
+            "spellcasting" => convert_spellcasting(value, &mut conversion),
+            key if METADATA_FIELDS.contains(&key) => {}
+            _ => conversion.unmapped(key, value),
+        }
+    }
+    Ok(conversion)
+}
+
+/// Replace the `{@tag ...}` markup of 5etools text with the text it stands
+/// for, as printed in the books: `{@atk mw} {@hit 4} to hit` becomes
+/// "Melee Weapon Attack: +4 to hit"
+pub(super) fn resolve_tags(text: &str) -> String {
+    let mut text = text.to_string();
+    // Innermost tags first, as tags can be nested
+    while let Some(start) = text.rfind("{@") {
+        let Some(length) = text[start..].find('}') else { break };
+        let end = start + length;
+        let resolved = resolve_tag(&text[start + 2..end]);
+        text.replace_range(start..=end, &resolved);
+    }
+    text
+}
+
+fn resolve_tag(tag: &str) -> String {
+    let (name, content) = tag.split_once(' ').unwrap_or((tag, ""));
+    let content = content.trim();
+    match name {
+        "atk" => attack_label(content),
+        "hit" | "d20" if content.starts_with(['+', '-']) => content.to_string(),
+        "hit" | "d20" => format!("+{}", content),
+        "h" => "Hit: ".to_string(),
+        "m" => "Miss: ".to_string(),
+        "hom" => "Hit or Miss: ".to_string(),
+        "dc" => format!("DC {}", content),
+        "recharge" if content.is_empty() || content == "6" => "(Recharge 6)".to_string(),
+        "recharge" => format!("(Recharge {}–6)", content),
+        _ => {
+            // e.g. `{@damage 2d6+3}`, `{@spell fireball}`, `{@creature goblin|MM|goblins}`:
+            // the third part is the text to display, if any
+            let parts: Vec<&str> = content.split('|').collect();
+            parts.get(2).filter(|text| !text.is_empty()).unwrap_or(&parts[0]).to_string()
+        }
+    }
+}
+
+/// Label of `{@atk mw,rw}`: "Melee or Ranged Weapon Attack:"
+fn attack_label(codes: &str) -> String {
+    let codes: Vec<&str> = codes.split(',').map(str::trim).collect();
+    let mut ranges = Vec::new();
+    for code in &codes {
+        let range = if code.starts_with('m') { "Melee" } else { "Ranged" };
+        if !ranges.contains(&range) {
+            ranges.push(range);
+        }
+    }
+    let kind = if codes.iter().any(|code| code.ends_with('s')) { "Spell" } else { "Weapon" };
+    format!("{} {} Attack:", ranges.join(" or "), kind)
+}
+
+/// Plain text of 5etools entries, one paragraph per line. Returns false if
+/// some of them, such as tables, couldn't be rendered.
+fn render_entries(entries: &Value, lines: &mut Vec<String>) -> bool {
+    match entries {
+        Value::String(text) => {
+            lines.push(resolve_tags(text));
+            true
+        }
+        Value::Array(entries) => {
+            let mut complete = true;
+            for entry in entries {
+                complete &= render_entries(entry, lines);
+            }
+            complete
+        }
+        Value::Object(entry) => {
+            // Lists, named sub-entries and list items
+            let start = lines.len();
+            let mut rendered = None;
+            for key in ["entries", "items", "entry"] {
+                if let Some(children) = entry.get(key) {
+                    rendered = Some(render_entries(children, lines) && rendered.unwrap_or(true));
+                }
+            }
+            if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                let name = resolve_tags(name);
+                match lines.get_mut(start) {
+                    Some(first) => *first = format!("{}. {}", name, first),
+                    None => lines.push(name),
+                }
+            }
+            rendered.unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+/// Text of the entries of a feature, reporting what couldn't be rendered
+fn entries_text(field: &str, entries: Option<&Value>, conversion: &mut Conversion) -> String {
+    let mut lines = Vec::new();
+    if let Some(entries) = entries {
+        if !render_entries(entries, &mut lines) {
+            conversion.unmapped(field, entries);
+        }
+    }
+    lines.join("\n")
+}
+
+fn strings(value: &Value) -> impl Iterator<Item = &str> {
+    value.as_array().into_iter().flatten().filter_map(Value::as_str)
+}
+
+fn convert_size(value: &Value, conversion: &mut Conversion) {
+    // Several sizes for e.g. "Medium or Small"
+    let sizes: Vec<&str> = strings(value).chain(value.as_str()).collect();
+    match sizes.first().and_then(|size| parse_size(size)) {
+        Some(size) if sizes.len() == 1 => conversion.stat_block.size = size,
+        Some(size) => {
+            conversion.stat_block.size = size;
+            conversion.unmapped("size", value);
+        }
+        None => conversion.unmapped("size", value),
+    }
+}
+
+/// `"beast"`, or `{"type": "humanoid", "tags": ["goblinoid"]}`
+fn convert_type(value: &Value, conversion: &mut Conversion) {
+    let creature_type = value.as_str().or_else(|| value.get("type")?.as_str()).and_then(parse_name::<CreatureType>);
+    match creature_type {
+        Some(creature_type) => conversion.stat_block.creature_type = creature_type,
+        None => return conversion.unmapped("type", value),
+    }
+
+    for tag in value.get("tags").and_then(Value::as_array).into_iter().flatten() {
+        let subtype = match tag {
+            Value::String(tag) => tag.clone(),
+            // e.g. {"tag": "elf", "prefix": "high"}
+            tag => [tag.get("prefix"), tag.get("tag")].into_iter().flatten()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        conversion.stat_block.subtypes.push(subtype);
+    }
+    if let Some(swarm_size) = value.get("swarmSize") {
+        conversion.unmapped("type.swarmSize", swarm_size);
+    }
+}
+
+/// `["C", "E"]` for "chaotic evil", `["L", "NX", "C", "E"]` for "any evil alignment"
+fn alignment_text(value: &Value) -> Option<String> {
+    let entries = value.as_array()?;
+    if !entries.is_empty() && entries.iter().all(Value::is_object) {
+        // Alternatives, e.g. [{"alignment": ["C", "E"]}, {"alignment": ["N", "E"]}]
+        let alternatives = entries.iter()
+            .map(|entry| match entry.get("special").and_then(Value::as_str) {
+                Some(special) => Some(special.to_string()),
+                None => alignment_text(entry.get("alignment")?),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        return Some(alternatives.join(" or "));
+    }
+
+    let codes = entries.iter().map(Value::as_str).collect::<Option<Vec<_>>>()?;
+    let has = |code: &str| codes.contains(&code);
+    let text = match codes.as_slice() {
+        ["A"] => "any alignment".to_string(),
+        ["U"] => "unaligned".to_string(),
+        codes if codes.len() > 2 && has("E") && !has("G") => "any evil alignment".to_string(),
+        codes if codes.len() > 2 && has("G") && !has("E") => "any good alignment".to_string(),
+        codes if codes.len() > 2 && has("C") && !has("L") => "any non-lawful alignment".to_string(),
+        codes if codes.len() > 2 && has("L") && !has("C") => "any non-chaotic alignment".to_string(),
+        codes if codes.len() > 2 => return None,
+        ["N"] => "neutral".to_string(),
+        codes => codes.iter()
+            .map(|code| match *code {
+                "L" => Some("lawful"),
+                "C" => Some("chaotic"),
+                "G" => Some("good"),
+                "E" => Some("evil"),
+                "N" | "NX" | "NY" => Some("neutral"),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?
+            .join(" "),
+    };
+    Some(text)
+}
+
+/// `[{"ac": 15, "from": ["{@item leather armor|phb}"]}]`, or `[12]`. Armor
+/// classes after the first one, e.g. "15 with mage armor", are reported.
+fn convert_armor_class(value: &Value, conversion: &mut Conversion) {
+    let entries = value.as_array().map(Vec::as_slice).unwrap_or_default();
+    let Some(first) = entries.first() else { return conversion.unmapped("ac", value) };
+    let armor_class = first.as_u64().or_else(|| first.get("ac")?.as_u64()).and_then(|ac| u8::try_from(ac).ok());
+    let Some(armor_class) = armor_class else { return conversion.unmapped("ac", value) };
+
+    conversion.stat_block.armor_class.value = armor_class;
+    conversion.stat_block.armor_class.source = first.get("from")
+        .map(|from| strings(from).map(resolve_tags).collect::<Vec<_>>().join(", "))
+        .filter(|source| !source.is_empty());
+    if first.get("condition").is_some() || entries.len() > 1 {
+        conversion.unmapped("ac", &Value::Array(entries.to_vec()));
+    }
+}
+
+/// `{"average": 7, "formula": "2d6"}`, or `{"special": "..."}`
+fn convert_hit_points(value: &Value, conversion: &mut Conversion) {
+    let average = value.get("average").and_then(Value::as_u64).and_then(|average| u32::try_from(average).ok());
+    let Some(average) = average else { return conversion.unmapped("hp", value) };
+
+    let hit_points = &mut conversion.stat_block.hit_points;
+    hit_points.average = average;
+    hit_points.formula = value.get("formula").and_then(Value::as_str)
+        .map(|formula| formula.chars().filter(|c| !c.is_whitespace()).collect());
+}
+
+/// `{"walk": 40, "fly": {"number": 80, "condition": "(hover)"}, "canHover": true}`
+fn convert_speed(value: &Value, conversion: &mut Conversion) {
+    let Some(speeds) = value.as_object() else { return conversion.unmapped("speed", value) };
+    let speed = &mut conversion.stat_block.speed;
+    speed.walk = 0;
+
+    let mut unmapped = Vec::new();
+    for (mode, value) in speeds {
+        if mode == "canHover" {
+            speed.hover = value.as_bool().unwrap_or(false);
+            continue;
+        }
+        let feet = value.as_u64().or_else(|| value.get("number")?.as_u64()).and_then(|feet| u32::try_from(feet).ok());
+        let slot = match mode.as_str() {
+            "walk" => None,
+            "burrow" => Some(&mut speed.burrow),
+            "climb" => Some(&mut speed.climb),
+            "fly" => Some(&mut speed.fly),
+            "swim" => Some(&mut speed.swim),
+            _ => {
+                unmapped.push((format!("speed.{}", mode), value));
+                continue;
+            }
+        };
+        let Some(feet) = feet else {
+            unmapped.push((format!("speed.{}", mode), value));
+            continue;
+        };
+        match slot {
+            Some(slot) => *slot = Some(feet),
+            None => speed.walk = feet,
+        }
+        match value.get("condition").and_then(Value::as_str) {
+            Some(condition) if condition.contains("hover") => speed.hover = true,
+            Some(_) => unmapped.push((format!("speed.{}", mode), value)),
+            None => {}
+        }
+    }
+    for (field, value) in unmapped {
+        conversion.unmapped(&field, value);
+    }
+}
+
+fn convert_score(key: &str, value: &Value, conversion: &mut Conversion) {
+    let Some(score) = value.as_u64().and_then(|score| u8::try_from(score).ok()) else {
+        return conversion.unmapped(key, value);
+    };
+    let scores = &mut conversion.stat_block.ability_scores;
+    let slot = match key {
+        "str" => &mut scores.strength,
+        "dex" => &mut scores.dexterity,
+        "con" => &mut scores.constitution,
+        "int" => &mut scores.intelligence,
+        "wis" => &mut scores.wisdom,
+        _ => &mut scores.charisma,
+    };
+    *slot = score;
+}
+
+/// Saves and skills: `{"dex": "+4"}`, `{"perception": "+5"}`
+fn convert_bonuses(key: &str, value: &Value, conversion: &mut Conversion) {
+    let Some(bonuses) = value.as_object() else { return conversion.unmapped(key, value) };
+    for (name, bonus) in bonuses {
+        let parsed = bonus.as_str().and_then(|bonus| bonus.trim().parse::<i32>().ok());
+        let stored = match (key, parsed) {
+            ("save", Some(bonus)) => parse_ability(name)
+                .map(|ability| conversion.stat_block.saving_throws.insert(ability, bonus))
+                .is_some(),
+            (_, Some(bonus)) => parse_name(name)
+                .map(|skill| conversion.stat_block.skills.insert(skill, bonus))
+                .is_some(),
+            (_, None) => false,
+        };
+        if !stored {
+            conversion.unmapped(&format!("{}.{}", key, name), bonus);
+        }
+    }
+}
+
+fn convert_senses(value: &Value, conversion: &mut Conversion) {
+    for sense in strings(value).chain(value.as_str()) {
+        let sense = resolve_tags(sense);
+        for sense in sense.split(',') {
+            if !is_passive_perception(sense) && !apply_sense(&mut conversion.stat_block.senses, sense) {
+                conversion.unmapped("senses", &Value::String(sense.trim().to_string()));
+            }
+        }
+    }
+}
+
+/// `"1/4"`, or `{"cr": "14", "lair": "15"}`
+fn convert_challenge_rating(value: &Value, conversion: &mut Conversion) {
+    let challenge_rating = value.as_str().or_else(|| value.get("cr")?.as_str()).and_then(|cr| cr.parse().ok());
+    match challenge_rating {
+        Some(challenge_rating) => conversion.stat_block.challenge_rating = challenge_rating,
+        None => return conversion.unmapped("cr", value),
+    }
+    for variant in ["lair", "coven"] {
+        if let Some(cr) = value.get(variant) {
+            conversion.unmapped(&format!("cr.{}", variant), cr);
+        }
+    }
+}
+
+/// `["fire", {"immune": ["bludgeoning", "piercing"], "note": "from nonmagical attacks", "cond": true}]`
+fn convert_defenses(key: &str, value: &Value, conversion: &mut Conversion) -> Vec<DamageDefense> {
+    let mut defenses = Vec::new();
+    for entry in value.as_array().into_iter().flatten() {
+        match entry {
+            Value::String(text) => {
+                let (parsed, unknown) = parse_damage_defenses(&resolve_tags(text));
+                defenses.extend(parsed);
+                for text in unknown {
+                    conversion.unmapped(key, &Value::String(text));
+                }
+            }
+            Value::Object(group) if group.contains_key(key) => {
+                let condition = [group.get("preNote"), group.get("note")].into_iter().flatten()
+                    .filter_map(Value::as_str)
+                    .map(resolve_tags)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let mut nested = convert_defenses(key, &group[key], conversion);
+                for defense in &mut nested {
+                    if !condition.is_empty() {
+                        defense.condition = Some(condition.clone());
+                    }
+                }
+                defenses.extend(nested);
+            }
+            entry => conversion.unmapped(key, entry),
+        }
+    }
+    defenses
+}
+
+fn convert_condition_immunities(value: &Value, conversion: &mut Conversion) {
+    for entry in value.as_array().into_iter().flatten() {
+        match entry.as_str().map(resolve_tags).and_then(|condition| parse_name::<Condition>(&condition)) {
+            Some(condition) => conversion.stat_block.condition_immunities.push(condition),
+            None => conversion.unmapped("conditionImmune", entry),
+        }
+    }
+}
+
+/// Named entries, e.g. `[{"name": "Pack Tactics", "entries": ["..."]}]`
+fn named_entries(value: &Value) -> impl Iterator<Item = (String, &Map<String, Value>)> {
+    value.as_array().into_iter().flatten()
+        .filter_map(Value::as_object)
+        .map(|entry| (resolve_tags(entry.get("name").and_then(Value::as_str).unwrap_or_default()), entry))
+}
+
+fn convert_features(key: &str, value: &Value, conversion: &mut Conversion) -> Vec<MonsterFeature> {
+    named_entries(value)
+        .map(|(name, entry)| {
+            let description = entries_text(&format!("{}[{}]", key, name), entry.get("entries"), conversion);
+            let (name, usage) = split_usage(&name);
+            MonsterFeature { name, description, usage }
+        })
+        .collect()
+}
+
+fn convert_actions(key: &str, value: &Value, conversion: &mut Conversion) -> Vec<MonsterAction> {
+    named_entries(value)
+        .map(|(name, entry)| {
+            let description = entries_text(&format!("{}[{}]", key, name), entry.get("entries"), conversion);
+            parse_action(&name, &description)
+        })
+        .collect()
+}
+
+fn convert_legendary_actions(fields: &Map<String, Value>, value: &Value, conversion: &mut Conversion) {
+    let actions = convert_actions("legendary", value, conversion).into_iter()
+        .map(|action| {
+            let (name, cost) = split_cost(&action.name);
+            LegendaryAction { action: MonsterAction { name, ..action }, cost }
+        })
+        .collect();
+    let header = fields.get("legendaryHeader")
+        .map(|header| entries_text("legendaryHeader", Some(header), conversion))
+        .filter(|header| !header.is_empty());
+
+    conversion.stat_block.legendary_actions = Some(LegendaryActions {
+        per_round: fields.get("legendaryActions").and_then(Value::as_u64)
+            .and_then(|per_round| u8::try_from(per_round).ok())
+            .unwrap_or(3),
+        description: header,
+        actions,
+    });
+}
+
+/// `[{"name": "Spellcasting", "headerEntries": [...], "ability": "int",
+/// "will": [...], "daily": {"1e": [...]}, "spells": {"1": {"slots": 4, "spells": [...]}}}]`
+fn convert_spellcasting(value: &Value, conversion: &mut Conversion) {
+    for (name, entry) in named_entries(value) {
+        let field = format!("spellcasting[{}]", name);
+        let header = entries_text(&field, entry.get("headerEntries"), conversion);
+        let ability = entry.get("ability").and_then(Value::as_str).and_then(parse_ability);
+        let mut spellcasting = match (parse_spellcasting(&name, &header), ability) {
+            (Some(spellcasting), _) => spellcasting,
+            (None, Some(ability)) => Spellcasting {
+                name: name.clone(),
+                ability,
+                save_dc: None,
+                attack_bonus: None,
+                caster_level: None,
+                description: (!header.is_empty()).then(|| header.clone()),
+                spells: Vec::new(),
+            },
+            (None, None) => {
+                conversion.unmapped(&field, &Value::Object(entry.clone()));
+                continue;
+            }
+        };
+        if let Some(ability) = ability {
+            spellcasting.ability = ability;
+        }
+
+        let spell_names = |spells: &Value| strings(spells).map(|spell| resolve_tags(spell).trim_matches('*').trim().to_string()).collect();
+        for (key, value) in entry {
+            match key.as_str() {
+                "will" => spellcasting.spells.push(SpellList { frequency: SpellFrequency::AtWill, spells: spell_names(value) }),
+                "daily" => {
+                    // {"3e": [...], "1e": [...]}: uses per day, "e" for each
+                    let mut lists: Vec<SpellList> = value.as_object().into_iter().flatten()
+                        .filter_map(|(uses, spells)| {
+                            let uses = uses.trim_end_matches('e').parse().ok()?;
+                            Some(SpellList { frequency: SpellFrequency::PerDay { uses }, spells: spell_names(spells) })
+                        })
+                        .collect();
+                    lists.sort_by_key(|list| match list.frequency {
+                        SpellFrequency::PerDay { uses } => std::cmp::Reverse(uses),
+                        _ => std::cmp::Reverse(0),
+                    });
+                    spellcasting.spells.extend(lists);
+                }
+                "spells" => {
+                    // {"0": {"spells": [...]}, "1": {"slots": 4, "spells": [...]}}
+                    for (level, list) in value.as_object().into_iter().flatten() {
+                        let frequency = match level.parse::<u8>() {
+                            Ok(0) => SpellFrequency::Cantrip,
+                            Ok(level) => {
+                                let slots = list.get("slots").and_then(Value::as_u64).unwrap_or(0);
+                                SpellFrequency::Level { level, slots: u8::try_from(slots).unwrap_or(u8::MAX) }
+                            }
+                            Err(_) => {
+                                conversion.unmapped(&format!("{}.spells.{}", field, level), list);
+                                continue;
+                            }
+                        };
+                        let spells = list.get("spells").map(spell_names).unwrap_or_default();
+                        spellcasting.spells.push(SpellList { frequency, spells });
+                    }
+                }
+                "footerEntries" => {
+                    let footer = entries_text(&field, Some(value), conversion);
+                    spellcasting.description = match spellcasting.description.take() {
+                        Some(description) => Some(format!("{}\n{}", description, footer)),
+                        None => Some(footer),
+                    };
+                }
+                "name" | "type" | "headerEntries" | "ability" | "displayAs" | "hidden" => {}
+                _ => conversion.unmapped(&format!("{}.{}", field, key), value),
+            }
+        }
+        conversion.stat_block.spellcasting.push(spellcasting);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_tags() {
+        assert_eq!(
+            resolve_tags("{@atk mw,rw} {@hit 5} to hit, reach 5 ft. or range 20/60 ft., one target. {@h}4 ({@damage 1d4 + 2}) piercing damage."),
+            "Melee or Ranged Weapon Attack: +5 to hit, reach 5 ft. or range 20/60 ft., one target. Hit: 4 (1d4 + 2) piercing damage.",
+        );
+        assert_eq!(resolve_tags("Fire Breath {@recharge 5}"), "Fire Breath (Recharge 5–6)");
+        assert_eq!(resolve_tags("a {@dc 15} {@skill Dexterity|XPHB} save, {@creature goblin|MM|goblins} and {@b {@spell fireball}}"),
+            "a DC 15 Dexterity save, goblins and fireball");
+        assert_eq!(resolve_tags("{@atk rs} {@hit -1} to hit"), "Ranged Spell Attack: -1 to hit");
+
+        assert_eq!(alignment_text(&serde_json::json!(["L", "NX", "C", "E"])).as_deref(), Some("any evil alignment"));
+        assert_eq!(alignment_text(&serde_json::json!([{"alignment": ["C", "E"]}, {"alignment": ["N"]}])).as_deref(),
+            Some("chaotic evil or neutral"));
+    }
+}
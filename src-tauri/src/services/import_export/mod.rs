@@ -0,0 +1,798 @@
+// src-tauri/src/services/import_export/mod.rs
+
+use crate::core::models::archive::{
+    ArchiveFile, ArchiveManifest, ConflictResolution, ExportResult, ImportMode, ImportResult,
+};
+use crate::core::models::backup::{BackupInfo, BackupReason};
+use crate::core::models::campaign::Campaign;
+use crate::core::models::character::PlayerCharacter;
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::core::models::event::ReloadReason;
+use crate::core::models::history::EntityChange;
+use crate::core::models::item::Item;
+use crate::core::models::monster::Monster;
+use crate::core::models::npc::Npc;
+use crate::core::models::spell::Spell;
+use crate::services::backup::BackupService;
+use crate::services::campaign_storage::{CampaignStorageService, CAMPAIGN_MANIFEST};
+use crate::services::entity_index::EntityIndexService;
+use crate::services::events::EventService;
+use crate::services::history::{EDGE_COLLECTION, HISTORY_FILE};
+use crate::services::migrations;
+use crate::services::relationship_graph::{RelationshipGraph, RelationshipGraphService};
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Repository, StorageBackend, SQLITE_FILE};
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{
+    ensure_dir_exists, get_app_data_dir, is_storage_artifact, list_files_recursive,
+    portable_path, save_json,
+};
+use crate::utils::id_generator::IdRemapper;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+mod bestiary;
+mod five_etools;
+mod open5e;
+
+/// Extension of campaign archives
+pub const ARCHIVE_EXTENSION: &str = "dmcampaign";
+
+const ARCHIVE_FORMAT: &str = "dmcampaign";
+
+/// Bumped when the layout of the archive itself changes
+const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Archive entry holding the manifest
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Archive directory holding the campaign files
+const FILES_PREFIX: &str = "campaign/";
+
+/// Campaign directory receiving the assets referenced from outside it
+const ASSETS_DIR: &str = "assets";
+
+/// Check if a field names a file that belongs with its entity, e.g. `portraitPath`
+fn is_asset_field(key: &str) -> bool {
+    key.ends_with("Path") || key.ends_with("_path")
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Absolute file paths held by asset fields of a document
+fn collect_asset_paths(value: &Value, paths: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(path) if is_asset_field(key) => {
+                        if Path::new(path).is_absolute() && Path::new(path).is_file() {
+                            paths.insert(path.clone());
+                        }
+                    }
+                    _ => collect_asset_paths(value, paths),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_asset_paths(v, paths)),
+        _ => {}
+    }
+}
+
+/// Replace every string found in `replacements`
+fn replace_strings(value: &mut Value, replacements: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            if let Some(replacement) = replacements.get(text.as_str()) {
+                *text = replacement.clone();
+            }
+        }
+        Value::Object(map) => map.values_mut().for_each(|v| replace_strings(v, replacements)),
+        Value::Array(items) => items.iter_mut().for_each(|v| replace_strings(v, replacements)),
+        _ => {}
+    }
+}
+
+/// Check if an incoming copy of an entity replaces the one in the campaign
+fn should_replace(conflicts: ConflictResolution, existing: DateTime<Utc>, incoming: DateTime<Utc>) -> bool {
+    match conflicts {
+        ConflictResolution::KeepExisting => false,
+        ConflictResolution::Replace => true,
+        ConflictResolution::KeepNewer => incoming > existing,
+    }
+}
+
+/// Verified content of an archive
+struct ArchiveContents {
+    manifest: ArchiveManifest,
+    documents: BTreeMap<String, Value>, // JSON files, upgraded to the current schema
+    assets: BTreeMap<String, Vec<u8>>,
+}
+
+impl ArchiveContents {
+    /// Original path -> path of the imported copy, for assets that were
+    /// referenced by absolute path
+    fn asset_locations(&self, campaign_dir: &Path) -> HashMap<String, String> {
+        self.manifest.files.iter()
+            .filter_map(|file| {
+                let original = file.original_path.clone()?;
+                Some((original, campaign_dir.join(&file.path).display().to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Added, updated and skipped entities of a merge
+#[derive(Default)]
+struct MergeCounts {
+    added: usize,
+    updated: usize,
+    skipped: usize,
+}
+
+/// Export and import of campaigns as single-file archives (`.dmcampaign`).
+///
+/// An archive is a zip holding a manifest (app and schema versions, SHA-256
+/// of every file) and the campaign files, including the assets its entities
+/// reference, such as portraits.
+///
+/// Bestiaries exported from 5etools and Open5e can be imported as monsters.
+pub struct ImportExportService;
+
+impl ImportExportService {
+    /// Export a campaign to an archive at `destination`
+    pub fn export_campaign(campaign_id: &Uuid, destination: &Path) -> DmResult<ExportResult> {
+        let _lock = lock_campaign(campaign_id)?;
+        let campaign = CampaignStorageService::require_campaign(campaign_id)?;
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+
+        let mut files = BTreeMap::new();
+        // Entities of SQLite campaigns travel as their JSON files
+        if StorageBackend::of(campaign_id)? == StorageBackend::Sqlite {
+            for (relative, document) in [
+                CampaignStorageService::collection_document::<PlayerCharacter>(campaign_id)?,
+                CampaignStorageService::collection_document::<Npc>(campaign_id)?,
+                CampaignStorageService::collection_document::<Monster>(campaign_id)?,
+                CampaignStorageService::collection_document::<Spell>(campaign_id)?,
+                CampaignStorageService::collection_document::<Item>(campaign_id)?,
+            ] {
+                files.insert(portable_path(&relative), serde_json::to_vec_pretty(&document)?);
+            }
+        }
+        for relative in list_files_recursive(&campaign_dir)? {
+            // The undo history only makes sense on this machine
+            if is_storage_artifact(&relative) || relative == Path::new(SQLITE_FILE) || relative == Path::new(HISTORY_FILE) {
+                continue;
+            }
+            let data = fs::read(campaign_dir.join(&relative))
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read file: {}", e)))?;
+            files.insert(portable_path(&relative), data);
+        }
+
+        let mut asset_paths = BTreeSet::new();
+        for (path, data) in &files {
+            if path.ends_with(".json") {
+                if let Ok(document) = serde_json::from_slice::<Value>(data) {
+                    collect_asset_paths(&document, &mut asset_paths);
+                }
+            }
+        }
+
+        let mut entries: Vec<ArchiveFile> = files.iter()
+            .map(|(path, data)| ArchiveFile {
+                path: path.clone(),
+                size: data.len() as u64,
+                sha256: sha256_hex(data),
+                original_path: None,
+            })
+            .collect();
+        for original in asset_paths {
+            // Already exported with the campaign directory: only the reference needs rewriting
+            if let Ok(relative) = Path::new(&original).strip_prefix(&campaign_dir) {
+                let relative = portable_path(relative);
+                if let Some(entry) = entries.iter_mut().find(|e| e.path == relative && e.original_path.is_none()) {
+                    entry.original_path = Some(original);
+                }
+                continue;
+            }
+
+            let data = fs::read(&original)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read asset {}: {}", original, e)))?;
+            let sha256 = sha256_hex(&data);
+            let file_name = Path::new(&original).file_name().and_then(|n| n.to_str()).unwrap_or("asset");
+            let path = format!("{}/{}-{}", ASSETS_DIR, &sha256[..12], file_name);
+            if files.contains_key(&path) {
+                continue;
+            }
+            entries.push(ArchiveFile { path: path.clone(), size: data.len() as u64, sha256, original_path: Some(original) });
+            files.insert(path, data);
+        }
+
+        let manifest = ArchiveManifest {
+            format: ARCHIVE_FORMAT.to_string(),
+            format_version: ARCHIVE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: SCHEMA_VERSION,
+            campaign_id: campaign.id,
+            campaign_name: campaign.name,
+            exported_at: Utc::now(),
+            files: entries,
+        };
+        let destination = if destination.extension().is_some() {
+            destination.to_path_buf()
+        } else {
+            destination.with_extension(ARCHIVE_EXTENSION)
+        };
+        Self::write_archive(&destination, &manifest, &files)?;
+
+        let size_bytes = fs::metadata(&destination)
+            .map(|metadata| metadata.len())
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read archive: {}", e)))?;
+        Ok(ExportResult { path: destination.display().to_string(), size_bytes, manifest })
+    }
+
+    /// Write the archive to a temporary file and move it into place once complete
+    fn write_archive(destination: &Path, manifest: &ArchiveManifest, files: &BTreeMap<String, Vec<u8>>) -> DmResult<()> {
+        let parent = destination.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        ensure_dir_exists(parent)?;
+        let temp_file = tempfile::NamedTempFile::new_in(parent)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to create archive: {}", e)))?;
+        let archive_error = |e: zip::result::ZipError| DmAssistantError::storage(&format!("Failed to write archive: {}", e));
+        let io_error = |e: std::io::Error| DmAssistantError::storage(&format!("Failed to write archive: {}", e));
+
+        let mut zip = ZipWriter::new(temp_file.reopen().map_err(io_error)?);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file(MANIFEST_ENTRY, options).map_err(archive_error)?;
+        zip.write_all(&serde_json::to_vec_pretty(manifest)?).map_err(io_error)?;
+        for (path, data) in files {
+            zip.start_file(format!("{}{}", FILES_PREFIX, path), options).map_err(archive_error)?;
+            zip.write_all(data).map_err(io_error)?;
+        }
+        zip.finish().map_err(archive_error)?.sync_all().map_err(io_error)?;
+
+        temp_file.persist(destination)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to write archive: {}", e.error)))?;
+        Ok(())
+    }
+
+    /// Read and verify the manifest of an archive without importing it
+    pub fn inspect_archive(archive_path: &Path) -> DmResult<ArchiveManifest> {
+        Ok(Self::read_archive(archive_path)?.manifest)
+    }
+
+    /// Read an archive, checking it against its manifest: every listed file
+    /// must be present with the recorded size and checksum, and nothing else.
+    fn read_archive(archive_path: &Path) -> DmResult<ArchiveContents> {
+        let invalid = |reason: String| DmAssistantError::validation(&format!(
+            "{} is not a valid campaign archive: {}", archive_path.display(), reason
+        ));
+
+        let file = File::open(archive_path)
+            .map_err(|e| DmAssistantError::not_found_msg(&format!("Failed to open {}: {}", archive_path.display(), e)))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+
+        let manifest: ArchiveManifest = {
+            let entry = archive.by_name(MANIFEST_ENTRY).map_err(|_| invalid("no manifest".to_string()))?;
+            serde_json::from_reader(entry).map_err(|e| invalid(format!("unreadable manifest ({})", e)))?
+        };
+        if manifest.format != ARCHIVE_FORMAT {
+            return Err(invalid(format!("unknown format '{}'", manifest.format)));
+        }
+        if manifest.format_version > ARCHIVE_FORMAT_VERSION || manifest.schema_version > SCHEMA_VERSION {
+            return Err(DmAssistantError::validation(&format!(
+                "{} was exported by a newer version of the app ({}); update to import it",
+                archive_path.display(), manifest.app_version
+            )));
+        }
+
+        let mut expected: HashMap<&str, &ArchiveFile> = HashMap::new();
+        for file in &manifest.files {
+            if expected.insert(file.path.as_str(), file).is_some() {
+                return Err(invalid(format!("{} is listed twice", file.path)));
+            }
+        }
+
+        let mut files = BTreeMap::new();
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).map_err(|e| invalid(e.to_string()))?;
+            if entry.is_dir() || entry.name() == MANIFEST_ENTRY {
+                continue;
+            }
+            // Never trust paths from an archive to stay inside the campaign directory
+            let path = entry.enclosed_name()
+                .and_then(|path| path.strip_prefix(FILES_PREFIX.trim_end_matches('/')).ok().map(portable_path))
+                .ok_or_else(|| invalid(format!("unexpected entry {}", entry.name())))?;
+            let listed = expected.get(path.as_str())
+                .ok_or_else(|| invalid(format!("{} isn't listed in the manifest", path)))?;
+
+            let mut data = Vec::new();
+            entry.take(listed.size + 1).read_to_end(&mut data)
+                .map_err(|e| invalid(format!("{} is unreadable ({})", path, e)))?;
+            if data.len() as u64 != listed.size || sha256_hex(&data) != listed.sha256 {
+                return Err(invalid(format!("{} doesn't match its checksum", path)));
+            }
+            files.insert(path, data);
+        }
+        if let Some(missing) = manifest.files.iter().find(|file| !files.contains_key(&file.path)) {
+            return Err(invalid(format!("{} is missing", missing.path)));
+        }
+
+        let mut documents = BTreeMap::new();
+        let mut assets = BTreeMap::new();
+        for (path, data) in files {
+            if !path.ends_with(".json") {
+                assets.insert(path, data);
+                continue;
+            }
+            let mut document: Value = serde_json::from_slice(&data)
+                .map_err(|e| invalid(format!("{} is not valid JSON ({})", path, e)))?;
+            if migrations::is_versioned(Path::new(&path)) && !path.contains('/') {
+                migrations::upgrade(&path, &mut document)?;
+            }
+            documents.insert(path, document);
+        }
+
+        let campaign_id = documents.get(CAMPAIGN_MANIFEST)
+            .ok_or_else(|| invalid(format!("{} is missing", CAMPAIGN_MANIFEST)))?
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| Uuid::parse_str(id).ok());
+        if campaign_id != Some(manifest.campaign_id) {
+            return Err(invalid("the manifest doesn't match the campaign".to_string()));
+        }
+
+        Ok(ArchiveContents { manifest, documents, assets })
+    }
+
+    /// Import an archive as a new campaign or into an existing one
+    pub fn import_campaign(archive_path: &Path, mode: ImportMode) -> DmResult<ImportResult> {
+        let contents = Self::read_archive(archive_path)?;
+        match mode {
+            ImportMode::CreateNew { name } => Self::import_as_new(contents, name),
+            ImportMode::Merge { campaign_id, conflicts } => {
+                let _lock = lock_campaign(&campaign_id)?;
+                CampaignStorageService::ensure_writable(&campaign_id)?;
+
+                // Merging can't be undone entity by entity: keep the campaign as it was
+                let safety_backup = BackupService::create_backup(&campaign_id, BackupReason::BeforeImport)?;
+                match Self::merge_into(contents, &campaign_id, conflicts, safety_backup.clone()) {
+                    Ok(result) => Ok(result),
+                    Err(e) => {
+                        BackupService::restore_backup(&campaign_id, &safety_backup.id)?;
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Create a new campaign from an archive, with a new ID for the campaign
+    /// and every entity so it never collides with the original
+    fn import_as_new(contents: ArchiveContents, name: Option<String>) -> DmResult<ImportResult> {
+        let mut remapper = IdRemapper::new();
+        let new_id = remapper.remap(contents.manifest.campaign_id);
+        for document in contents.documents.values() {
+            remapper.collect_ids(document);
+        }
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&new_id)?;
+        let asset_locations = contents.asset_locations(&campaign_dir);
+
+        // Write into a staging directory, so a failure never leaves a
+        // half-imported campaign in the campaigns list
+        let staging_dir = get_app_data_dir()?.join("tmp").join(new_id.to_string());
+        let mut added = 0;
+        let staged = (|| -> DmResult<()> {
+            for (path, document) in &contents.documents {
+                let mut document = document.clone();
+                remapper.rewrite(&mut document);
+                replace_strings(&mut document, &asset_locations);
+                added += Self::count_entities(path, &document);
+                save_json(&document, &staging_dir.join(path))?;
+            }
+            for (path, data) in &contents.assets {
+                let target = staging_dir.join(path);
+                if let Some(parent) = target.parent() {
+                    ensure_dir_exists(parent)?;
+                }
+                fs::write(&target, data)
+                    .map_err(|e| DmAssistantError::storage(&format!("Failed to write {}: {}", path, e)))?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = staged {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+
+        fs::rename(&staging_dir, &campaign_dir)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to create campaign directory: {}", e)))?;
+
+        let mut campaign = CampaignStorageService::require_campaign(&new_id)?;
+        let now = Utc::now();
+        campaign.name = name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| contents.manifest.campaign_name.clone());
+        campaign.is_archived = false;
+        campaign.archived_at = None;
+        campaign.created_at = now;
+        campaign.updated_at = now;
+        CampaignStorageService::save_campaign(&campaign)?;
+        EntityIndexService::register_campaign(&new_id)?;
+        EventService::campaign_reloaded(&new_id, ReloadReason::Imported);
+
+        Ok(ImportResult {
+            campaign,
+            added,
+            updated: 0,
+            skipped: 0,
+            assets: contents.assets.len(),
+            safety_backup: None,
+        })
+    }
+
+    /// Entities and relationship edges held by a campaign file
+    fn count_entities(path: &str, document: &Value) -> usize {
+        let field = path.strip_suffix(".json").unwrap_or(path);
+        match (document.get(field), document.get("edges")) {
+            (Some(Value::Object(entities)), _) => entities.len(),
+            (_, Some(Value::Array(edges))) => edges.iter()
+                .filter(|edge| !edge.get("fromCharacter").and_then(Value::as_bool).unwrap_or(false))
+                .count(),
+            _ => 0,
+        }
+    }
+
+    /// Merge the entities, relationships and assets of an archive into a campaign.
+    /// The caller holds the campaign lock.
+    fn merge_into(
+        mut contents: ArchiveContents,
+        campaign_id: &Uuid,
+        conflicts: ConflictResolution,
+        safety_backup: BackupInfo,
+    ) -> DmResult<ImportResult> {
+        // Entities keep their IDs, so merging an archive of the same campaign
+        // updates them in place, unless the ID already belongs to another
+        // campaign (e.g. an archive of a local campaign merged into a new one)
+        let mut remapper = IdRemapper::new();
+        remapper.insert(contents.manifest.campaign_id, *campaign_id);
+        for (path, document) in &contents.documents {
+            let field = path.strip_suffix(".json").unwrap_or(path);
+            let Some(Value::Object(entities)) = document.get(field) else {
+                continue;
+            };
+            for id in entities.keys().filter_map(|key| Uuid::parse_str(key).ok()) {
+                if EntityIndexService::lookup(&id)?.is_some_and(|location| location.campaign_id != *campaign_id) {
+                    remapper.remap(id);
+                }
+            }
+        }
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        let asset_locations = contents.asset_locations(&campaign_dir);
+        for document in contents.documents.values_mut() {
+            remapper.rewrite(document);
+            replace_strings(document, &asset_locations);
+        }
+
+        let mut counts = MergeCounts::default();
+        Self::merge_collection::<PlayerCharacter>(campaign_id, &contents.documents, conflicts, &mut counts)?;
+        Self::merge_collection::<Npc>(campaign_id, &contents.documents, conflicts, &mut counts)?;
+        Self::merge_collection::<Monster>(campaign_id, &contents.documents, conflicts, &mut counts)?;
+        Self::merge_collection::<Spell>(campaign_id, &contents.documents, conflicts, &mut counts)?;
+        Self::merge_collection::<Item>(campaign_id, &contents.documents, conflicts, &mut counts)?;
+        if let Some(document) = contents.documents.get("relationships.json") {
+            let incoming: RelationshipGraph = serde_json::from_value(document.clone())?;
+            Self::merge_edges(campaign_id, incoming, conflicts, &mut counts)?;
+        }
+
+        let mut assets = 0;
+        for (path, data) in &contents.assets {
+            let target = campaign_dir.join(path);
+            let replace = match fs::read(&target) {
+                Ok(existing) => existing != *data && conflicts != ConflictResolution::KeepExisting,
+                Err(_) => true,
+            };
+            if !replace {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                ensure_dir_exists(parent)?;
+            }
+            fs::write(&target, data)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to write {}: {}", path, e)))?;
+            assets += 1;
+        }
+
+        EntityIndexService::register_campaign(campaign_id)?;
+        let campaign: Campaign = CampaignStorageService::modify_campaign(campaign_id, |_| Ok(()))?;
+        Ok(ImportResult {
+            campaign,
+            added: counts.added,
+            updated: counts.updated,
+            skipped: counts.skipped,
+            assets,
+            safety_backup: Some(safety_backup),
+        })
+    }
+
+    /// Merge one entity collection of an archive into the campaign, sending
+    /// the events of the entities added or replaced
+    fn merge_collection<T: Entity>(
+        campaign_id: &Uuid,
+        documents: &BTreeMap<String, Value>,
+        conflicts: ConflictResolution,
+        counts: &mut MergeCounts,
+    ) -> DmResult<()> {
+        let Some(document) = documents.get(&format!("{}.json", T::COLLECTION)) else {
+            return Ok(());
+        };
+        let incoming: EntityCollection<T> = serde_json::from_value(document.clone())
+            .map_err(|e| DmAssistantError::json(&format!("Invalid {} in archive: {}", T::COLLECTION, e)))?;
+
+        let repository = CampaignRepository::<T>::new();
+        let mut collection = repository.load_collection(campaign_id)?;
+        let mut changes = Vec::new();
+        for entity in incoming.values() {
+            match collection.get(&entity.id()) {
+                None => {
+                    changes.extend(EntityChange::between(T::COLLECTION, entity.id(), None, Some(serde_json::to_value(entity)?)));
+                    collection.insert(entity.clone());
+                    counts.added += 1;
+                }
+                Some(existing) if should_replace(conflicts, existing.updated_at(), entity.updated_at()) => {
+                    changes.extend(EntityChange::between(
+                        T::COLLECTION,
+                        entity.id(),
+                        Some(serde_json::to_value(existing)?),
+                        Some(serde_json::to_value(entity)?),
+                    ));
+                    collection.replace(entity.clone());
+                    counts.updated += 1;
+                }
+                Some(_) => counts.skipped += 1,
+            }
+        }
+        repository.save_collection(&collection)?;
+
+        for change in &changes {
+            EventService::entity_changed(campaign_id, change);
+        }
+        Ok(())
+    }
+
+    /// Merge relationship edges. Edges mirrored from character relationships
+    /// come with the characters.
+    fn merge_edges(
+        campaign_id: &Uuid,
+        incoming: RelationshipGraph,
+        conflicts: ConflictResolution,
+        counts: &mut MergeCounts,
+    ) -> DmResult<()> {
+        let mut graph = RelationshipGraphService::load_graph(campaign_id)?;
+        let mut changes = Vec::new();
+        for edge in incoming.edges.into_iter().filter(|edge| !edge.from_character) {
+            match graph.edges.iter_mut().find(|existing| existing.id == edge.id) {
+                None => {
+                    changes.extend(EntityChange::between(EDGE_COLLECTION, edge.id, None, Some(serde_json::to_value(&edge)?)));
+                    graph.edges.push(edge);
+                    counts.added += 1;
+                }
+                Some(existing) if should_replace(conflicts, existing.updated_at, edge.updated_at) => {
+                    changes.extend(EntityChange::between(
+                        EDGE_COLLECTION,
+                        edge.id,
+                        Some(serde_json::to_value(&*existing)?),
+                        Some(serde_json::to_value(&edge)?),
+                    ));
+                    *existing = edge;
+                    counts.updated += 1;
+                }
+                Some(_) => counts.skipped += 1,
+            }
+        }
+        graph.updated_at = Utc::now();
+        RelationshipGraphService::save_graph(&graph)?;
+
+        for change in &changes {
+            EventService::entity_changed(campaign_id, change);
+        }
+        Ok(())
+    }
+
+    /// Default file name for the archive of a campaign
+    pub fn archive_file_name(campaign: &Campaign) -> String {
+        let name: String = campaign.name.chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        format!("{}.{}", name.trim_matches('_'), ARCHIVE_EXTENSION)
+    }
+
+    /// Directory archives are exported to when no destination is given
+    pub fn exports_dir() -> DmResult<PathBuf> {
+        let dir = get_app_data_dir()?.join("exports");
+        ensure_dir_exists(&dir)?;
+        Ok(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::CreateCharacterRequest;
+    use crate::core::models::npc::CreateNpcRequest;
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::services::npc_storage::NpcStorageService;
+    use crate::utils::test_support::{create_test_campaign, MockEmitter};
+    use tempfile::tempdir;
+
+    fn new_character(campaign_id: Uuid, name: &str) -> PlayerCharacter {
+        CharacterStorageService::create_character(PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: "Dwarf".to_string(),
+            class: "Cleric".to_string(),
+            level: 1,
+            max_hp: 10,
+            background: String::new(),
+            notes: None,
+        })).unwrap()
+    }
+
+    fn new_npc(campaign_id: Uuid, name: &str, portrait_path: Option<String>) -> Npc {
+        NpcStorageService::create_npc(Npc::new(CreateNpcRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: None,
+            role: None,
+            location_id: None,
+            faction: None,
+            attitude: None,
+            voice: None,
+            mannerisms: None,
+            secrets: None,
+            portrait_path,
+            notes: None,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_export_and_import_as_new() {
+        let campaign_id = create_test_campaign();
+        let dir = tempdir().unwrap();
+        let portrait = dir.path().join("innkeeper.png");
+        fs::write(&portrait, b"not really a png").unwrap();
+        let character = new_character(campaign_id, "Brann");
+        let npc = new_npc(campaign_id, "Innkeeper", Some(portrait.display().to_string()));
+
+        let export = ImportExportService::export_campaign(&campaign_id, &dir.path().join("shared")).unwrap();
+        assert!(export.path.ends_with("shared.dmcampaign"));
+        assert!(export.manifest.files.iter().any(|f| f.path == "npcs.json"));
+        let asset = export.manifest.files.iter().find(|f| f.original_path.is_some()).unwrap();
+        assert!(asset.path.starts_with("assets/"));
+        assert_eq!(ImportExportService::inspect_archive(Path::new(&export.path)).unwrap().campaign_id, campaign_id);
+
+        let result = ImportExportService::import_campaign(
+            Path::new(&export.path),
+            ImportMode::CreateNew { name: Some("Shared copy".to_string()) },
+        ).unwrap();
+        let imported = result.campaign;
+        assert_ne!(imported.id, campaign_id);
+        assert_eq!(imported.name, "Shared copy");
+        assert_eq!(result.added, 2);
+        assert_eq!(result.assets, 1);
+
+        // Every entity has a new ID, the originals are untouched
+        let characters = CharacterStorageService::get_characters_by_campaign(&imported.id).unwrap();
+        assert_eq!(characters.len(), 1);
+        assert_ne!(characters[0].id, character.id);
+        assert_eq!(characters[0].campaign_id, imported.id);
+        assert_eq!(CharacterStorageService::find_character(&character.id).unwrap().unwrap().campaign_id, campaign_id);
+
+        // The portrait was imported with the campaign
+        let npcs = NpcStorageService::get_npcs_by_campaign(&imported.id).unwrap();
+        assert_ne!(npcs[0].id, npc.id);
+        let imported_portrait = PathBuf::from(npcs[0].portrait_path.clone().unwrap());
+        assert!(imported_portrait.starts_with(CampaignStorageService::get_campaign_dir_path(&imported.id).unwrap()));
+        assert_eq!(fs::read(imported_portrait).unwrap(), b"not really a png");
+    }
+
+    #[test]
+    fn test_import_as_new_loads_the_new_campaign() {
+        let campaign_id = create_test_campaign();
+        new_character(campaign_id, "Tordek");
+        let dir = tempdir().unwrap();
+        let export = ImportExportService::export_campaign(&campaign_id, &dir.path().join("tordek")).unwrap();
+
+        // The new campaign has no ID to listen to until it's imported
+        let events = MockEmitter::subscribe_all();
+        let result = ImportExportService::import_campaign(Path::new(&export.path), ImportMode::CreateNew { name: None }).unwrap();
+        let reloads: Vec<_> = events.take_reloads().into_iter()
+            .filter(|event| event.campaign_id == result.campaign.id)
+            .collect();
+        assert_eq!(reloads.len(), 1);
+        assert_eq!(reloads[0].reason, ReloadReason::Imported);
+        assert!(!events.take().iter().any(|event| event.campaign_id == result.campaign.id));
+    }
+
+    #[test]
+    fn test_merge_resolves_conflicts() {
+        let campaign_id = create_test_campaign();
+        let character = new_character(campaign_id, "Brann");
+        let dir = tempdir().unwrap();
+        let archive = PathBuf::from(
+            ImportExportService::export_campaign(&campaign_id, &dir.path().join("snapshot.dmcampaign")).unwrap().path
+        );
+
+        // Changed locally since the export
+        CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+            c.level = 3;
+            Ok(())
+        }).unwrap();
+        let added = new_character(campaign_id, "Tova");
+
+        let merge = |conflicts| ImportMode::Merge { campaign_id, conflicts };
+        let result = ImportExportService::import_campaign(&archive, merge(ConflictResolution::KeepNewer)).unwrap();
+        assert_eq!((result.added, result.updated, result.skipped), (0, 0, 1));
+        assert!(result.safety_backup.is_some());
+        assert_eq!(CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap().level, 3);
+
+        let result = ImportExportService::import_campaign(&archive, merge(ConflictResolution::Replace)).unwrap();
+        assert_eq!(result.updated, 1);
+        assert_eq!(CharacterStorageService::get_character(&campaign_id, &character.id).unwrap().unwrap().level, 1);
+        // Merging never removes what the archive doesn't have
+        assert!(CharacterStorageService::get_character(&campaign_id, &added.id).unwrap().is_some());
+
+        // Into another campaign, entities get new IDs since theirs are taken
+        let other_id = create_test_campaign();
+        let result = ImportExportService::import_campaign(
+            &archive,
+            ImportMode::Merge { campaign_id: other_id, conflicts: ConflictResolution::KeepNewer },
+        ).unwrap();
+        assert_eq!(result.added, 1);
+        let merged = CharacterStorageService::get_characters_by_campaign(&other_id).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_ne!(merged[0].id, character.id);
+        assert_eq!(merged[0].campaign_id, other_id);
+    }
+
+    #[test]
+    fn test_import_verifies_integrity() {
+        let campaign_id = create_test_campaign();
+        new_character(campaign_id, "Brann");
+        let dir = tempdir().unwrap();
+        let export = ImportExportService::export_campaign(&campaign_id, &dir.path().join("original.dmcampaign")).unwrap();
+
+        // Same manifest, tampered characters file
+        let tampered = dir.path().join("tampered.dmcampaign");
+        let mut source = ZipArchive::new(File::open(&export.path).unwrap()).unwrap();
+        let mut zip = ZipWriter::new(File::create(&tampered).unwrap());
+        for i in 0..source.len() {
+            let mut entry = source.by_index(i).unwrap();
+            let name = entry.name().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            if name.ends_with("characters.json") {
+                data = String::from_utf8(data).unwrap().replace("Brann", "Brenn").into_bytes();
+            }
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(&data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let error = ImportExportService::import_campaign(&tampered, ImportMode::CreateNew { name: None }).unwrap_err();
+        assert!(error.to_string().contains("checksum"));
+
+        let not_an_archive = dir.path().join("notes.dmcampaign");
+        fs::write(&not_an_archive, "just some notes").unwrap();
+        assert!(ImportExportService::inspect_archive(&not_an_archive).is_err());
+    }
+}
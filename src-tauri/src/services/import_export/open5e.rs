@@ -0,0 +1,259 @@
+// src-tauri/src/services/import_export/open5e.rs
+
+use super::bestiary::{
+    apply_language, apply_sense, is_passive_perception, parse_ability, parse_action, parse_damage_defenses,
+    parse_name, parse_size, parse_spellcasting, split_cost, split_usage, Conversion,
+};
+use crate::core::models::monster::{
+    Condition, CreatureType, LegendaryAction, LegendaryActions, MonsterFeature,
+};
+use crate::utils::error::{DmAssistantError, DmResult};
+use serde_json::{Map, Value};
+
+/// Fields describing the entry rather than the monster: document, art, and
+/// values repeated elsewhere in the entry
+const METADATA_FIELDS: [&str; 13] = [
+    "slug", "page_no", "environments", "img_main", "document__slug", "document__title", "document__license_url",
+    "document__url", "v2_converted_path", "spell_list", "group", "cr", "perception",
+];
+
+const ABILITY_FIELDS: [&str; 6] = ["strength", "dexterity", "constitution", "intelligence", "wisdom", "charisma"];
+
+/// Convert a monster of the Open5e API. Its text is plain, as printed in the books.
+pub(super) fn convert(entry: &Value) -> DmResult<Conversion> {
+    let fields = entry.as_object()
+        .ok_or_else(|| DmAssistantError::invalid_input("Monster entry is not an object"))?;
+    let name = fields.get("name").and_then(Value::as_str).filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| DmAssistantError::invalid_input("Monster has no name"))?;
+
+    let mut conversion = Conversion::new(name);
+    for (key, value) in fields {
+        // Empty text and lists stand for missing values
+        let empty = match value {
+            Value::Null => true,
+            Value::String(text) => text.trim().is_empty(),
+            Value::Array(items) => items.is_empty(),
+            _ => false,
+        };
+        if empty {
+            continue;
+        }
+
+        match key.as_str() {
+            // Read along with other fields
+            "name" | "legendary_desc" => {}
+            "desc" => conversion.notes = text(value).to_string(),
+            "size" => match parse_size(text(value)) {
+                Some(size) => conversion.stat_block.size = size,
+                None => conversion.unmapped(key, value),
+            },
+            "type" => match parse_name::<CreatureType>(text(value)) {
+                Some(creature_type) => conversion.stat_block.creature_type = creature_type,
+                None => conversion.unmapped(key, value),
+            },
+            "subtype" => conversion.stat_block.subtypes = text(value).split(',')
+                .map(|subtype| subtype.trim().to_string())
+                .filter(|subtype| !subtype.is_empty())
+                .collect(),
+            "alignment" => conversion.stat_block.alignment = text(value).trim().to_string(),
+            "armor_class" => match value.as_u64().and_then(|ac| u8::try_from(ac).ok()) {
+                Some(armor_class) => conversion.stat_block.armor_class.value = armor_class,
+                None => conversion.unmapped(key, value),
+            },
+            // e.g. "natural armor", or another armor class: "15 with mage armor"
+            "armor_desc" if text(value).trim_start().starts_with(|c: char| c.is_ascii_digit()) => conversion.unmapped(key, value),
+            "armor_desc" => conversion.stat_block.armor_class.source = Some(text(value).trim().to_string()),
+            "hit_points" => match value.as_u64().and_then(|hp| u32::try_from(hp).ok()) {
+                Some(average) => conversion.stat_block.hit_points.average = average,
+                None => conversion.unmapped(key, value),
+            },
+            "hit_dice" => conversion.stat_block.hit_points.formula =
+                Some(text(value).chars().filter(|c| !c.is_whitespace()).collect()),
+            "speed" => convert_speed(value, &mut conversion),
+            key if ABILITY_FIELDS.contains(&key) => convert_score(key, value, &mut conversion),
+            key if key.ends_with("_save") => {
+                let bonus = value.as_i64().and_then(|bonus| i32::try_from(bonus).ok());
+                match (parse_ability(key), bonus) {
+                    (Some(ability), Some(bonus)) => { conversion.stat_block.saving_throws.insert(ability, bonus); }
+                    _ => conversion.unmapped(key, value),
+                }
+            }
+            "skills" => convert_skills(value, &mut conversion),
+            "damage_vulnerabilities" | "damage_resistances" | "damage_immunities" => {
+                let (defenses, unknown) = parse_damage_defenses(text(value));
+                for text in unknown {
+                    conversion.unmapped(key, &Value::String(text));
+                }
+                let stat_block = &mut conversion.stat_block;
+                match key.as_str() {
+                    "damage_vulnerabilities" => stat_block.damage_vulnerabilities = defenses,
+                    "damage_resistances" => stat_block.damage_resistances = defenses,
+                    _ => stat_block.damage_immunities = defenses,
+                }
+            }
+            "condition_immunities" => {
+                for condition in text(value).split(',').map(str::trim) {
+                    match parse_name::<Condition>(condition) {
+                        Some(condition) => conversion.stat_block.condition_immunities.push(condition),
+                        None => conversion.unmapped(key, &Value::String(condition.to_string())),
+                    }
+                }
+            }
+            "senses" => {
+                for sense in text(value).split(',') {
+                    if !is_passive_perception(sense) && !apply_sense(&mut conversion.stat_block.senses, sense) {
+                        conversion.unmapped(key, &Value::String(sense.trim().to_string()));
+                    }
+                }
+            }
+            "languages" => {
+                for language in text(value).split(',') {
+                    apply_language(&mut conversion.stat_block, language);
+                }
+            }
+            "challenge_rating" => match text(value).parse() {
+                Ok(challenge_rating) => conversion.stat_block.challenge_rating = challenge_rating,
+                Err(_) => conversion.unmapped(key, value),
+            },
+            "actions" | "bonus_actions" | "reactions" => {
+                let actions = named_entries(key, value, &mut conversion)
+                    .map(|(name, description)| parse_action(name, description))
+                    .collect();
+                let stat_block = &mut conversion.stat_block;
+                match key.as_str() {
+                    "actions" => stat_block.actions = actions,
+                    "bonus_actions" => stat_block.bonus_actions = actions,
+                    _ => stat_block.reactions = actions,
+                }
+            }
+            "special_abilities" => convert_special_abilities(value, &mut conversion),
+            "legendary_actions" => convert_legendary_actions(fields, value, &mut conversion),
+            key if METADATA_FIELDS.contains(&key) => {}
+            _ => conversion.unmapped(key, value),
+        }
+    }
+    Ok(conversion)
+}
+
+fn text(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+/// `{"walk": 40, "fly": 80, "hover": true}`
+fn convert_speed(value: &Value, conversion: &mut Conversion) {
+    let Some(speeds) = value.as_object() else { return conversion.unmapped("speed", value) };
+    let speed = &mut conversion.stat_block.speed;
+    speed.walk = 0;
+
+    let mut unmapped = Vec::new();
+    for (mode, value) in speeds {
+        if mode == "hover" {
+            speed.hover = value.as_bool().unwrap_or(false);
+            continue;
+        }
+        let slot = match mode.as_str() {
+            "walk" => None,
+            "burrow" => Some(&mut speed.burrow),
+            "climb" => Some(&mut speed.climb),
+            "fly" => Some(&mut speed.fly),
+            "swim" => Some(&mut speed.swim),
+            _ => {
+                unmapped.push((format!("speed.{}", mode), value));
+                continue;
+            }
+        };
+        match (slot, value.as_u64().and_then(|feet| u32::try_from(feet).ok())) {
+            (Some(slot), Some(feet)) => *slot = Some(feet),
+            (None, Some(feet)) => speed.walk = feet,
+            (_, None) => unmapped.push((format!("speed.{}", mode), value)),
+        }
+    }
+    for (field, value) in unmapped {
+        conversion.unmapped(&field, value);
+    }
+}
+
+fn convert_score(key: &str, value: &Value, conversion: &mut Conversion) {
+    let Some(score) = value.as_u64().and_then(|score| u8::try_from(score).ok()) else {
+        return conversion.unmapped(key, value);
+    };
+    let scores = &mut conversion.stat_block.ability_scores;
+    let slot = match key {
+        "strength" => &mut scores.strength,
+        "dexterity" => &mut scores.dexterity,
+        "constitution" => &mut scores.constitution,
+        "intelligence" => &mut scores.intelligence,
+        "wisdom" => &mut scores.wisdom,
+        _ => &mut scores.charisma,
+    };
+    *slot = score;
+}
+
+/// `{"perception": 11, "stealth": 7}`
+fn convert_skills(value: &Value, conversion: &mut Conversion) {
+    let Some(skills) = value.as_object() else { return conversion.unmapped("skills", value) };
+    for (name, bonus) in skills {
+        let bonus = bonus.as_i64().and_then(|bonus| i32::try_from(bonus).ok());
+        match (parse_name(name), bonus) {
+            (Some(skill), Some(bonus)) => { conversion.stat_block.skills.insert(skill, bonus); }
+            _ => conversion.unmapped(&format!("skills.{}", name), &skills[name]),
+        }
+    }
+}
+
+/// Name and text of the features of a list, e.g. `[{"name": "Bite", "desc": "..."}]`.
+/// Entries without a name are reported.
+fn named_entries<'a>(key: &str, value: &'a Value, conversion: &mut Conversion) -> impl Iterator<Item = (&'a str, &'a str)> {
+    let Some(entries) = value.as_array() else {
+        conversion.unmapped(key, value);
+        return Vec::new().into_iter();
+    };
+    let mut named = Vec::new();
+    for entry in entries {
+        match entry.get("name").and_then(Value::as_str).filter(|name| !name.trim().is_empty()) {
+            Some(name) => named.push((name, entry.get("desc").and_then(Value::as_str).unwrap_or_default())),
+            None => conversion.unmapped(key, entry),
+        }
+    }
+    named.into_iter()
+}
+
+/// Traits, and the spellcasting traits that list spells
+fn convert_special_abilities(value: &Value, conversion: &mut Conversion) {
+    for (name, description) in named_entries("special_abilities", value, conversion) {
+        let spellcasting = name.contains("Spellcasting").then(|| parse_spellcasting(name, description)).flatten();
+        match spellcasting {
+            Some(spellcasting) => conversion.stat_block.spellcasting.push(spellcasting),
+            None => {
+                let (name, usage) = split_usage(name);
+                conversion.stat_block.traits.push(MonsterFeature { name, description: description.trim().to_string(), usage });
+            }
+        }
+    }
+}
+
+fn convert_legendary_actions(fields: &Map<String, Value>, value: &Value, conversion: &mut Conversion) {
+    let actions = named_entries("legendary_actions", value, conversion)
+        .map(|(name, description)| {
+            let (name, cost) = split_cost(name);
+            LegendaryAction { action: parse_action(&name, description), cost }
+        })
+        .collect();
+    // "The dragon can take 3 legendary actions, ..."
+    let description = fields.get("legendary_desc").and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|description| !description.is_empty());
+    let per_round = description
+        .and_then(|description| {
+            let words: Vec<&str> = description.split_whitespace().collect();
+            let position = words.windows(2).position(|pair| pair[0] == "take" && pair[1].parse::<u8>().is_ok())?;
+            words[position + 1].parse().ok()
+        })
+        .unwrap_or(3);
+
+    conversion.stat_block.legendary_actions = Some(LegendaryActions {
+        per_round,
+        description: description.map(str::to_string),
+        actions,
+    });
+}
@@ -0,0 +1,90 @@
+// src-tauri/src/services/item_storage.rs
+
+use crate::core::models::common::EntityKind;
+use crate::core::models::item::{Item, ItemDetails, UpdateItemRequest};
+use crate::core::rules::dice;
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Query, Repository};
+use crate::utils::error::{validate_non_empty, DmAssistantError, DmResult};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Item collection for a campaign
+pub type ItemCollection = EntityCollection<Item>;
+
+impl Entity for Item {
+    const KIND: EntityKind = EntityKind::Item;
+    const COLLECTION: &'static str = "items";
+    const LABEL: &'static str = "Item";
+
+    fn id(&self) -> Uuid { self.id }
+    fn campaign_id(&self) -> Uuid { self.campaign_id }
+    fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+    fn touch(&mut self) { self.updated_at = Utc::now(); }
+}
+
+static REPOSITORY: CampaignRepository<Item> = CampaignRepository::new();
+
+/// File-based item storage service: the homebrew items of each campaign
+pub struct ItemStorageService;
+
+impl ItemStorageService {
+    /// Repository holding the items
+    pub fn repository() -> &'static dyn Repository<Item> {
+        &REPOSITORY
+    }
+
+    /// Create a new item
+    pub fn create_item(item: Item) -> DmResult<Item> {
+        Self::validate_details(&item.details)?;
+        Self::repository().create(item)
+    }
+
+    /// Get item by ID
+    pub fn get_item(campaign_id: &Uuid, item_id: &Uuid) -> DmResult<Option<Item>> {
+        Self::repository().get(campaign_id, item_id)
+    }
+
+    /// Get all items for campaign, sorted by name
+    pub fn get_items_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<Item>> {
+        Self::repository().query(
+            campaign_id,
+            &Query::new().sort_by(|a: &Item, b: &Item| a.details.name.cmp(&b.details.name)),
+        )
+    }
+
+    /// Update item data
+    pub fn update_item(campaign_id: &Uuid, item_id: &Uuid, req: UpdateItemRequest) -> DmResult<Item> {
+        Self::repository().modify(campaign_id, item_id, Box::new(|item| {
+            item.update(req);
+            Self::validate_details(&item.details)
+        }))
+    }
+
+    /// Delete item
+    pub fn delete_item(campaign_id: &Uuid, item_id: &Uuid) -> DmResult<bool> {
+        Self::repository().delete(campaign_id, item_id)
+    }
+
+    /// Check an item is consistent: weapon dice that can be rolled, sensible ranges and weight
+    pub fn validate_details(details: &ItemDetails) -> DmResult<()> {
+        validate_non_empty(&details.name, "Item name")?;
+        if details.weight.is_some_and(|weight| weight < 0.0 || weight.is_nan()) {
+            return Err(DmAssistantError::validation("Weight can't be negative"));
+        }
+        if let Some(weapon) = &details.weapon {
+            if let Some(damage) = &weapon.damage {
+                dice::parse(&damage.dice)?;
+            }
+            if let Some(versatile) = &weapon.versatile {
+                dice::parse(versatile)?;
+            }
+            if let (Some(range), Some(long_range)) = (weapon.range, weapon.long_range) {
+                if long_range < range {
+                    return Err(DmAssistantError::validation("Long range must be at least the normal range"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
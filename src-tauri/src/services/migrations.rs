@@ -0,0 +1,392 @@
+// src-tauri/src/services/migrations.rs
+
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::services::campaign_storage::{CampaignStorageService, CAMPAIGN_MANIFEST};
+use crate::services::history::HISTORY_FILE;
+use crate::services::storage::CampaignStore;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{backup_file, file_exists, get_app_data_dir, load_json, save_json};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Key holding the schema version in the files with snake_case keys
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Key holding the schema version in the files with camelCase keys
+pub const CAMEL_CASE_SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+/// Files written with camelCase keys, like the models sent to the frontend
+const CAMEL_CASE_FILES: &[&str] = &[CAMPAIGN_MANIFEST, "app_state.json"];
+
+/// Versioned files of a campaign directory
+pub const CAMPAIGN_FILES: &[&str] = &[
+    CAMPAIGN_MANIFEST,
+    "characters.json",
+    "npcs.json",
+    "monsters.json",
+    "spells.json",
+    "items.json",
+    "relationships.json",
+    "roll_history.json",
+    HISTORY_FILE,
+];
+
+/// Versioned files of the app data directory
+pub const APP_FILES: &[&str] = &["app_state.json"];
+
+/// Step upgrading a file from `from_version` to `from_version + 1`
+pub struct Migration {
+    pub file: &'static str,
+    pub from_version: u32,
+    pub description: &'static str,
+    pub apply: fn(&mut Value) -> DmResult<()>,
+}
+
+/// Registered migrations.
+///
+/// Version 1 introduced `schema_version` itself, so files written before it
+/// (version 0) only need to be stamped. A format change bumps `SCHEMA_VERSION`
+/// and adds a step here for every file it affects; files without a step for a
+/// version are just stamped with the new one.
+const MIGRATIONS: &[Migration] = &[
+    Migration { file: CAMPAIGN_MANIFEST, from_version: 1, description: "camelCase schema version key", apply: camel_case_version_key },
+    Migration { file: "app_state.json", from_version: 1, description: "camelCase schema version key", apply: camel_case_version_key },
+];
+
+/// Version 1 wrote `schema_version` in every file, camelCase ones included
+fn camel_case_version_key(value: &mut Value) -> DmResult<()> {
+    if let Some(object) = value.as_object_mut() {
+        object.remove(SCHEMA_VERSION_KEY);
+    }
+    Ok(())
+}
+
+/// File migrated to the current schema
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMigration {
+    pub path: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub backup_path: String, // Copy of the file before migrating
+}
+
+/// File that couldn't be migrated
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationFailure {
+    pub campaign_id: Option<Uuid>, // None for app-level files
+    pub path: String,
+    pub error: String,
+}
+
+/// Outcome of migrating every file
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub migrated: Vec<FileMigration>,
+    pub failures: Vec<MigrationFailure>,
+}
+
+/// Check if a file carries a schema version
+pub fn is_versioned(file_path: &Path) -> bool {
+    file_path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| CAMPAIGN_FILES.contains(&name) || APP_FILES.contains(&name))
+}
+
+/// Key holding the schema version in a file, in the casing of its other keys
+pub fn schema_version_key(file_name: &str) -> &'static str {
+    if CAMEL_CASE_FILES.contains(&file_name) {
+        CAMEL_CASE_SCHEMA_VERSION_KEY
+    } else {
+        SCHEMA_VERSION_KEY
+    }
+}
+
+/// Schema version of a JSON document, 0 if it predates versioning
+pub fn schema_version(file_name: &str, value: &Value) -> u32 {
+    // Version 1 camelCase files still have the snake_case key
+    [schema_version_key(file_name), SCHEMA_VERSION_KEY].iter()
+        .find_map(|key| value.get(key).and_then(Value::as_u64))
+        .map(|version| version as u32)
+        .unwrap_or(0)
+}
+
+/// Upgrade a document to the current schema in memory.
+/// Returns the version it had if it was upgraded.
+pub fn upgrade(file_name: &str, value: &mut Value) -> DmResult<Option<u32>> {
+    upgrade_with(MIGRATIONS, SCHEMA_VERSION, file_name, value)
+}
+
+fn upgrade_with(migrations: &[Migration], target: u32, file_name: &str, value: &mut Value) -> DmResult<Option<u32>> {
+    if !value.is_object() {
+        return Err(DmAssistantError::json(&format!("{} is not a JSON object", file_name)));
+    }
+
+    let from_version = schema_version(file_name, value);
+    if from_version > target {
+        return Err(DmAssistantError::storage(&format!(
+            "{} has schema version {}, but this version of the app only supports up to {}",
+            file_name, from_version, target
+        )));
+    }
+    if from_version == target {
+        return Ok(None);
+    }
+
+    for version in from_version..target {
+        for migration in migrations.iter().filter(|m| m.file == file_name && m.from_version == version) {
+            (migration.apply)(value).map_err(|e| DmAssistantError::storage(&format!(
+                "Migration of {} from version {} ({}) failed: {}",
+                file_name, version, migration.description, e
+            )))?;
+        }
+        value[schema_version_key(file_name)] = Value::from(version + 1);
+    }
+
+    Ok(Some(from_version))
+}
+
+/// Load a file, upgrading it to the current schema in memory if it's versioned.
+/// Nothing is written: the file is saved in the current schema by the next write.
+pub fn load_versioned<T: DeserializeOwned>(file_path: &Path) -> DmResult<T> {
+    let mut value: Value = load_json(file_path)?;
+    if is_versioned(file_path) {
+        let file_name = file_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        upgrade(file_name, &mut value)?;
+    }
+    serde_json::from_value(value)
+        .map_err(|e| DmAssistantError::json(&format!("Failed to deserialize {}: {}", file_path.display(), e)))
+}
+
+/// Upgrades persisted files to the current schema
+pub struct MigrationService;
+
+impl MigrationService {
+    /// Migrate a file on disk, keeping a timestamped backup of the old version.
+    /// Returns None if the file doesn't exist or is already current.
+    pub fn migrate_file(file_path: &Path) -> DmResult<Option<FileMigration>> {
+        if !file_exists(file_path) {
+            return Ok(None);
+        }
+
+        let file_name = file_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let mut value: Value = load_json(file_path)?;
+        let Some(from_version) = upgrade(file_name, &mut value)? else {
+            return Ok(None);
+        };
+
+        let backup_path = backup_file(file_path)?;
+        save_json(&value, file_path)?;
+        CampaignStore::global().invalidate(file_path);
+
+        Ok(Some(FileMigration {
+            path: file_path.display().to_string(),
+            from_version,
+            to_version: SCHEMA_VERSION,
+            backup_path: backup_path.display().to_string(),
+        }))
+    }
+
+    /// Migrate every file of a campaign, stopping at the first failure
+    pub fn migrate_campaign(campaign_id: &Uuid) -> DmResult<Vec<FileMigration>> {
+        let _lock = lock_campaign(campaign_id)?;
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+
+        let mut migrated = Vec::new();
+        for file_name in CAMPAIGN_FILES {
+            if let Some(migration) = Self::migrate_file(&campaign_dir.join(file_name))? {
+                migrated.push(migration);
+            }
+        }
+        Ok(migrated)
+    }
+
+    /// Migrate the app files and every campaign.
+    /// A file that fails is reported and left untouched, the others are still migrated.
+    pub fn migrate_all() -> DmResult<MigrationReport> {
+        let mut report = MigrationReport::default();
+
+        let app_dir = get_app_data_dir()?;
+        for file_name in APP_FILES {
+            let file_path = app_dir.join(file_name);
+            report.record(None, &file_path, Self::migrate_file(&file_path));
+        }
+
+        for campaign_id in CampaignStorageService::list_campaign_ids()? {
+            let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign_id)?;
+            // A campaign that can't be locked is reported, the others are still migrated
+            let _lock = match lock_campaign(&campaign_id) {
+                Ok(lock) => lock,
+                Err(e) => {
+                    report.record(Some(campaign_id), &campaign_dir, Err(e));
+                    continue;
+                }
+            };
+            for file_name in CAMPAIGN_FILES {
+                let file_path = campaign_dir.join(file_name);
+                report.record(Some(campaign_id), &file_path, Self::migrate_file(&file_path));
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl MigrationReport {
+    fn record(&mut self, campaign_id: Option<Uuid>, file_path: &Path, result: DmResult<Option<FileMigration>>) {
+        match result {
+            Ok(Some(migration)) => self.migrated.push(migration),
+            Ok(None) => {}
+            Err(e) => self.failures.push(MigrationFailure {
+                campaign_id,
+                path: file_path.display().to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::character_storage::CharacterStorageService;
+    use crate::utils::test_support::create_test_campaign;
+    use serde_json::json;
+
+    fn rename_hp(value: &mut Value) -> DmResult<()> {
+        let hp = value.as_object_mut().and_then(|o| o.remove("hp"))
+            .ok_or_else(|| DmAssistantError::validation("missing hp"))?;
+        value["max_hp"] = hp;
+        Ok(())
+    }
+
+    fn add_temp_hp(value: &mut Value) -> DmResult<()> {
+        value["temp_hp"] = json!(0);
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration { file: "hero.json", from_version: 1, description: "rename hp", apply: rename_hp },
+        Migration { file: "hero.json", from_version: 2, description: "add temp hp", apply: add_temp_hp },
+        Migration { file: "other.json", from_version: 1, description: "unrelated", apply: rename_hp },
+    ];
+
+    #[test]
+    fn test_upgrade_runs_steps_in_order() {
+        let mut legacy = json!({ "hp": 12 });
+        assert_eq!(upgrade_with(TEST_MIGRATIONS, 3, "hero.json", &mut legacy).unwrap(), Some(0));
+        assert_eq!(legacy, json!({ "max_hp": 12, "temp_hp": 0, "schema_version": 3 }));
+
+        // Already current: untouched
+        assert_eq!(upgrade_with(TEST_MIGRATIONS, 3, "hero.json", &mut legacy).unwrap(), None);
+
+        // Written by a newer app
+        let mut newer = json!({ "schema_version": 4 });
+        assert!(upgrade_with(TEST_MIGRATIONS, 3, "hero.json", &mut newer).is_err());
+
+        // A failing step names the migration
+        let mut broken = json!({ "schema_version": 1 });
+        let error = upgrade_with(TEST_MIGRATIONS, 3, "hero.json", &mut broken).unwrap_err();
+        assert!(error.to_string().contains("rename hp"));
+    }
+
+    #[test]
+    fn test_migrate_legacy_campaign_files() {
+        let campaign_id = create_test_campaign();
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap();
+        let characters_path = campaign_dir.join("characters.json");
+        save_json(&json!({
+            "campaign_id": campaign_id,
+            "characters": {},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+        }), &characters_path).unwrap();
+
+        // Readable before migrating, without writing
+        assert!(CharacterStorageService::get_characters_by_campaign(&campaign_id).unwrap().is_empty());
+        assert_eq!(schema_version("characters.json", &load_json(&characters_path).unwrap()), 0);
+
+        let migrated = MigrationService::migrate_campaign(&campaign_id).unwrap();
+        assert_eq!(migrated.len(), 1);
+        assert_eq!(migrated[0].from_version, 0);
+        assert_eq!(schema_version("characters.json", &load_json(&characters_path).unwrap()), SCHEMA_VERSION);
+        let backup: Value = load_json(Path::new(&migrated[0].backup_path)).unwrap();
+        assert_eq!(schema_version("characters.json", &backup), 0);
+
+        // Nothing left to do
+        assert!(MigrationService::migrate_campaign(&campaign_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_camel_case_files_move_to_the_camel_case_key() {
+        let campaign_id = create_test_campaign();
+        let manifest_path = CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap().join(CAMPAIGN_MANIFEST);
+        let mut manifest: Value = load_json(&manifest_path).unwrap();
+        assert_eq!(manifest[CAMEL_CASE_SCHEMA_VERSION_KEY], json!(SCHEMA_VERSION));
+        assert!(manifest.get(SCHEMA_VERSION_KEY).is_none());
+
+        // As written by version 1
+        let object = manifest.as_object_mut().unwrap();
+        object.remove(CAMEL_CASE_SCHEMA_VERSION_KEY);
+        object.insert(SCHEMA_VERSION_KEY.to_string(), json!(1));
+        save_json(&manifest, &manifest_path).unwrap();
+        assert_eq!(schema_version(CAMPAIGN_MANIFEST, &manifest), 1);
+
+        let migrated = MigrationService::migrate_campaign(&campaign_id).unwrap();
+        assert_eq!(migrated[0].from_version, 1);
+        let manifest: Value = load_json(&manifest_path).unwrap();
+        assert_eq!(manifest[CAMEL_CASE_SCHEMA_VERSION_KEY], json!(SCHEMA_VERSION));
+        assert!(manifest.get(SCHEMA_VERSION_KEY).is_none());
+        assert_eq!(CampaignStorageService::require_campaign(&campaign_id).unwrap().schema_version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_all_reports_failures() {
+        let campaign_id = create_test_campaign();
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap();
+        save_json(&json!({ "schema_version": SCHEMA_VERSION + 1 }), &campaign_dir.join("npcs.json")).unwrap();
+        save_json(&json!({
+            "campaign_id": campaign_id,
+            "edges": [],
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+        }), &campaign_dir.join("relationships.json")).unwrap();
+
+        let report = MigrationService::migrate_all().unwrap();
+        let failures: Vec<&MigrationFailure> = report.failures.iter()
+            .filter(|f| f.campaign_id == Some(campaign_id))
+            .collect();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].path.ends_with("npcs.json"));
+        assert!(report.migrated.iter().any(|m| m.path.ends_with("relationships.json")
+            && m.path.contains(&campaign_id.to_string())));
+    }
+
+    #[test]
+    fn test_migrate_all_reports_campaigns_that_cant_be_locked() {
+        let locked_out = create_test_campaign();
+        let lock_path = get_app_data_dir().unwrap().join("locks").join(format!("{}.lock", locked_out));
+        let _ = std::fs::remove_file(&lock_path);
+        std::fs::create_dir_all(&lock_path).unwrap();
+
+        let campaign_id = create_test_campaign();
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap();
+        save_json(&json!({
+            "campaign_id": campaign_id,
+            "edges": [],
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+        }), &campaign_dir.join("relationships.json")).unwrap();
+
+        let report = MigrationService::migrate_all().unwrap();
+        assert!(report.failures.iter().any(|f| f.campaign_id == Some(locked_out) && f.error.contains("lock")));
+        assert!(report.migrated.iter().any(|m| m.path.contains(&campaign_id.to_string())));
+        std::fs::remove_dir(&lock_path).unwrap();
+    }
+}
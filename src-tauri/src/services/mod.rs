@@ -2,4 +2,18 @@ pub mod storage;
 pub mod backup;
 pub mod import_export;
 pub mod templates;
-pub mod character_storage;
\ No newline at end of file
+pub mod campaign_storage;
+pub mod character_storage;
+pub mod compendium;
+pub mod dice_roller;
+pub mod entity_index;
+pub mod events;
+pub mod history;
+pub mod item_storage;
+pub mod migrations;
+pub mod monster_storage;
+pub mod npc_storage;
+pub mod relationship_graph;
+pub mod spell_storage;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_storage;
@@ -0,0 +1,240 @@
+// src-tauri/src/services/monster_storage.rs
+
+use crate::core::models::common::{Ability, EntityKind};
+use crate::core::models::monster::{ActionUsage, Monster, MonsterAction, SpellFrequency, StatBlock, UpdateMonsterRequest};
+use crate::core::rules::dice;
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Query, Repository};
+use crate::utils::error::{validate_non_empty, validate_range, DmAssistantError, DmResult};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Monster collection for a campaign
+pub type MonsterCollection = EntityCollection<Monster>;
+
+impl Entity for Monster {
+    const KIND: EntityKind = EntityKind::Monster;
+    const COLLECTION: &'static str = "monsters";
+    const LABEL: &'static str = "Monster";
+
+    fn id(&self) -> Uuid { self.id }
+    fn campaign_id(&self) -> Uuid { self.campaign_id }
+    fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+    fn touch(&mut self) { self.updated_at = Utc::now(); }
+}
+
+static REPOSITORY: CampaignRepository<Monster> = CampaignRepository::new();
+
+/// File-based monster storage service: the bestiary of each campaign
+pub struct MonsterStorageService;
+
+impl MonsterStorageService {
+    /// Repository holding the monsters
+    pub fn repository() -> &'static dyn Repository<Monster> {
+        &REPOSITORY
+    }
+
+    /// Create a new monster
+    pub fn create_monster(monster: Monster) -> DmResult<Monster> {
+        Self::validate_stat_block(&monster.stat_block)?;
+        Self::repository().create(monster)
+    }
+
+    /// Get monster by ID
+    pub fn get_monster(campaign_id: &Uuid, monster_id: &Uuid) -> DmResult<Option<Monster>> {
+        Self::repository().get(campaign_id, monster_id)
+    }
+
+    /// Find a monster by ID alone, using the entity index
+    pub fn find_monster(monster_id: &Uuid) -> DmResult<Option<Monster>> {
+        Self::repository().find(monster_id)
+    }
+
+    /// Get all monsters for campaign, sorted by name
+    pub fn get_monsters_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<Monster>> {
+        Self::repository().query(
+            campaign_id,
+            &Query::new().sort_by(|a: &Monster, b: &Monster| a.stat_block.name.cmp(&b.stat_block.name)),
+        )
+    }
+
+    /// Modify monster in place. The stat block must still be valid afterwards.
+    pub fn modify_monster<F>(campaign_id: &Uuid, monster_id: &Uuid, modifier: F) -> DmResult<Monster>
+    where
+        F: FnOnce(&mut Monster) -> DmResult<()>,
+    {
+        Self::repository().modify(campaign_id, monster_id, Box::new(|monster| {
+            modifier(monster)?;
+            Self::validate_stat_block(&monster.stat_block)
+        }))
+    }
+
+    /// Update monster data
+    pub fn update_monster(campaign_id: &Uuid, monster_id: &Uuid, req: UpdateMonsterRequest) -> DmResult<Monster> {
+        Self::modify_monster(campaign_id, monster_id, |monster| {
+            monster.update(req);
+            Ok(())
+        })
+    }
+
+    /// Delete monster
+    pub fn delete_monster(campaign_id: &Uuid, monster_id: &Uuid) -> DmResult<bool> {
+        Self::repository().delete(campaign_id, monster_id)
+    }
+
+    /// Check a stat block is consistent: scores in range, dice expressions
+    /// that can be rolled, usage limits that make sense
+    pub fn validate_stat_block(stat_block: &StatBlock) -> DmResult<()> {
+        validate_non_empty(&stat_block.name, "Monster name")?;
+        for ability in Ability::ALL {
+            validate_range(stat_block.ability_scores.get(ability), 1, 30, &format!("{:?} score", ability))?;
+        }
+        validate_range(stat_block.armor_class.value, 1, 30, "Armor class")?;
+        if stat_block.hit_points.average == 0 {
+            return Err(DmAssistantError::validation("Hit points must be at least 1"));
+        }
+        if let Some(formula) = &stat_block.hit_points.formula {
+            dice::parse(formula)?;
+        }
+
+        let legendary = stat_block.legendary_actions.iter().flat_map(|legendary| &legendary.actions);
+        for action in stat_block.actions.iter()
+            .chain(&stat_block.bonus_actions)
+            .chain(&stat_block.reactions)
+            .chain(legendary.clone().map(|legendary| &legendary.action))
+        {
+            Self::validate_action(action)?;
+        }
+        for feature in stat_block.traits.iter().chain(stat_block.lair_actions.iter().flat_map(|lair| &lair.actions)) {
+            validate_non_empty(&feature.name, "Trait name")?;
+            if let Some(usage) = &feature.usage {
+                Self::validate_usage(usage)?;
+            }
+        }
+        if let Some(legendary) = &stat_block.legendary_actions {
+            validate_range(legendary.per_round, 1, 10, "Legendary actions per round")?;
+            for action in legendary.actions.iter() {
+                validate_range(action.cost, 1, legendary.per_round, &format!("Cost of {}", action.action.name))?;
+            }
+        }
+
+        for spellcasting in &stat_block.spellcasting {
+            for list in &spellcasting.spells {
+                match list.frequency {
+                    SpellFrequency::Level { level, .. } => validate_range(level, 1, 9, "Spell level")?,
+                    SpellFrequency::PerDay { uses } => validate_range(uses, 1, 10, "Spell uses per day")?,
+                    SpellFrequency::AtWill | SpellFrequency::Cantrip => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_action(action: &MonsterAction) -> DmResult<()> {
+        validate_non_empty(&action.name, "Action name")?;
+        for damage in &action.damage {
+            dice::parse(&damage.dice)?;
+        }
+        if let Some(usage) = &action.usage {
+            Self::validate_usage(usage)?;
+        }
+        Ok(())
+    }
+
+    fn validate_usage(usage: &ActionUsage) -> DmResult<()> {
+        match *usage {
+            ActionUsage::Recharge { min } => validate_range(min, 2, 6, "Recharge roll"),
+            ActionUsage::PerDay { uses } => validate_range(uses, 1, 10, "Uses per day"),
+            ActionUsage::RechargeAfterRest => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::monster::{
+        Attack, AttackKind, CreateMonsterRequest, Damage, DamageType, HitPoints, LegendaryAction, LegendaryActions,
+    };
+    use crate::services::history::HistoryService;
+    use crate::utils::test_support::create_test_campaign;
+
+    fn scimitar() -> MonsterAction {
+        MonsterAction {
+            name: "Scimitar".to_string(),
+            description: "Melee Weapon Attack: +4 to hit, reach 5 ft., one target. Hit: 5 (1d6 + 2) slashing damage.".to_string(),
+            attack: Some(Attack { kind: AttackKind::MeleeWeapon, to_hit: 4, reach: Some(5), range: None, long_range: None }),
+            saving_throw: None,
+            damage: vec![Damage { dice: "1d6+2".to_string(), damage_type: DamageType::Slashing }],
+            usage: None,
+        }
+    }
+
+    fn goblin(campaign_id: Uuid) -> Monster {
+        let mut stat_block = StatBlock {
+            name: "Goblin".to_string(),
+            hit_points: HitPoints { average: 7, formula: Some("2d6".to_string()) },
+            challenge_rating: "1/4".parse().unwrap(),
+            actions: vec![scimitar()],
+            ..StatBlock::default()
+        };
+        stat_block.ability_scores.dexterity = 14;
+        Monster::new(CreateMonsterRequest { campaign_id, stat_block, notes: None })
+    }
+
+    #[test]
+    fn test_monster_crud_and_lookup() {
+        let campaign_id = create_test_campaign();
+        let monster = MonsterStorageService::create_monster(goblin(campaign_id)).unwrap();
+        assert_eq!(MonsterStorageService::find_monster(&monster.id).unwrap().unwrap().stat_block.name, "Goblin");
+
+        let mut stat_block = monster.stat_block.clone();
+        stat_block.name = "Goblin Boss".to_string();
+        stat_block.hit_points = HitPoints { average: 21, formula: Some("6d6".to_string()) };
+        MonsterStorageService::update_monster(&campaign_id, &monster.id, UpdateMonsterRequest {
+            stat_block: Some(stat_block),
+            notes: None,
+        }).unwrap();
+
+        let monsters = MonsterStorageService::get_monsters_by_campaign(&campaign_id).unwrap();
+        assert_eq!(monsters.len(), 1);
+        assert_eq!(monsters[0].stat_block.name, "Goblin Boss");
+        assert_eq!(monsters[0].stat_block.hit_points.average, 21);
+
+        // Monsters are in the history like any other entity
+        HistoryService::undo(&campaign_id).unwrap();
+        assert_eq!(MonsterStorageService::get_monster(&campaign_id, &monster.id).unwrap().unwrap().stat_block.name, "Goblin");
+
+        assert!(MonsterStorageService::delete_monster(&campaign_id, &monster.id).unwrap());
+        assert!(MonsterStorageService::find_monster(&monster.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_invalid_stat_blocks_are_rejected() {
+        let campaign_id = create_test_campaign();
+
+        let mut monster = goblin(campaign_id);
+        monster.stat_block.ability_scores.strength = 0;
+        assert!(MonsterStorageService::create_monster(monster).is_err());
+
+        let mut monster = goblin(campaign_id);
+        monster.stat_block.actions[0].damage[0].dice = "1d6+".to_string();
+        assert!(MonsterStorageService::create_monster(monster).is_err());
+
+        let mut monster = goblin(campaign_id);
+        monster.stat_block.legendary_actions = Some(LegendaryActions {
+            per_round: 3,
+            description: None,
+            actions: vec![LegendaryAction { action: scimitar(), cost: 4 }],
+        });
+        assert!(MonsterStorageService::create_monster(monster).is_err());
+
+        // An update leaving the stat block invalid changes nothing
+        let monster = MonsterStorageService::create_monster(goblin(campaign_id)).unwrap();
+        assert!(MonsterStorageService::modify_monster(&campaign_id, &monster.id, |m| {
+            m.stat_block.name = "  ".to_string();
+            Ok(())
+        }).is_err());
+        assert_eq!(MonsterStorageService::get_monster(&campaign_id, &monster.id).unwrap().unwrap().stat_block.name, "Goblin");
+    }
+}
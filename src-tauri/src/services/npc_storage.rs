@@ -0,0 +1,268 @@
+// src-tauri/src/services/npc_storage.rs
+
+use crate::core::models::common::EntityKind;
+use crate::core::models::npc::{Npc, NpcInteraction, LogInteractionRequest, DanglingRelationship, NpcDeletionReport};
+use crate::core::models::relationship::GraphNode;
+use crate::services::character_storage::CharacterStorageService;
+use crate::services::history::HistoryService;
+use crate::services::relationship_graph::RelationshipGraphService;
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Query, Repository};
+use crate::utils::error::{DmAssistantError, DmResult};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// NPC collection for a campaign
+pub type NpcCollection = EntityCollection<Npc>;
+
+impl Entity for Npc {
+    const KIND: EntityKind = EntityKind::Npc;
+    const COLLECTION: &'static str = "npcs";
+    const LABEL: &'static str = "NPC";
+
+    fn id(&self) -> Uuid { self.id }
+    fn campaign_id(&self) -> Uuid { self.campaign_id }
+    fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+    fn touch(&mut self) { self.updated_at = Utc::now(); }
+}
+
+static REPOSITORY: CampaignRepository<Npc> = CampaignRepository::new();
+
+/// File-based NPC storage service
+pub struct NpcStorageService;
+
+impl NpcStorageService {
+    /// Repository holding the NPCs
+    pub fn repository() -> &'static dyn Repository<Npc> {
+        &REPOSITORY
+    }
+
+    /// Load NPCs for a campaign
+    pub fn load_npcs(campaign_id: &Uuid) -> DmResult<NpcCollection> {
+        REPOSITORY.load_collection(campaign_id)
+    }
+
+    /// Create a new NPC
+    pub fn create_npc(npc: Npc) -> DmResult<Npc> {
+        Self::repository().create(npc)
+    }
+
+    /// Get NPC by ID
+    pub fn get_npc(campaign_id: &Uuid, npc_id: &Uuid) -> DmResult<Option<Npc>> {
+        Self::repository().get(campaign_id, npc_id)
+    }
+
+    /// Find an NPC by ID alone, using the entity index
+    pub fn find_npc(npc_id: &Uuid) -> DmResult<Option<Npc>> {
+        Self::repository().find(npc_id)
+    }
+
+    /// Get all NPCs for campaign, sorted by name
+    pub fn get_npcs_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<Npc>> {
+        Self::repository().query(campaign_id, &Query::new().sort_by(|a: &Npc, b: &Npc| a.name.cmp(&b.name)))
+    }
+
+    /// Modify NPC in place
+    pub fn modify_npc<F>(campaign_id: &Uuid, npc_id: &Uuid, modifier: F) -> DmResult<Npc>
+    where
+        F: FnOnce(&mut Npc) -> DmResult<()>,
+    {
+        Self::repository().modify(campaign_id, npc_id, Box::new(modifier))
+    }
+
+    /// Append an interaction to the NPC log.
+    /// Every character must belong to the NPC's campaign.
+    pub fn log_interaction(campaign_id: &Uuid, npc_id: &Uuid, req: LogInteractionRequest) -> DmResult<NpcInteraction> {
+        if req.summary.trim().is_empty() {
+            return Err(DmAssistantError::validation("Interaction summary cannot be empty"));
+        }
+        for character_id in &req.character_ids {
+            if CharacterStorageService::get_character(campaign_id, character_id)?.is_none() {
+                return Err(DmAssistantError::not_found("Character", &character_id.to_string()));
+            }
+        }
+
+        let mut interaction = None;
+        Self::modify_npc(campaign_id, npc_id, |npc| {
+            interaction = Some(npc.log_interaction(req));
+            Ok(())
+        })?;
+
+        interaction.ok_or_else(|| DmAssistantError::internal("Interaction was not recorded"))
+    }
+
+    /// Character relationships pointing to an NPC
+    pub fn find_relationships_to(campaign_id: &Uuid, npc_id: &Uuid) -> DmResult<Vec<DanglingRelationship>> {
+        let mut relationships: Vec<DanglingRelationship> = CharacterStorageService::get_characters_by_campaign(campaign_id)?
+            .into_iter()
+            .filter_map(|character| {
+                let relationship = character.get_relationship(*npc_id)?;
+                Some(DanglingRelationship {
+                    character_id: character.id,
+                    character_name: character.name.clone(),
+                    relationship_id: relationship.id,
+                })
+            })
+            .collect();
+        relationships.sort_by(|a, b| a.character_name.cmp(&b.character_name));
+        Ok(relationships)
+    }
+
+    /// Delete NPC.
+    /// Character relationships and graph edges to the NPC are removed if
+    /// `remove_relationships` is set, otherwise they're left in place and reported.
+    pub fn delete_npc(campaign_id: &Uuid, npc_id: &Uuid, remove_relationships: bool) -> DmResult<NpcDeletionReport> {
+        // Undone as a whole, relationships included
+        let (deleted, relationships) = HistoryService::batch(campaign_id, || {
+            let deleted = Self::repository().delete(campaign_id, npc_id)?;
+
+            let relationships = Self::find_relationships_to(campaign_id, npc_id)?;
+            if remove_relationships {
+                for relationship in &relationships {
+                    CharacterStorageService::modify_character(campaign_id, &relationship.character_id, |character| {
+                        character.relationships.retain(|r| r.npc_id != *npc_id);
+                        Ok(())
+                    })?;
+                }
+                RelationshipGraphService::remove_node(campaign_id, &GraphNode::Npc(*npc_id))?;
+            }
+            Ok((deleted, relationships))
+        })?;
+
+        Ok(NpcDeletionReport {
+            npc_id: *npc_id,
+            deleted,
+            relationships,
+            relationships_removed: remove_relationships,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, PlayerCharacter, RelationshipType, UpdateRelationshipRequest};
+    use crate::core::models::npc::{CreateNpcRequest, NpcAttitude};
+    use crate::services::campaign_storage::CampaignStorageService;
+    use crate::services::entity_index::EntityIndexService;
+    use crate::utils::test_support::create_test_campaign;
+
+    fn new_npc(campaign_id: Uuid, name: &str) -> Npc {
+        Npc::new(CreateNpcRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: Some("Dwarf".to_string()),
+            role: Some("Blacksmith".to_string()),
+            location_id: None,
+            faction: Some("Lords' Alliance".to_string()),
+            attitude: None,
+            voice: Some("Gruff, speaks in short sentences".to_string()),
+            mannerisms: None,
+            secrets: Some(vec!["Owes money to the Zhentarim".to_string()]),
+            portrait_path: None,
+            notes: None,
+        })
+    }
+
+    fn character_related_to(campaign_id: Uuid, npc_id: Uuid) -> PlayerCharacter {
+        let mut character = PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: "Lia".to_string(),
+            race: "Gnome".to_string(),
+            class: "Wizard".to_string(),
+            level: 4,
+            max_hp: 22,
+            background: String::new(),
+            notes: None,
+        });
+        character.update_relationship(UpdateRelationshipRequest {
+            character_id: character.id,
+            npc_id,
+            relationship_type: RelationshipType::Friendly,
+            notes: None,
+        });
+        CharacterStorageService::create_character(character).unwrap()
+    }
+
+    #[test]
+    fn test_npc_crud_and_lookup() {
+        let campaign_id = create_test_campaign();
+        let npc = NpcStorageService::create_npc(new_npc(campaign_id, "Durgan")).unwrap();
+
+        assert_eq!(NpcStorageService::find_npc(&npc.id).unwrap().unwrap().name, "Durgan");
+        assert_eq!(NpcStorageService::get_npcs_by_campaign(&campaign_id).unwrap().len(), 1);
+
+        let updated = NpcStorageService::modify_npc(&campaign_id, &npc.id, |n| {
+            n.status = crate::core::models::npc::NpcStatus::Dead;
+            Ok(())
+        }).unwrap();
+        assert!(!updated.is_alive());
+    }
+
+    #[test]
+    fn test_lookup_in_a_missing_campaign_creates_nothing() {
+        let campaign_id = Uuid::new_v4();
+        let npc_id = Uuid::new_v4();
+        EntityIndexService::register(&npc_id, &campaign_id, EntityKind::Npc).unwrap();
+
+        assert!(NpcStorageService::find_npc(&npc_id).unwrap().is_none());
+        assert!(!CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap().exists());
+    }
+
+    #[test]
+    fn test_interaction_log_is_appended() {
+        let campaign_id = create_test_campaign();
+        let npc = NpcStorageService::create_npc(new_npc(campaign_id, "Sildar")).unwrap();
+        let character = character_related_to(campaign_id, npc.id);
+
+        NpcStorageService::log_interaction(&campaign_id, &npc.id, LogInteractionRequest {
+            character_ids: vec![character.id],
+            session_number: Some(1),
+            summary: "Rescued from the goblins".to_string(),
+            attitude_after: Some(NpcAttitude::Friendly),
+        }).unwrap();
+        NpcStorageService::log_interaction(&campaign_id, &npc.id, LogInteractionRequest {
+            character_ids: Vec::new(),
+            session_number: Some(2),
+            summary: "Sent a letter to the party".to_string(),
+            attitude_after: None,
+        }).unwrap();
+
+        let npc = NpcStorageService::get_npc(&campaign_id, &npc.id).unwrap().unwrap();
+        assert_eq!(npc.interactions.len(), 2);
+        assert_eq!(npc.interactions[0].session_number, Some(1));
+        assert_eq!(npc.attitude, NpcAttitude::Friendly);
+        assert_eq!(npc.interactions_with(&character.id).len(), 1);
+
+        // Characters from other campaigns can't be logged
+        let stranger = Uuid::new_v4();
+        assert!(NpcStorageService::log_interaction(&campaign_id, &npc.id, LogInteractionRequest {
+            character_ids: vec![stranger],
+            session_number: None,
+            summary: "Never happened".to_string(),
+            attitude_after: None,
+        }).is_err());
+    }
+
+    #[test]
+    fn test_delete_reports_or_removes_relationships() {
+        let campaign_id = create_test_campaign();
+        let kept = NpcStorageService::create_npc(new_npc(campaign_id, "Halia")).unwrap();
+        let removed = NpcStorageService::create_npc(new_npc(campaign_id, "Glasstaff")).unwrap();
+        let first = character_related_to(campaign_id, kept.id);
+        let second = character_related_to(campaign_id, removed.id);
+
+        let report = NpcStorageService::delete_npc(&campaign_id, &kept.id, false).unwrap();
+        assert!(report.deleted);
+        assert_eq!(report.relationships.len(), 1);
+        assert_eq!(report.relationships[0].character_id, first.id);
+        let first = CharacterStorageService::get_character(&campaign_id, &first.id).unwrap().unwrap();
+        assert!(first.get_relationship(kept.id).is_some());
+        assert!(NpcStorageService::find_npc(&kept.id).unwrap().is_none());
+
+        let report = NpcStorageService::delete_npc(&campaign_id, &removed.id, true).unwrap();
+        assert!(report.relationships_removed);
+        let second = CharacterStorageService::get_character(&campaign_id, &second.id).unwrap().unwrap();
+        assert!(second.get_relationship(removed.id).is_none());
+    }
+}
@@ -0,0 +1,573 @@
+// src-tauri/src/services/relationship_graph.rs
+
+use crate::core::models::character::PlayerCharacter;
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::core::models::relationship::{
+    GraphNode, RelationshipEdge, EdgeDirection, CreateEdgeRequest, UpdateEdgeRequest,
+    GraphNeighborhood, LabeledNode, GraphExport,
+};
+use crate::core::models::history::EntityChange;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::character_storage::CharacterStorageService;
+use crate::services::events::EventService;
+use crate::services::history::{HistoryService, EDGE_COLLECTION};
+use crate::services::npc_storage::NpcStorageService;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::services::storage::CampaignStore;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Relationship graph of a campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipGraph {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub campaign_id: Uuid,
+    pub edges: Vec<RelationshipEdge>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RelationshipGraph {
+    pub fn new(campaign_id: Uuid) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            campaign_id,
+            edges: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn get_edge(&self, edge_id: &Uuid) -> Option<&RelationshipEdge> {
+        self.edges.iter().find(|e| e.id == *edge_id)
+    }
+
+    /// Mirror character relationships as edges: character -> NPC, with the
+    /// relationship ID as edge ID. Returns true if the graph changed.
+    pub fn sync_character_relationships(&mut self, characters: &[PlayerCharacter]) -> bool {
+        let relationships: HashMap<Uuid, (&PlayerCharacter, _)> = characters.iter()
+            .flat_map(|c| c.relationships.iter().map(move |r| (r.id, (c, r))))
+            .collect();
+
+        let before = self.edges.len();
+        self.edges.retain(|e| !e.from_character || relationships.contains_key(&e.id));
+        let mut changed = self.edges.len() != before;
+
+        for (id, (character, relationship)) in relationships {
+            match self.edges.iter_mut().find(|e| e.id == id) {
+                Some(edge) => {
+                    if edge.relationship_type != relationship.relationship_type
+                        || edge.notes != relationship.notes
+                        || edge.last_interaction != relationship.last_interaction
+                    {
+                        edge.relationship_type = relationship.relationship_type.clone();
+                        edge.notes = relationship.notes.clone();
+                        edge.last_interaction = relationship.last_interaction;
+                        edge.updated_at = relationship.updated_at;
+                        changed = true;
+                    }
+                }
+                None => {
+                    self.edges.push(RelationshipEdge {
+                        id,
+                        from: GraphNode::Character(character.id),
+                        to: GraphNode::Npc(relationship.npc_id),
+                        relationship_type: relationship.relationship_type.clone(),
+                        mutual: false,
+                        notes: relationship.notes.clone(),
+                        last_interaction: relationship.last_interaction,
+                        from_character: true,
+                        created_at: relationship.created_at,
+                        updated_at: relationship.updated_at,
+                    });
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            // Keep a stable order regardless of HashMap iteration
+            self.edges.sort_by_key(|e| e.created_at);
+            self.updated_at = chrono::Utc::now();
+        }
+        changed
+    }
+
+    /// Edges that can be followed from a node in the given direction.
+    /// Mutual edges can be followed both ways.
+    pub fn edges_from<'a>(&'a self, node: &'a GraphNode, direction: EdgeDirection) -> impl Iterator<Item = (&'a RelationshipEdge, &'a GraphNode)> + 'a {
+        self.edges.iter().filter_map(move |edge| {
+            let outgoing = edge.from == *node;
+            let incoming = edge.to == *node;
+            let follow = match direction {
+                EdgeDirection::Outgoing => outgoing || (edge.mutual && incoming),
+                EdgeDirection::Incoming => incoming || (edge.mutual && outgoing),
+                EdgeDirection::Both => outgoing || incoming,
+            };
+            if follow { edge.other_end(node).map(|other| (edge, other)) } else { None }
+        })
+    }
+
+    /// Nodes and edges reachable from a node within `depth` steps
+    pub fn neighborhood(&self, center: &GraphNode, depth: usize, direction: EdgeDirection) -> GraphNeighborhood {
+        let mut visited: HashSet<&GraphNode> = HashSet::from([center]);
+        let mut nodes = Vec::new();
+        let mut edge_ids = HashSet::new();
+        let mut edges = Vec::new();
+        let mut frontier = vec![center];
+
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for node in frontier {
+                for (edge, other) in self.edges_from(node, direction) {
+                    if edge_ids.insert(edge.id) {
+                        edges.push(edge.clone());
+                    }
+                    if visited.insert(other) {
+                        nodes.push(other.clone());
+                        next.push(other);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        GraphNeighborhood { center: center.clone(), nodes, edges }
+    }
+
+    /// Shortest chain of edges linking two nodes (breadth-first search)
+    pub fn find_path(&self, from: &GraphNode, to: &GraphNode, direction: EdgeDirection) -> Option<Vec<RelationshipEdge>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        // Node -> edge used to reach it
+        let mut reached_by: HashMap<&GraphNode, &RelationshipEdge> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(node) = queue.pop_front() {
+            for (edge, other) in self.edges_from(node, direction) {
+                if other == from || reached_by.contains_key(other) {
+                    continue;
+                }
+                reached_by.insert(other, edge);
+                if other == to {
+                    let mut path = Vec::new();
+                    let mut current = to;
+                    while current != from {
+                        let edge = reached_by[current];
+                        path.push(edge.clone());
+                        current = edge.other_end(current)?;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(other);
+            }
+        }
+
+        None
+    }
+
+    /// Remove the edges touching a node, returning the removed edges. Edges
+    /// mirrored from character relationships stay: they follow the relationships.
+    pub fn remove_node(&mut self, node: &GraphNode) -> Vec<RelationshipEdge> {
+        let (removed, kept) = std::mem::take(&mut self.edges).into_iter()
+            .partition(|e| node.is_end_of(e) && !e.from_character);
+        self.edges = kept;
+        if !removed.is_empty() {
+            self.updated_at = chrono::Utc::now();
+        }
+        removed
+    }
+
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self, nodes: &[LabeledNode]) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph \"{}\" {{", self.campaign_id);
+        let _ = writeln!(dot, "    rankdir=LR;");
+
+        for LabeledNode { node, label } in nodes {
+            let shape = match node {
+                GraphNode::Character(_) => "ellipse",
+                GraphNode::Npc(_) => "box",
+                GraphNode::Faction(_) => "hexagon",
+            };
+            let _ = writeln!(dot, "    \"{}\" [label=\"{}\", shape={}];", dot_id(node), escape_dot(label), shape);
+        }
+
+        for edge in &self.edges {
+            let mut attributes = format!("label=\"{}\"", escape_dot(edge.relationship_type.display()));
+            if edge.mutual {
+                attributes.push_str(", dir=both");
+            }
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\" [{}];", dot_id(&edge.from), dot_id(&edge.to), attributes);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Stable DOT identifier for a node
+fn dot_id(node: &GraphNode) -> String {
+    match node {
+        GraphNode::Character(id) => format!("character:{}", id),
+        GraphNode::Npc(id) => format!("npc:{}", id),
+        GraphNode::Faction(name) => format!("faction:{}", escape_dot(name)),
+    }
+}
+
+/// Escape a string for use inside a quoted DOT identifier
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// File-based relationship graph storage.
+///
+/// Character relationships stay the source of truth for character -> NPC
+/// edges: they're mirrored into the graph every time it's loaded.
+pub struct RelationshipGraphService;
+
+impl RelationshipGraphService {
+    /// Get file path for campaign relationship graph
+    fn get_graph_file_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        Ok(campaign_dir.join("relationships.json"))
+    }
+
+    /// Load the graph, with character relationships mirrored in
+    pub fn load_graph(campaign_id: &Uuid) -> DmResult<RelationshipGraph> {
+        let file_path = Self::get_graph_file_path(campaign_id)?;
+
+        let mut graph = CampaignStore::global().load_or_else(&file_path, || RelationshipGraph::new(*campaign_id))?;
+
+        let characters = CharacterStorageService::get_characters_by_campaign(campaign_id)?;
+        graph.sync_character_relationships(&characters);
+        Ok(graph)
+    }
+
+    /// Save the graph
+    pub fn save_graph(graph: &RelationshipGraph) -> DmResult<()> {
+        let file_path = Self::get_graph_file_path(&graph.campaign_id)?;
+        CampaignStore::global().save(graph, &file_path)
+    }
+
+    /// Fail if a node doesn't exist in the campaign
+    fn ensure_node_exists(campaign_id: &Uuid, node: &GraphNode) -> DmResult<()> {
+        let exists = match node {
+            GraphNode::Character(id) => CharacterStorageService::get_character(campaign_id, id)?.is_some(),
+            GraphNode::Npc(id) => NpcStorageService::get_npc(campaign_id, id)?.is_some(),
+            GraphNode::Faction(name) => {
+                if name.trim().is_empty() || name.trim() != name {
+                    return Err(DmAssistantError::validation("Faction name cannot be empty or padded with spaces"));
+                }
+                true
+            }
+        };
+
+        if exists {
+            Ok(())
+        } else {
+            Err(DmAssistantError::not_found("Node", &dot_id(node)))
+        }
+    }
+
+    /// Add an edge between two nodes
+    pub fn add_edge(campaign_id: &Uuid, req: CreateEdgeRequest) -> DmResult<RelationshipEdge> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        if req.from == req.to {
+            return Err(DmAssistantError::validation("A relationship must link two different nodes"));
+        }
+        Self::ensure_node_exists(campaign_id, &req.from)?;
+        Self::ensure_node_exists(campaign_id, &req.to)?;
+
+        let mut graph = Self::load_graph(campaign_id)?;
+        if graph.edges.iter().any(|e| e.from == req.from && e.to == req.to && e.relationship_type == req.relationship_type) {
+            return Err(DmAssistantError::validation("This relationship already exists"));
+        }
+
+        let edge = RelationshipEdge::new(req);
+        graph.edges.push(edge.clone());
+        graph.updated_at = chrono::Utc::now();
+        Self::save_graph(&graph)?;
+        Self::record_change(campaign_id, &edge.id, None, Some(&edge))?;
+
+        Ok(edge)
+    }
+
+    /// Find an edge that can be edited from the graph
+    fn get_editable_edge<'a>(graph: &'a mut RelationshipGraph, edge_id: &Uuid) -> DmResult<&'a mut RelationshipEdge> {
+        let edge = graph.edges.iter_mut().find(|e| e.id == *edge_id)
+            .ok_or_else(|| DmAssistantError::not_found("Relationship", &edge_id.to_string()))?;
+        if edge.from_character {
+            return Err(DmAssistantError::validation("This relationship belongs to a character, edit it from the character"));
+        }
+        Ok(edge)
+    }
+
+    /// Update an edge
+    pub fn update_edge(campaign_id: &Uuid, edge_id: &Uuid, req: UpdateEdgeRequest) -> DmResult<RelationshipEdge> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let mut graph = Self::load_graph(campaign_id)?;
+
+        let edge = Self::get_editable_edge(&mut graph, edge_id)?;
+        let before = edge.clone();
+        edge.update(req);
+        let result = edge.clone();
+
+        graph.updated_at = chrono::Utc::now();
+        Self::save_graph(&graph)?;
+        Self::record_change(campaign_id, edge_id, Some(&before), Some(&result))?;
+        Ok(result)
+    }
+
+    /// Remove an edge
+    pub fn remove_edge(campaign_id: &Uuid, edge_id: &Uuid) -> DmResult<()> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let mut graph = Self::load_graph(campaign_id)?;
+
+        let removed = Self::get_editable_edge(&mut graph, edge_id)?.clone();
+        graph.edges.retain(|e| e.id != *edge_id);
+
+        graph.updated_at = chrono::Utc::now();
+        Self::save_graph(&graph)?;
+        Self::record_change(campaign_id, edge_id, Some(&removed), None)
+    }
+
+    /// Remove the edges touching a node (when the entity is deleted),
+    /// returning how many were removed. Edges mirrored from character
+    /// relationships are removed with the relationships, so aren't counted.
+    pub fn remove_node(campaign_id: &Uuid, node: &GraphNode) -> DmResult<usize> {
+        let _lock = lock_campaign(campaign_id)?;
+        let mut graph = Self::load_graph(campaign_id)?;
+        let removed = graph.remove_node(node);
+        if !removed.is_empty() {
+            Self::save_graph(&graph)?;
+        }
+        for edge in &removed {
+            Self::record_change(campaign_id, &edge.id, Some(edge), None)?;
+        }
+        Ok(removed.len())
+    }
+
+    /// Record a change to an edge in the campaign's history, and send its event
+    fn record_change(
+        campaign_id: &Uuid,
+        edge_id: &Uuid,
+        before: Option<&RelationshipEdge>,
+        after: Option<&RelationshipEdge>,
+    ) -> DmResult<()> {
+        let before = before.map(serde_json::to_value).transpose()?;
+        let after = after.map(serde_json::to_value).transpose()?;
+        if let Some(change) = EntityChange::between(EDGE_COLLECTION, *edge_id, before, after) {
+            EventService::entity_changed(campaign_id, &change);
+            HistoryService::record(campaign_id, &change.describe("Relationship", None), change);
+        }
+        Ok(())
+    }
+
+    /// Every node of the campaign with its label: characters, NPCs, factions
+    /// of NPCs and any other node appearing in the graph
+    pub fn labeled_nodes(campaign_id: &Uuid, graph: &RelationshipGraph) -> DmResult<Vec<LabeledNode>> {
+        let mut labels: BTreeMap<GraphNode, String> = BTreeMap::new();
+
+        for character in CharacterStorageService::get_characters_by_campaign(campaign_id)? {
+            labels.insert(GraphNode::Character(character.id), character.name);
+        }
+        for npc in NpcStorageService::get_npcs_by_campaign(campaign_id)? {
+            if let Some(faction) = &npc.faction {
+                labels.insert(GraphNode::Faction(faction.clone()), faction.clone());
+            }
+            labels.insert(GraphNode::Npc(npc.id), npc.name);
+        }
+        for edge in &graph.edges {
+            for node in [&edge.from, &edge.to] {
+                labels.entry(node.clone()).or_insert_with(|| match node {
+                    GraphNode::Faction(name) => name.clone(),
+                    _ => format!("? {}", dot_id(node)), // Entity no longer exists
+                });
+            }
+        }
+
+        Ok(labels.into_iter().map(|(node, label)| LabeledNode { node, label }).collect())
+    }
+
+    /// Export the graph for visualization
+    pub fn export_json(campaign_id: &Uuid) -> DmResult<GraphExport> {
+        let graph = Self::load_graph(campaign_id)?;
+        let nodes = Self::labeled_nodes(campaign_id, &graph)?;
+        Ok(GraphExport { campaign_id: *campaign_id, nodes, edges: graph.edges })
+    }
+
+    /// Export the graph in Graphviz DOT format
+    pub fn export_dot(campaign_id: &Uuid) -> DmResult<String> {
+        let graph = Self::load_graph(campaign_id)?;
+        let nodes = Self::labeled_nodes(campaign_id, &graph)?;
+        Ok(graph.to_dot(&nodes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::character::{CreateCharacterRequest, RelationshipType, UpdateRelationshipRequest};
+    use crate::core::models::npc::{CreateNpcRequest, Npc};
+    use crate::utils::test_support::create_test_campaign;
+
+    fn edge(from: GraphNode, to: GraphNode, mutual: bool) -> RelationshipEdge {
+        RelationshipEdge::new(CreateEdgeRequest {
+            from,
+            to,
+            relationship_type: RelationshipType::Ally,
+            mutual: Some(mutual),
+            notes: None,
+        })
+    }
+
+    fn npc(campaign_id: Uuid, name: &str, faction: Option<&str>) -> Npc {
+        NpcStorageService::create_npc(Npc::new(CreateNpcRequest {
+            campaign_id,
+            name: name.to_string(),
+            race: None,
+            role: None,
+            location_id: None,
+            faction: faction.map(|f| f.to_string()),
+            attitude: None,
+            voice: None,
+            mannerisms: None,
+            secrets: None,
+            portrait_path: None,
+            notes: None,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_neighborhood_and_path() {
+        let a = GraphNode::Npc(Uuid::new_v4());
+        let b = GraphNode::Npc(Uuid::new_v4());
+        let c = GraphNode::Faction("Harpers".to_string());
+        let d = GraphNode::Character(Uuid::new_v4());
+
+        let mut graph = RelationshipGraph::new(Uuid::new_v4());
+        graph.edges.push(edge(a.clone(), b.clone(), false));
+        graph.edges.push(edge(b.clone(), c.clone(), true));
+        graph.edges.push(edge(d.clone(), c.clone(), false));
+
+        let around_a = graph.neighborhood(&a, 1, EdgeDirection::Both);
+        assert_eq!(around_a.nodes, vec![b.clone()]);
+        let around_a = graph.neighborhood(&a, 3, EdgeDirection::Both);
+        assert_eq!(around_a.nodes, vec![b.clone(), c.clone(), d.clone()]);
+        assert_eq!(around_a.edges.len(), 3);
+
+        // Directed: b is only reached from a, the mutual edge works both ways
+        assert!(graph.neighborhood(&b, 1, EdgeDirection::Outgoing).nodes == vec![c.clone()]);
+        assert!(graph.neighborhood(&c, 1, EdgeDirection::Outgoing).nodes == vec![b.clone()]);
+        assert!(graph.neighborhood(&c, 1, EdgeDirection::Incoming).nodes.contains(&d));
+
+        let path = graph.find_path(&a, &d, EdgeDirection::Both).unwrap();
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[0].from, a);
+        assert_eq!(path[2].from, d);
+        assert!(graph.find_path(&a, &d, EdgeDirection::Outgoing).is_none());
+        assert_eq!(graph.find_path(&a, &c, EdgeDirection::Outgoing).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_character_relationships_are_mirrored() {
+        let campaign_id = create_test_campaign();
+        let npc = npc(campaign_id, "Toblen", None);
+
+        let mut character = PlayerCharacter::new(CreateCharacterRequest {
+            campaign_id,
+            name: "Kael".to_string(),
+            race: "Tiefling".to_string(),
+            class: "Warlock".to_string(),
+            level: 1,
+            max_hp: 9,
+            background: String::new(),
+            notes: None,
+        });
+        character.update_relationship(UpdateRelationshipRequest {
+            character_id: character.id,
+            npc_id: npc.id,
+            relationship_type: RelationshipType::Suspicious,
+            notes: None,
+        });
+        let character = CharacterStorageService::create_character(character).unwrap();
+        let relationship_id = character.relationships[0].id;
+
+        let graph = RelationshipGraphService::load_graph(&campaign_id).unwrap();
+        let mirrored = graph.get_edge(&relationship_id).unwrap();
+        assert!(mirrored.from_character);
+        assert_eq!(mirrored.from, GraphNode::Character(character.id));
+        assert_eq!(mirrored.to, GraphNode::Npc(npc.id));
+
+        // Mirrored edges are edited from the character
+        assert!(RelationshipGraphService::remove_edge(&campaign_id, &relationship_id).is_err());
+        CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+            c.relationships[0].relationship_type = RelationshipType::Friendly;
+            Ok(())
+        }).unwrap();
+        let graph = RelationshipGraphService::load_graph(&campaign_id).unwrap();
+        assert_eq!(graph.get_edge(&relationship_id).unwrap().relationship_type, RelationshipType::Friendly);
+
+        // Removing the NPC node leaves the mirrored edge to its relationship
+        assert_eq!(RelationshipGraphService::remove_node(&campaign_id, &GraphNode::Npc(npc.id)).unwrap(), 0);
+        assert!(RelationshipGraphService::load_graph(&campaign_id).unwrap().get_edge(&relationship_id).is_some());
+
+        CharacterStorageService::modify_character(&campaign_id, &character.id, |c| {
+            c.relationships.clear();
+            Ok(())
+        }).unwrap();
+        assert!(RelationshipGraphService::load_graph(&campaign_id).unwrap().edges.is_empty());
+    }
+
+    #[test]
+    fn test_edges_and_exports() {
+        let campaign_id = create_test_campaign();
+        let spy = npc(campaign_id, "Iarno \"Glasstaff\"", Some("Redbrands"));
+        let boss = npc(campaign_id, "Nezznar", None);
+
+        let edge = RelationshipGraphService::add_edge(&campaign_id, CreateEdgeRequest {
+            from: GraphNode::Npc(spy.id),
+            to: GraphNode::Npc(boss.id),
+            relationship_type: RelationshipType::Feared,
+            mutual: None,
+            notes: None,
+        }).unwrap();
+        assert!(RelationshipGraphService::add_edge(&campaign_id, CreateEdgeRequest {
+            from: GraphNode::Npc(spy.id),
+            to: GraphNode::Npc(Uuid::new_v4()),
+            relationship_type: RelationshipType::Ally,
+            mutual: None,
+            notes: None,
+        }).is_err());
+
+        let export = RelationshipGraphService::export_json(&campaign_id).unwrap();
+        assert_eq!(export.edges.len(), 1);
+        assert!(export.nodes.iter().any(|n| n.node == GraphNode::Faction("Redbrands".to_string())));
+
+        let dot = RelationshipGraphService::export_dot(&campaign_id).unwrap();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains(&format!("\"npc:{}\" -> \"npc:{}\" [label=\"Temuto\"];", spy.id, boss.id)));
+        assert!(dot.contains("Iarno \\\"Glasstaff\\\""));
+
+        // Deleting the NPC cleans up its edges
+        NpcStorageService::delete_npc(&campaign_id, &boss.id, true).unwrap();
+        let graph = RelationshipGraphService::load_graph(&campaign_id).unwrap();
+        assert!(graph.get_edge(&edge.id).is_none());
+    }
+}
@@ -0,0 +1,78 @@
+// src-tauri/src/services/spell_storage.rs
+
+use crate::core::models::common::EntityKind;
+use crate::core::models::spell::{Spell, SpellDetails, UpdateSpellRequest};
+use crate::services::storage::{CampaignRepository, Entity, EntityCollection, Query, Repository};
+use crate::utils::error::{validate_non_empty, validate_range, DmResult};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Spell collection for a campaign
+pub type SpellCollection = EntityCollection<Spell>;
+
+impl Entity for Spell {
+    const KIND: EntityKind = EntityKind::Spell;
+    const COLLECTION: &'static str = "spells";
+    const LABEL: &'static str = "Spell";
+
+    fn id(&self) -> Uuid { self.id }
+    fn campaign_id(&self) -> Uuid { self.campaign_id }
+    fn created_at(&self) -> DateTime<Utc> { self.created_at }
+    fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+    fn touch(&mut self) { self.updated_at = Utc::now(); }
+}
+
+static REPOSITORY: CampaignRepository<Spell> = CampaignRepository::new();
+
+/// File-based spell storage service: the homebrew spells of each campaign
+pub struct SpellStorageService;
+
+impl SpellStorageService {
+    /// Repository holding the spells
+    pub fn repository() -> &'static dyn Repository<Spell> {
+        &REPOSITORY
+    }
+
+    /// Create a new spell
+    pub fn create_spell(spell: Spell) -> DmResult<Spell> {
+        Self::validate_details(&spell.details)?;
+        Self::repository().create(spell)
+    }
+
+    /// Get spell by ID
+    pub fn get_spell(campaign_id: &Uuid, spell_id: &Uuid) -> DmResult<Option<Spell>> {
+        Self::repository().get(campaign_id, spell_id)
+    }
+
+    /// Get all spells for campaign, sorted by level then name
+    pub fn get_spells_by_campaign(campaign_id: &Uuid) -> DmResult<Vec<Spell>> {
+        Self::repository().query(
+            campaign_id,
+            &Query::new().sort_by(|a: &Spell, b: &Spell| {
+                a.details.level.cmp(&b.details.level).then_with(|| a.details.name.cmp(&b.details.name))
+            }),
+        )
+    }
+
+    /// Update spell data
+    pub fn update_spell(campaign_id: &Uuid, spell_id: &Uuid, req: UpdateSpellRequest) -> DmResult<Spell> {
+        Self::repository().modify(campaign_id, spell_id, Box::new(|spell| {
+            spell.update(req);
+            Self::validate_details(&spell.details)
+        }))
+    }
+
+    /// Delete spell
+    pub fn delete_spell(campaign_id: &Uuid, spell_id: &Uuid) -> DmResult<bool> {
+        Self::repository().delete(campaign_id, spell_id)
+    }
+
+    pub fn validate_details(details: &SpellDetails) -> DmResult<()> {
+        validate_non_empty(&details.name, "Spell name")?;
+        validate_range(details.level, 0, 9, "Spell level")?;
+        validate_non_empty(&details.casting_time, "Casting time")?;
+        validate_non_empty(&details.range, "Range")?;
+        validate_non_empty(&details.duration, "Duration")?;
+        Ok(())
+    }
+}
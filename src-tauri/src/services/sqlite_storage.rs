@@ -0,0 +1,392 @@
+// src-tauri/src/services/sqlite_storage.rs
+
+use crate::core::models::common::SCHEMA_VERSION;
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::services::entity_index::EntityIndexService;
+use crate::services::migrations::{self, SCHEMA_VERSION_KEY};
+use crate::services::storage::{Entity, EntityCollection, Modifier, Repository, SQLITE_FILE};
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use serde_json::{Map, Value};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Tables of a campaign database. Entities are stored as JSON documents, one
+/// row each, so every model is stored losslessly without a table per type.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS collections (
+        name TEXT PRIMARY KEY,
+        campaign_id TEXT NOT NULL,
+        schema_version INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS entities (
+        collection TEXT NOT NULL,
+        id TEXT NOT NULL,
+        data TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (collection, id)
+    );
+";
+
+/// Path of a campaign database
+pub fn database_path(campaign_id: &Uuid) -> DmResult<PathBuf> {
+    Ok(CampaignStorageService::get_campaign_dir_path(campaign_id)?.join(SQLITE_FILE))
+}
+
+/// Open a campaign database for writing, creating it and its tables if needed
+fn open(campaign_id: &Uuid) -> DmResult<Connection> {
+    let conn = Connection::open(database_path(campaign_id)?)?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// Open a campaign database for reading. None if nothing was written to it
+/// yet: the tables are only created by writes.
+fn open_existing(campaign_id: &Uuid) -> DmResult<Option<Connection>> {
+    let path = database_path(campaign_id)?;
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    Ok(Some(conn))
+}
+
+/// IDs stored in every collection of a database, without loading the entities
+pub fn list_entity_ids(database: &Path) -> DmResult<Vec<(String, Uuid)>> {
+    let conn = Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut statement = conn.prepare("SELECT collection, id FROM entities")?;
+    let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+    let mut ids = Vec::new();
+    for row in rows {
+        let (collection, id) = row?;
+        if let Ok(id) = Uuid::parse_str(&id) {
+            ids.push((collection, id));
+        }
+    }
+    Ok(ids)
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn parse_time(text: &str) -> DmResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| DmAssistantError::storage(&format!("Invalid timestamp '{}' in database: {}", text, e)))
+}
+
+/// Collection metadata row
+struct CollectionRow {
+    schema_version: u32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Repository keeping a campaign's entities in `campaign.db`.
+///
+/// Like `JsonRepository`, writes hold the campaign lock, are refused on
+/// archived campaigns and keep the entity index up to date. A collection
+/// written with an older schema is read through the migration registry and
+/// rewritten in the current schema by the first write.
+pub struct SqliteRepository<T: Entity> {
+    _entity: PhantomData<fn() -> T>,
+}
+
+impl<T: Entity> SqliteRepository<T> {
+    pub const fn new() -> Self {
+        Self { _entity: PhantomData }
+    }
+
+    fn collection_row(conn: &Connection) -> DmResult<Option<CollectionRow>> {
+        let row = conn.query_row(
+            "SELECT schema_version, created_at, updated_at FROM collections WHERE name = ?1",
+            params![T::COLLECTION],
+            |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+        ).optional()?;
+
+        match row {
+            Some((schema_version, created_at, updated_at)) => Ok(Some(CollectionRow {
+                schema_version,
+                created_at: parse_time(&created_at)?,
+                updated_at: parse_time(&updated_at)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Check if the collection can be read row by row (no migration needed)
+    fn is_current(conn: &Connection) -> DmResult<bool> {
+        Ok(Self::collection_row(conn)?.is_none_or(|row| row.schema_version == SCHEMA_VERSION))
+    }
+
+    /// Record that the collection changed, creating its metadata row if needed
+    fn touch_collection(conn: &Connection, campaign_id: &Uuid) -> DmResult<()> {
+        let now = format_time(&Utc::now());
+        conn.execute(
+            "INSERT INTO collections (name, campaign_id, schema_version, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(name) DO UPDATE SET updated_at = excluded.updated_at",
+            params![T::COLLECTION, campaign_id.to_string(), SCHEMA_VERSION, now],
+        )?;
+        Ok(())
+    }
+
+    fn decode(data: &str) -> DmResult<T> {
+        serde_json::from_str(data)
+            .map_err(|e| DmAssistantError::json(&format!("Failed to deserialize {}: {}", T::LABEL, e)))
+    }
+
+    fn encode(entity: &T) -> DmResult<String> {
+        serde_json::to_string(entity)
+            .map_err(|e| DmAssistantError::json(&format!("Failed to serialize {}: {}", T::LABEL, e)))
+    }
+
+    fn select(conn: &Connection, id: &Uuid) -> DmResult<Option<T>> {
+        let data: Option<String> = conn.query_row(
+            "SELECT data FROM entities WHERE collection = ?1 AND id = ?2",
+            params![T::COLLECTION, id.to_string()],
+            |row| row.get(0),
+        ).optional()?;
+        data.map(|data| Self::decode(&data)).transpose()
+    }
+
+    fn write(conn: &Connection, entity: &T) -> DmResult<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO entities (collection, id, data, updated_at) VALUES (?1, ?2, ?3, ?4)",
+            params![T::COLLECTION, entity.id().to_string(), Self::encode(entity)?, format_time(&entity.updated_at())],
+        )?;
+        Ok(())
+    }
+
+    /// Open the database for a write, upgrading an outdated collection first
+    fn open_for_write(campaign_id: &Uuid) -> DmResult<Connection> {
+        CampaignStorageService::ensure_writable(campaign_id)?;
+        let mut conn = open(campaign_id)?;
+        if !Self::is_current(&conn)? {
+            let collection = Self::read_collection(&conn, campaign_id)?;
+            Self::replace_collection(&mut conn, &collection)?;
+        }
+        Ok(conn)
+    }
+
+    /// Read the whole collection, through the migration registry
+    fn read_collection(conn: &Connection, campaign_id: &Uuid) -> DmResult<EntityCollection<T>> {
+        let Some(row) = Self::collection_row(conn)? else {
+            return Ok(EntityCollection::new(*campaign_id));
+        };
+
+        let mut items = Map::new();
+        let mut statement = conn.prepare("SELECT id, data FROM entities WHERE collection = ?1")?;
+        let rows = statement.query_map(params![T::COLLECTION], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (id, data) = row?;
+            items.insert(id, serde_json::from_str(&data)?);
+        }
+
+        // Same document as the JSON backend's file, so the same migrations apply
+        let mut document = Map::new();
+        document.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(row.schema_version));
+        document.insert("campaign_id".to_string(), Value::from(campaign_id.to_string()));
+        document.insert(T::COLLECTION.to_string(), Value::Object(items));
+        document.insert("created_at".to_string(), Value::from(format_time(&row.created_at)));
+        document.insert("updated_at".to_string(), Value::from(format_time(&row.updated_at)));
+        let mut document = Value::Object(document);
+        migrations::upgrade(&format!("{}.json", T::COLLECTION), &mut document)?;
+
+        serde_json::from_value(document)
+            .map_err(|e| DmAssistantError::json(&format!("Failed to deserialize {} collection: {}", T::LABEL, e)))
+    }
+
+    /// Replace the whole collection in a single transaction
+    fn replace_collection(conn: &mut Connection, collection: &EntityCollection<T>) -> DmResult<()> {
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM entities WHERE collection = ?1", params![T::COLLECTION])?;
+        tx.execute(
+            "INSERT OR REPLACE INTO collections (name, campaign_id, schema_version, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                T::COLLECTION,
+                collection.campaign_id.to_string(),
+                collection.schema_version,
+                format_time(&collection.created_at),
+                format_time(&collection.updated_at),
+            ],
+        )?;
+        for entity in collection.values() {
+            Self::write(&tx, entity)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl<T: Entity> Default for SqliteRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Entity> Repository<T> for SqliteRepository<T> {
+    fn create(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        let conn = Self::open_for_write(&campaign_id)?;
+
+        if Self::select(&conn, &entity.id())?.is_some() {
+            return Err(DmAssistantError::validation(&format!("{} with ID {} already exists", T::LABEL, entity.id())));
+        }
+
+        Self::write(&conn, &entity)?;
+        Self::touch_collection(&conn, &campaign_id)?;
+        EntityIndexService::register(&entity.id(), &campaign_id, T::KIND)?;
+
+        Ok(entity)
+    }
+
+    fn get(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<Option<T>> {
+        let Some(conn) = open_existing(campaign_id)? else { return Ok(None) };
+        if Self::is_current(&conn)? {
+            Self::select(&conn, id)
+        } else {
+            Ok(Self::read_collection(&conn, campaign_id)?.get(id).cloned())
+        }
+    }
+
+    fn list(&self, campaign_id: &Uuid) -> DmResult<Vec<T>> {
+        let Some(conn) = open_existing(campaign_id)? else { return Ok(Vec::new()) };
+        if !Self::is_current(&conn)? {
+            return Ok(Self::read_collection(&conn, campaign_id)?.items.into_values().collect());
+        }
+
+        let mut statement = conn.prepare("SELECT data FROM entities WHERE collection = ?1")?;
+        let rows = statement.query_map(params![T::COLLECTION], |row| row.get::<_, String>(0))?;
+        let mut entities = Vec::new();
+        for data in rows {
+            entities.push(Self::decode(&data?)?);
+        }
+        Ok(entities)
+    }
+
+    fn update(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        let conn = Self::open_for_write(&campaign_id)?;
+
+        if Self::select(&conn, &entity.id())?.is_none() {
+            return Err(DmAssistantError::not_found(T::LABEL, &entity.id().to_string()));
+        }
+
+        Self::write(&conn, &entity)?;
+        Self::touch_collection(&conn, &campaign_id)?;
+        Ok(entity)
+    }
+
+    fn delete(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool> {
+        let _lock = lock_campaign(campaign_id)?;
+        let conn = Self::open_for_write(campaign_id)?;
+
+        let deleted = conn.execute(
+            "DELETE FROM entities WHERE collection = ?1 AND id = ?2",
+            params![T::COLLECTION, id.to_string()],
+        )? > 0;
+        if !deleted {
+            return Ok(false);
+        }
+
+        Self::touch_collection(&conn, campaign_id)?;
+        EntityIndexService::unregister(id)?;
+        Ok(true)
+    }
+
+    fn modify<'a>(
+        &self,
+        campaign_id: &Uuid,
+        id: &Uuid,
+        modifier: Modifier<'a, T>,
+    ) -> DmResult<T> {
+        let _lock = lock_campaign(campaign_id)?;
+        let conn = Self::open_for_write(campaign_id)?;
+
+        let mut entity = Self::select(&conn, id)?
+            .ok_or_else(|| DmAssistantError::not_found(T::LABEL, &id.to_string()))?;
+        modifier(&mut entity)?;
+        entity.touch();
+
+        Self::write(&conn, &entity)?;
+        Self::touch_collection(&conn, campaign_id)?;
+        Ok(entity)
+    }
+
+    fn load_collection(&self, campaign_id: &Uuid) -> DmResult<EntityCollection<T>> {
+        match open_existing(campaign_id)? {
+            Some(conn) => Self::read_collection(&conn, campaign_id),
+            None => Ok(EntityCollection::new(*campaign_id)),
+        }
+    }
+
+    fn save_collection(&self, collection: &EntityCollection<T>) -> DmResult<()> {
+        let _lock = lock_campaign(&collection.campaign_id)?;
+        CampaignStorageService::ensure_writable(&collection.campaign_id)?;
+        Self::replace_collection(&mut open(&collection.campaign_id)?, collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::storage::contract::{self, rumor, Rumor, RumorCampaign};
+    use crate::utils::test_support::create_test_campaign;
+
+    #[test]
+    fn test_sqlite_repository_contract() {
+        contract::check_crud(&SqliteRepository::<Rumor>::new());
+        contract::check_query(&SqliteRepository::<Rumor>::new());
+        contract::check_collection_round_trip(&SqliteRepository::<Rumor>::new());
+        contract::check_archived_campaigns_are_read_only(&SqliteRepository::<Rumor>::new());
+    }
+
+    #[test]
+    fn test_reads_do_not_create_the_database() {
+        let campaign_id = create_test_campaign();
+        let repository = SqliteRepository::<Rumor>::new();
+        assert!(repository.list(&campaign_id).unwrap().is_empty());
+        assert_eq!(repository.get(&campaign_id, &Uuid::new_v4()).unwrap(), None);
+        assert!(repository.load_collection(&campaign_id).unwrap().is_empty());
+        assert!(!database_path(&campaign_id).unwrap().exists());
+    }
+
+    #[test]
+    fn test_outdated_collection_is_upgraded_by_first_write() {
+        let campaign = RumorCampaign::new();
+        let campaign_id = campaign.0;
+        let repository = SqliteRepository::<Rumor>::new();
+        let entry = repository.create(rumor(campaign_id, "The bridge is out", 1)).unwrap();
+
+        // Collection written before schema versioning
+        let conn = open(&campaign_id).unwrap();
+        conn.execute("UPDATE collections SET schema_version = 0", []).unwrap();
+        assert_eq!(repository.get(&campaign_id, &entry.id).unwrap(), Some(entry.clone()));
+        assert_eq!(repository.load_collection(&campaign_id).unwrap().schema_version, SCHEMA_VERSION);
+
+        repository.create(rumor(campaign_id, "The ferry still runs", 2)).unwrap();
+        let version: u32 = conn.query_row("SELECT schema_version FROM collections", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        assert_eq!(repository.list(&campaign_id).unwrap().len(), 2);
+
+        let ids = list_entity_ids(&database_path(&campaign_id).unwrap()).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.iter().all(|(collection, _)| collection == "rumors"));
+    }
+}
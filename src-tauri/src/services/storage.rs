@@ -0,0 +1,987 @@
+// src-tauri/src/services/storage.rs
+
+use crate::core::models::common::{EntityKind, SCHEMA_VERSION};
+use crate::services::campaign_storage::CampaignStorageService;
+use crate::core::models::history::EntityChange;
+use crate::services::entity_index::EntityIndexService;
+use crate::services::events::EventService;
+use crate::services::history::HistoryService;
+use crate::services::migrations::load_versioned;
+#[cfg(feature = "sqlite")]
+use crate::services::sqlite_storage::SqliteRepository;
+use crate::utils::campaign_lock::lock_campaign;
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{save_json, file_exists};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::Any;
+use std::cmp::Ordering as SortOrdering;
+use std::collections::HashMap;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Cached content of a file, with what the file looked like when it was read
+struct CacheEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Cache statistics
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStats {
+    pub cached_files: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// In-memory cache of campaign files.
+///
+/// Reads are served from memory as long as the file on disk has the same
+/// modification time and size as when it was read, so changes made outside
+/// the app are picked up on the next read. Writes go through to disk
+/// immediately and update the cache.
+///
+/// The storage services and commands use the shared instance from
+/// `CampaignStore::global()`.
+pub struct CampaignStore {
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CampaignStore {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Shared store used by the storage services
+    pub fn global() -> Arc<CampaignStore> {
+        static STORE: OnceLock<Arc<CampaignStore>> = OnceLock::new();
+        STORE.get_or_init(|| Arc::new(CampaignStore::new())).clone()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Modification time and size of a file, if it exists
+    fn file_version(file_path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(file_path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// Load a file, from memory if it hasn't changed on disk.
+    /// Returns None if the file doesn't exist.
+    pub fn load<T>(&self, file_path: &Path) -> DmResult<Option<T>>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let Some((modified, len)) = Self::file_version(file_path) else {
+            self.entries().remove(file_path);
+            return Ok(None);
+        };
+
+        if let Some(entry) = self.entries().get(file_path) {
+            if entry.modified == modified && entry.len == len {
+                if let Some(value) = entry.value.downcast_ref::<T>() {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(Some(value.clone()));
+                }
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value: T = load_versioned(file_path)?;
+        self.entries().insert(file_path.to_path_buf(), CacheEntry {
+            value: Arc::new(value.clone()),
+            modified,
+            len,
+        });
+        Ok(Some(value))
+    }
+
+    /// Load a file, or get a default value if it doesn't exist (nothing is written)
+    pub fn load_or_else<T, F>(&self, file_path: &Path, default: F) -> DmResult<T>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        Ok(self.load(file_path)?.unwrap_or_else(default))
+    }
+
+    /// Write a file to disk and cache it
+    pub fn save<T>(&self, data: &T, file_path: &Path) -> DmResult<()>
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+    {
+        if let Err(e) = save_json(data, file_path) {
+            self.entries().remove(file_path);
+            return Err(e);
+        }
+
+        match Self::file_version(file_path) {
+            Some((modified, len)) => {
+                self.entries().insert(file_path.to_path_buf(), CacheEntry {
+                    value: Arc::new(data.clone()),
+                    modified,
+                    len,
+                });
+            }
+            None => {
+                self.entries().remove(file_path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a file exists on disk
+    pub fn exists(&self, file_path: &Path) -> bool {
+        file_exists(file_path)
+    }
+
+    /// Forget a cached file
+    pub fn invalidate(&self, file_path: &Path) {
+        self.entries().remove(file_path);
+    }
+
+    /// Forget every cached file under a directory (e.g. a deleted campaign)
+    pub fn invalidate_dir(&self, dir_path: &Path) {
+        self.entries().retain(|path, _| !path.starts_with(dir_path));
+    }
+
+    /// Forget everything
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Cache statistics
+    pub fn stats(&self) -> StoreStats {
+        StoreStats {
+            cached_files: self.entries().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for CampaignStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Entity stored per campaign
+pub trait Entity: Clone + Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Kind recorded in the entity index
+    const KIND: EntityKind;
+    /// Collection name: entities live in `<collection>.json`, under the `<collection>` field
+    const COLLECTION: &'static str;
+    /// Name used in error messages
+    const LABEL: &'static str;
+
+    fn id(&self) -> Uuid;
+    fn campaign_id(&self) -> Uuid;
+    fn created_at(&self) -> DateTime<Utc>;
+    fn updated_at(&self) -> DateTime<Utc>;
+    /// Mark the entity as just updated
+    fn touch(&mut self);
+}
+
+/// Entities of one kind in a campaign, keyed by ID
+#[derive(Debug, Clone)]
+pub struct EntityCollection<T: Entity> {
+    pub schema_version: u32,
+    pub campaign_id: Uuid,
+    pub items: HashMap<Uuid, T>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl<T: Entity> EntityCollection<T> {
+    pub fn new(campaign_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            campaign_id,
+            items: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Insert or replace an entity
+    pub fn insert(&mut self, entity: T) {
+        self.items.insert(entity.id(), entity);
+        self.updated_at = Utc::now();
+    }
+
+    /// Replace an existing entity, returning false if it isn't there
+    pub fn replace(&mut self, entity: T) -> bool {
+        match self.items.get_mut(&entity.id()) {
+            Some(existing) => {
+                *existing = entity;
+                self.updated_at = Utc::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, id: &Uuid) -> Option<T> {
+        let removed = self.items.remove(id);
+        if removed.is_some() {
+            self.updated_at = Utc::now();
+        }
+        removed
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&T> {
+        self.items.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: &Uuid) -> Option<&mut T> {
+        self.items.get_mut(id)
+    }
+
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.items.contains_key(id)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.items.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+// Serialized with the entities under `T::COLLECTION`, so each kind keeps its
+// own file format (e.g. `{ "campaign_id": ..., "characters": { ... } }`)
+impl<T: Entity> Serialize for EntityCollection<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("EntityCollection", 5)?;
+        state.serialize_field("schema_version", &self.schema_version)?;
+        state.serialize_field("campaign_id", &self.campaign_id)?;
+        state.serialize_field(T::COLLECTION, &self.items)?;
+        state.serialize_field("created_at", &self.created_at)?;
+        state.serialize_field("updated_at", &self.updated_at)?;
+        state.end()
+    }
+}
+
+impl<'de, T: Entity> Deserialize<'de> for EntityCollection<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(default)]
+            schema_version: u32,
+            campaign_id: Uuid,
+            created_at: DateTime<Utc>,
+            updated_at: DateTime<Utc>,
+            #[serde(flatten)]
+            rest: serde_json::Map<String, serde_json::Value>,
+        }
+
+        let mut raw = Raw::deserialize(deserializer)?;
+        let items = match raw.rest.remove(T::COLLECTION) {
+            Some(value) => serde_json::from_value(value).map_err(serde::de::Error::custom)?,
+            None => return Err(serde::de::Error::missing_field(T::COLLECTION)),
+        };
+
+        Ok(Self {
+            schema_version: raw.schema_version,
+            campaign_id: raw.campaign_id,
+            items,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+type Filter<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+type Comparator<'a, T> = Box<dyn Fn(&T, &T) -> SortOrdering + 'a>;
+
+/// Change applied to an entity by `Repository::modify`
+pub type Modifier<'a, T> = Box<dyn FnOnce(&mut T) -> DmResult<()> + 'a>;
+
+/// Filter, sort and paging applied to a listing
+pub struct Query<'a, T> {
+    filter: Option<Filter<'a, T>>,
+    sort: Option<Comparator<'a, T>>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl<'a, T> Query<'a, T> {
+    pub fn new() -> Self {
+        Self { filter: None, sort: None, offset: 0, limit: None }
+    }
+
+    /// Keep only entities matching the predicate
+    pub fn filter(mut self, predicate: impl Fn(&T) -> bool + 'a) -> Self {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    /// Sort with a comparator
+    pub fn sort_by(mut self, compare: impl Fn(&T, &T) -> SortOrdering + 'a) -> Self {
+        self.sort = Some(Box::new(compare));
+        self
+    }
+
+    /// Sort by a key
+    pub fn sort_by_key<K: Ord>(self, key: impl Fn(&T) -> K + 'a) -> Self {
+        self.sort_by(move |a, b| key(a).cmp(&key(b)))
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Apply the query to a list of entities
+    pub fn apply(&self, entities: Vec<T>) -> Vec<T> {
+        let mut entities: Vec<T> = match &self.filter {
+            Some(filter) => entities.into_iter().filter(|e| filter(e)).collect(),
+            None => entities,
+        };
+        if let Some(sort) = &self.sort {
+            entities.sort_by(|a, b| sort(a, b));
+        }
+        let entities = entities.into_iter().skip(self.offset);
+        match self.limit {
+            Some(limit) => entities.take(limit).collect(),
+            None => entities.collect(),
+        }
+    }
+}
+
+impl<T> Default for Query<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// CRUD storage for the entities of a campaign
+pub trait Repository<T: Entity>: Send + Sync {
+    /// Store a new entity, failing if its ID is already taken
+    fn create(&self, entity: T) -> DmResult<T>;
+
+    fn get(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<Option<T>>;
+
+    /// Every entity of the campaign, in no particular order
+    fn list(&self, campaign_id: &Uuid) -> DmResult<Vec<T>>;
+
+    /// Replace an existing entity
+    fn update(&self, entity: T) -> DmResult<T>;
+
+    /// Delete an entity, returning false if it didn't exist
+    fn delete(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool>;
+
+    /// Load, change and save an entity as a single operation
+    fn modify<'a>(
+        &self,
+        campaign_id: &Uuid,
+        id: &Uuid,
+        modifier: Modifier<'a, T>,
+    ) -> DmResult<T>;
+
+    /// Entities matching a query
+    fn query(&self, campaign_id: &Uuid, query: &Query<T>) -> DmResult<Vec<T>> {
+        Ok(query.apply(self.list(campaign_id)?))
+    }
+
+    fn exists(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool> {
+        Ok(self.get(campaign_id, id)?.is_some())
+    }
+
+    /// Find an entity by ID alone, using the entity index. The campaign an
+    /// index entry points to is checked, never created.
+    fn find(&self, id: &Uuid) -> DmResult<Option<T>> {
+        let location = EntityIndexService::resolve(id, |location| {
+            Ok(location.kind == T::KIND
+                && CampaignStorageService::get_campaign_dir_path(&location.campaign_id)?.is_dir()
+                && self.exists(&location.campaign_id, id)?)
+        })?;
+
+        match location {
+            Some(location) => self.get(&location.campaign_id, id),
+            None => Ok(None),
+        }
+    }
+
+    /// Whole collection of the campaign, with its metadata
+    fn load_collection(&self, campaign_id: &Uuid) -> DmResult<EntityCollection<T>>;
+
+    /// Replace the whole collection. Used for bulk operations such as
+    /// converting between backends: the caller holds the campaign lock.
+    fn save_collection(&self, collection: &EntityCollection<T>) -> DmResult<()>;
+}
+
+/// Repository keeping each kind of entity in a JSON file of the campaign directory.
+///
+/// Writes hold the campaign lock, are refused on archived campaigns and keep
+/// the entity index up to date.
+pub struct JsonRepository<T: Entity> {
+    _entity: PhantomData<fn() -> T>,
+}
+
+impl<T: Entity> JsonRepository<T> {
+    pub const fn new() -> Self {
+        Self { _entity: PhantomData }
+    }
+
+    /// Path of the collection file
+    pub fn file_path(&self, campaign_id: &Uuid) -> DmResult<PathBuf> {
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        Ok(campaign_dir.join(format!("{}.json", T::COLLECTION)))
+    }
+
+    /// Load the collection; the file is only created by the first write
+    pub fn load(&self, campaign_id: &Uuid) -> DmResult<EntityCollection<T>> {
+        CampaignStore::global().load_or_else(&self.file_path(campaign_id)?, || EntityCollection::new(*campaign_id))
+    }
+
+    /// Save the collection
+    pub fn save(&self, collection: &EntityCollection<T>) -> DmResult<()> {
+        CampaignStore::global().save(collection, &self.file_path(&collection.campaign_id)?)
+    }
+
+    /// Check if the collection file exists
+    pub fn file_exists(&self, campaign_id: &Uuid) -> bool {
+        self.file_path(campaign_id).map(|path| file_exists(&path)).unwrap_or(false)
+    }
+}
+
+impl<T: Entity> Default for JsonRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Entity> Repository<T> for JsonRepository<T> {
+    fn create(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        CampaignStorageService::ensure_writable(&campaign_id)?;
+
+        let mut collection = self.load(&campaign_id)?;
+        if collection.contains(&entity.id()) {
+            return Err(DmAssistantError::validation(&format!("{} with ID {} already exists", T::LABEL, entity.id())));
+        }
+
+        collection.insert(entity.clone());
+        self.save(&collection)?;
+        EntityIndexService::register(&entity.id(), &campaign_id, T::KIND)?;
+
+        Ok(entity)
+    }
+
+    fn get(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<Option<T>> {
+        Ok(self.load(campaign_id)?.get(id).cloned())
+    }
+
+    fn list(&self, campaign_id: &Uuid) -> DmResult<Vec<T>> {
+        Ok(self.load(campaign_id)?.items.into_values().collect())
+    }
+
+    fn update(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        CampaignStorageService::ensure_writable(&campaign_id)?;
+
+        let mut collection = self.load(&campaign_id)?;
+        if !collection.replace(entity.clone()) {
+            return Err(DmAssistantError::not_found(T::LABEL, &entity.id().to_string()));
+        }
+
+        self.save(&collection)?;
+        Ok(entity)
+    }
+
+    fn delete(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+
+        let mut collection = self.load(campaign_id)?;
+        if collection.remove(id).is_none() {
+            return Ok(false);
+        }
+
+        self.save(&collection)?;
+        EntityIndexService::unregister(id)?;
+        Ok(true)
+    }
+
+    fn modify<'a>(
+        &self,
+        campaign_id: &Uuid,
+        id: &Uuid,
+        modifier: Modifier<'a, T>,
+    ) -> DmResult<T> {
+        let _lock = lock_campaign(campaign_id)?;
+        CampaignStorageService::ensure_writable(campaign_id)?;
+
+        let mut collection = self.load(campaign_id)?;
+        let entity = collection.get_mut(id)
+            .ok_or_else(|| DmAssistantError::not_found(T::LABEL, &id.to_string()))?;
+
+        modifier(entity)?;
+        entity.touch();
+
+        let result = entity.clone();
+        collection.updated_at = Utc::now();
+        self.save(&collection)?;
+
+        Ok(result)
+    }
+
+    fn load_collection(&self, campaign_id: &Uuid) -> DmResult<EntityCollection<T>> {
+        self.load(campaign_id)
+    }
+
+    fn save_collection(&self, collection: &EntityCollection<T>) -> DmResult<()> {
+        let _lock = lock_campaign(&collection.campaign_id)?;
+        CampaignStorageService::ensure_writable(&collection.campaign_id)?;
+        self.save(collection)
+    }
+}
+
+/// Database file of campaigns stored with the SQLite backend
+pub const SQLITE_FILE: &str = "campaign.db";
+
+/// How a campaign's entities are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageBackend {
+    Json,
+    Sqlite,
+}
+
+impl StorageBackend {
+    /// Backend a campaign is stored with: SQLite if it has a database file
+    pub fn of(campaign_id: &Uuid) -> DmResult<Self> {
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(campaign_id)?;
+        if file_exists(&campaign_dir.join(SQLITE_FILE)) {
+            Ok(Self::Sqlite)
+        } else {
+            Ok(Self::Json)
+        }
+    }
+}
+
+/// Outcome of converting a campaign to another backend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageConversion {
+    pub from: StorageBackend,
+    pub to: StorageBackend,
+    pub entities: usize,
+    pub backup_path: Option<String>, // Copy of the campaign before converting, if anything changed
+}
+
+/// Repository storing each campaign's entities with the campaign's backend.
+///
+/// Changes made through it are recorded in the campaign's history, so they
+/// can be undone; the backends themselves don't record anything.
+pub struct CampaignRepository<T: Entity> {
+    json: JsonRepository<T>,
+    #[cfg(feature = "sqlite")]
+    sqlite: SqliteRepository<T>,
+}
+
+impl<T: Entity> CampaignRepository<T> {
+    pub const fn new() -> Self {
+        Self {
+            json: JsonRepository::new(),
+            #[cfg(feature = "sqlite")]
+            sqlite: SqliteRepository::new(),
+        }
+    }
+
+    /// JSON repository, for operations on the files themselves
+    pub fn json(&self) -> &JsonRepository<T> {
+        &self.json
+    }
+
+    /// Repository of the backend a campaign uses
+    pub fn backend(&self, campaign_id: &Uuid) -> DmResult<&dyn Repository<T>> {
+        match StorageBackend::of(campaign_id)? {
+            StorageBackend::Json => Ok(&self.json),
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite => Ok(&self.sqlite),
+            #[cfg(not(feature = "sqlite"))]
+            StorageBackend::Sqlite => Err(DmAssistantError::storage(&format!(
+                "Campaign {} is stored in SQLite, which this build doesn't support", campaign_id
+            ))),
+        }
+    }
+}
+
+/// Record a change to an entity in the history of its campaign, and send its event
+fn record_change<T: Entity>(campaign_id: &Uuid, id: &Uuid, before: Option<&T>, after: Option<&T>) -> DmResult<()> {
+    let before = before.map(serde_json::to_value).transpose()?;
+    let after = after.map(serde_json::to_value).transpose()?;
+    let name = after.as_ref().or(before.as_ref())
+        .and_then(|state| state.get("name"))
+        .and_then(|name| name.as_str())
+        .map(str::to_string);
+    if let Some(change) = EntityChange::between(T::COLLECTION, *id, before, after) {
+        EventService::entity_changed(campaign_id, &change);
+        HistoryService::record(campaign_id, &change.describe(T::LABEL, name.as_deref()), change);
+    }
+    Ok(())
+}
+
+impl<T: Entity> Default for CampaignRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Entity> Repository<T> for CampaignRepository<T> {
+    fn create(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        let created = self.backend(&campaign_id)?.create(entity)?;
+        record_change(&campaign_id, &created.id(), None, Some(&created))?;
+        Ok(created)
+    }
+
+    fn get(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<Option<T>> {
+        self.backend(campaign_id)?.get(campaign_id, id)
+    }
+
+    fn list(&self, campaign_id: &Uuid) -> DmResult<Vec<T>> {
+        self.backend(campaign_id)?.list(campaign_id)
+    }
+
+    fn update(&self, entity: T) -> DmResult<T> {
+        let campaign_id = entity.campaign_id();
+        let _lock = lock_campaign(&campaign_id)?;
+        let backend = self.backend(&campaign_id)?;
+        let before = backend.get(&campaign_id, &entity.id())?;
+        let updated = backend.update(entity)?;
+        record_change(&campaign_id, &updated.id(), before.as_ref(), Some(&updated))?;
+        Ok(updated)
+    }
+
+    fn delete(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool> {
+        let _lock = lock_campaign(campaign_id)?;
+        let backend = self.backend(campaign_id)?;
+        let before = backend.get(campaign_id, id)?;
+        let deleted = backend.delete(campaign_id, id)?;
+        if let Some(before) = before.filter(|_| deleted) {
+            record_change(campaign_id, id, Some(&before), None)?;
+        }
+        Ok(deleted)
+    }
+
+    fn modify<'a>(
+        &self,
+        campaign_id: &Uuid,
+        id: &Uuid,
+        modifier: Modifier<'a, T>,
+    ) -> DmResult<T> {
+        let _lock = lock_campaign(campaign_id)?;
+        let backend = self.backend(campaign_id)?;
+        let before = backend.get(campaign_id, id)?;
+        let modified = backend.modify(campaign_id, id, modifier)?;
+        record_change(campaign_id, id, before.as_ref(), Some(&modified))?;
+        Ok(modified)
+    }
+
+    fn query(&self, campaign_id: &Uuid, query: &Query<T>) -> DmResult<Vec<T>> {
+        self.backend(campaign_id)?.query(campaign_id, query)
+    }
+
+    fn exists(&self, campaign_id: &Uuid, id: &Uuid) -> DmResult<bool> {
+        self.backend(campaign_id)?.exists(campaign_id, id)
+    }
+
+    fn load_collection(&self, campaign_id: &Uuid) -> DmResult<EntityCollection<T>> {
+        self.backend(campaign_id)?.load_collection(campaign_id)
+    }
+
+    fn save_collection(&self, collection: &EntityCollection<T>) -> DmResult<()> {
+        self.backend(&collection.campaign_id)?.save_collection(collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::contract::{rumor, Rumor};
+    use crate::utils::test_support::create_test_campaign;
+    use tempfile::tempdir;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Party {
+        members: Vec<String>,
+    }
+
+    fn party(members: &[&str]) -> Party {
+        Party { members: members.iter().map(|m| m.to_string()).collect() }
+    }
+
+    #[test]
+    fn test_reads_are_served_from_memory() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("party.json");
+        let store = CampaignStore::new();
+
+        assert_eq!(store.load::<Party>(&file_path).unwrap(), None);
+        assert!(!file_exists(&file_path));
+
+        store.save(&party(&["Aria"]), &file_path).unwrap();
+        assert_eq!(store.load::<Party>(&file_path).unwrap(), Some(party(&["Aria"])));
+        assert_eq!(store.load::<Party>(&file_path).unwrap(), Some(party(&["Aria"])));
+
+        let stats = store.stats();
+        assert_eq!(stats.cached_files, 1);
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 0);
+    }
+
+    #[test]
+    fn test_external_changes_invalidate_the_cache() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("party.json");
+        let store = CampaignStore::new();
+        store.save(&party(&["Aria"]), &file_path).unwrap();
+
+        // Another program rewrites the file
+        save_json(&party(&["Aria", "Borin"]), &file_path).unwrap();
+        let file = fs::File::options().write(true).open(&file_path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5)).unwrap();
+
+        assert_eq!(store.load::<Party>(&file_path).unwrap(), Some(party(&["Aria", "Borin"])));
+        assert_eq!(store.stats().misses, 1);
+
+        // Or deletes it
+        fs::remove_file(&file_path).unwrap();
+        assert_eq!(store.load::<Party>(&file_path).unwrap(), None);
+        assert_eq!(store.stats().cached_files, 0);
+    }
+
+    #[test]
+    fn test_invalidate_dir() {
+        let temp_dir = tempdir().unwrap();
+        let store = CampaignStore::new();
+        store.save(&party(&["Aria"]), &temp_dir.path().join("a").join("party.json")).unwrap();
+        store.save(&party(&["Borin"]), &temp_dir.path().join("b").join("party.json")).unwrap();
+
+        store.invalidate_dir(&temp_dir.path().join("a"));
+        assert_eq!(store.stats().cached_files, 1);
+    }
+
+    #[test]
+    fn test_collection_keeps_its_file_format() {
+        let campaign_id = Uuid::new_v4();
+        let mut collection = EntityCollection::new(campaign_id);
+        let entry = rumor(campaign_id, "The mayor is a doppelganger", 3);
+        collection.insert(entry.clone());
+
+        let value = serde_json::to_value(&collection).unwrap();
+        assert_eq!(value["campaign_id"], campaign_id.to_string());
+        assert_eq!(value["rumors"][entry.id.to_string()]["text"], "The mayor is a doppelganger");
+
+        let loaded: EntityCollection<Rumor> = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get(&entry.id).unwrap().heard_in_session, 3);
+
+        let missing = serde_json::json!({
+            "campaign_id": campaign_id,
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        });
+        assert!(serde_json::from_value::<EntityCollection<Rumor>>(missing).is_err());
+    }
+
+    #[test]
+    fn test_json_repository_contract() {
+        contract::check_crud(&JsonRepository::<Rumor>::new());
+        contract::check_query(&JsonRepository::<Rumor>::new());
+        contract::check_collection_round_trip(&JsonRepository::<Rumor>::new());
+        contract::check_archived_campaigns_are_read_only(&JsonRepository::<Rumor>::new());
+    }
+
+    #[test]
+    fn test_backend_follows_the_campaign_files() {
+        let campaign_id = create_test_campaign();
+        assert_eq!(StorageBackend::of(&campaign_id).unwrap(), StorageBackend::Json);
+
+        let campaign_dir = CampaignStorageService::get_campaign_dir_path(&campaign_id).unwrap();
+        fs::write(campaign_dir.join(SQLITE_FILE), b"").unwrap();
+        assert_eq!(StorageBackend::of(&campaign_id).unwrap(), StorageBackend::Sqlite);
+        #[cfg(not(feature = "sqlite"))]
+        assert!(CampaignRepository::<Rumor>::new().list(&campaign_id).is_err());
+    }
+}
+
+/// Behavior every `Repository` implementation must have
+#[cfg(test)]
+pub(crate) mod contract {
+    use super::*;
+    use crate::utils::test_support::create_test_campaign;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Rumor {
+        pub id: Uuid,
+        pub campaign_id: Uuid,
+        pub text: String,
+        pub heard_in_session: u32,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+
+    impl Entity for Rumor {
+        const KIND: EntityKind = EntityKind::Npc;
+        const COLLECTION: &'static str = "rumors";
+        const LABEL: &'static str = "Rumor";
+
+        fn id(&self) -> Uuid { self.id }
+        fn campaign_id(&self) -> Uuid { self.campaign_id }
+        fn created_at(&self) -> DateTime<Utc> { self.created_at }
+        fn updated_at(&self) -> DateTime<Utc> { self.updated_at }
+        fn touch(&mut self) { self.updated_at = Utc::now(); }
+    }
+
+    /// Campaign of a check. Rumors are indexed as NPCs, so they're removed
+    /// from the entity index when the check ends.
+    pub struct RumorCampaign(pub Uuid);
+
+    impl RumorCampaign {
+        pub fn new() -> Self {
+            Self(create_test_campaign())
+        }
+    }
+
+    impl Drop for RumorCampaign {
+        fn drop(&mut self) {
+            let _ = EntityIndexService::unregister_campaign(&self.0);
+        }
+    }
+
+    pub fn rumor(campaign_id: Uuid, text: &str, heard_in_session: u32) -> Rumor {
+        let now = Utc::now();
+        Rumor { id: Uuid::new_v4(), campaign_id, text: text.to_string(), heard_in_session, created_at: now, updated_at: now }
+    }
+
+    pub fn check_crud(repository: &dyn Repository<Rumor>) {
+        let campaign = RumorCampaign::new();
+        let campaign_id = campaign.0;
+        let entry = rumor(campaign_id, "A dragon sleeps under the hill", 1);
+        assert!(repository.list(&campaign_id).unwrap().is_empty());
+        assert_eq!(repository.get(&campaign_id, &entry.id).unwrap(), None);
+
+        assert_eq!(repository.create(entry.clone()).unwrap(), entry);
+        assert!(repository.create(entry.clone()).is_err());
+        assert_eq!(repository.get(&campaign_id, &entry.id).unwrap(), Some(entry.clone()));
+        assert!(repository.exists(&campaign_id, &entry.id).unwrap());
+
+        let modified = repository.modify(&campaign_id, &entry.id, Box::new(|r: &mut Rumor| {
+            r.heard_in_session = 2;
+            Ok(())
+        })).unwrap();
+        assert_eq!(modified.heard_in_session, 2);
+        assert!(modified.updated_at >= entry.updated_at);
+        assert_eq!(repository.get(&campaign_id, &entry.id).unwrap(), Some(modified.clone()));
+
+        // A failing modifier changes nothing
+        let failed = repository.modify(&campaign_id, &entry.id, Box::new(|r: &mut Rumor| {
+            r.heard_in_session = 99;
+            Err(DmAssistantError::validation("no"))
+        }));
+        assert!(failed.is_err());
+        assert_eq!(repository.get(&campaign_id, &entry.id).unwrap(), Some(modified.clone()));
+
+        let mut updated = modified.clone();
+        updated.text = "Two dragons sleep under the hill".to_string();
+        repository.update(updated.clone()).unwrap();
+        assert_eq!(repository.list(&campaign_id).unwrap(), vec![updated]);
+
+        // Entities are indexed
+        let location = EntityIndexService::lookup(&entry.id).unwrap().unwrap();
+        assert_eq!(location.campaign_id, campaign_id);
+
+        assert!(repository.delete(&campaign_id, &entry.id).unwrap());
+        assert!(!repository.delete(&campaign_id, &entry.id).unwrap());
+        assert!(repository.update(entry.clone()).is_err());
+        assert!(repository.modify(&campaign_id, &entry.id, Box::new(|_: &mut Rumor| Ok(()))).is_err());
+        assert_eq!(EntityIndexService::lookup(&entry.id).unwrap(), None);
+    }
+
+    pub fn check_query(repository: &dyn Repository<Rumor>) {
+        let campaign = RumorCampaign::new();
+        let campaign_id = campaign.0;
+        for (text, session) in [("Bandits on the road", 4), ("Cursed well", 1), ("Missing miller", 3), ("Haunted mill", 2)] {
+            repository.create(rumor(campaign_id, text, session)).unwrap();
+        }
+
+        let query = Query::new()
+            .filter(|r: &Rumor| r.heard_in_session > 1)
+            .sort_by_key(|r: &Rumor| r.heard_in_session)
+            .offset(1)
+            .limit(1);
+        let results = repository.query(&campaign_id, &query).unwrap();
+        let texts: Vec<&str> = results.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, vec!["Missing miller"]);
+
+        let all = repository.query(&campaign_id, &Query::new()).unwrap();
+        assert_eq!(all.len(), 4);
+
+        // Other campaigns are not visible
+        assert!(repository.list(&create_test_campaign()).unwrap().is_empty());
+    }
+
+    pub fn check_collection_round_trip(repository: &dyn Repository<Rumor>) {
+        let campaign = RumorCampaign::new();
+        let campaign_id = campaign.0;
+        let mut collection = EntityCollection::new(campaign_id);
+        collection.created_at = Utc::now() - chrono::Duration::days(30);
+        for text in ["Gold in the river", "The king is ill"] {
+            collection.insert(rumor(campaign_id, text, 5));
+        }
+
+        repository.save_collection(&collection).unwrap();
+        let loaded = repository.load_collection(&campaign_id).unwrap();
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&collection).unwrap());
+    }
+
+    pub fn check_archived_campaigns_are_read_only(repository: &dyn Repository<Rumor>) {
+        let campaign = RumorCampaign::new();
+        let campaign_id = campaign.0;
+        let entry = repository.create(rumor(campaign_id, "The tower is empty", 1)).unwrap();
+        CampaignStorageService::archive_campaign(&campaign_id).unwrap();
+
+        assert!(repository.create(rumor(campaign_id, "Too late", 2)).is_err());
+        assert!(repository.modify(&campaign_id, &entry.id, Box::new(|_: &mut Rumor| Ok(()))).is_err());
+        assert!(repository.delete(&campaign_id, &entry.id).is_err());
+        assert!(repository.save_collection(&EntityCollection::new(campaign_id)).is_err());
+        assert_eq!(repository.list(&campaign_id).unwrap(), vec![entry]);
+    }
+}
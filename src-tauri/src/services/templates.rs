@@ -0,0 +1,555 @@
+// src-tauri/src/services/templates.rs
+
+use crate::core::models::common::Ability;
+use crate::core::models::monster::{
+    ChallengeRating, Condition, CreatureType, DamageDefense, DamageType, HitPoints, Monster, MonsterAction,
+    MonsterFeature, MonsterTemplate, ScaleMonsterRequest, ScaledMonster, Size, StatBlock, TemplateChange,
+};
+use crate::core::rules::ability_scores::{hit_dice_average, modifier};
+use crate::core::rules::challenge_rating::{self, statistics};
+use crate::core::rules::dice::MAX_DICE;
+use crate::services::monster_storage::MonsterStorageService;
+use crate::utils::error::{DmAssistantError, DmResult};
+use chrono::Utc;
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// Changes made by the template being applied
+struct Changelog<'a> {
+    template: String,
+    changes: &'a mut Vec<TemplateChange>,
+}
+
+impl Changelog<'_> {
+    fn record(&mut self, field: &str, before: impl Display, after: impl Display) {
+        let (before, after) = (before.to_string(), after.to_string());
+        if before != after {
+            self.changes.push(TemplateChange { template: self.template.clone(), field: field.to_string(), before, after });
+        }
+    }
+}
+
+/// Dice expression of the form "NdS+M", the form used in stat blocks
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SimpleDice {
+    count: u32,
+    sides: u32,
+    bonus: i32,
+}
+
+impl SimpleDice {
+    fn parse(expression: &str) -> Option<Self> {
+        let compact: String = expression.chars().filter(|c| !c.is_whitespace()).collect();
+        let (count, rest) = compact.split_once(['d', 'D'])?;
+        let count = if count.is_empty() { 1 } else { count.parse().ok()? };
+        let split = rest.find(['+', '-']).unwrap_or(rest.len());
+        let sides = rest[..split].parse().ok()?;
+        let bonus = match &rest[split..] {
+            "" => 0,
+            bonus => bonus.trim_start_matches('+').parse().ok()?,
+        };
+        Some(SimpleDice { count, sides, bonus })
+    }
+
+    fn die_average(&self) -> f64 {
+        (self.sides as f64 + 1.0) / 2.0
+    }
+
+    fn average(&self) -> f64 {
+        self.count as f64 * self.die_average() + self.bonus as f64
+    }
+}
+
+impl std::fmt::Display for SimpleDice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bonus {
+            0 => write!(f, "{}d{}", self.count, self.sides),
+            bonus if bonus > 0 => write!(f, "{}d{}+{}", self.count, self.sides, bonus),
+            bonus => write!(f, "{}d{}{}", self.count, self.sides, bonus),
+        }
+    }
+}
+
+fn midpoint(min: u32, max: u32) -> f64 {
+    (min + max) as f64 / 2.0
+}
+
+fn signed(value: i32) -> String {
+    format!("{:+}", value)
+}
+
+fn all_actions(stat_block: &mut StatBlock) -> impl Iterator<Item = &mut MonsterAction> {
+    let legendary = stat_block.legendary_actions.iter_mut()
+        .flat_map(|legendary| legendary.actions.iter_mut().map(|action| &mut action.action));
+    stat_block.actions.iter_mut()
+        .chain(stat_block.bonus_actions.iter_mut())
+        .chain(stat_block.reactions.iter_mut())
+        .chain(legendary)
+}
+
+fn has_trait(stat_block: &StatBlock, prefix: &str) -> bool {
+    stat_block.traits.iter().any(|feature| feature.name.to_lowercase().starts_with(&prefix.to_lowercase()))
+}
+
+fn add_trait(stat_block: &mut StatBlock, name: &str, description: &str, log: &mut Changelog) {
+    stat_block.traits.push(MonsterFeature { name: name.to_string(), description: description.to_string(), usage: None });
+    log.record("Traits", "", name);
+}
+
+/// Scale the hit points, keeping the hit die and recomputing the number of
+/// dice with the Constitution modifier
+fn scale_hit_points(stat_block: &mut StatBlock, ratio: f64, log: &mut Changelog) -> DmResult<()> {
+    let before = stat_block.hit_points.clone();
+    let target = (before.average as f64 * ratio).round().max(1.0);
+    let hit_dice = before.formula.as_deref().and_then(SimpleDice::parse);
+
+    match hit_dice {
+        Some(dice) => {
+            let constitution = modifier(stat_block.ability_scores.constitution);
+            let per_die = (dice.die_average() + constitution as f64).max(1.0);
+            let count = (target / per_die).round().max(1.0);
+            if count > MAX_DICE as f64 {
+                return Err(DmAssistantError::invalid_input(&format!(
+                    "Scaled hit points of {} would need {} hit dice, more than the limit of {}", target, count, MAX_DICE)));
+            }
+            let count = count as u32;
+            let formula = SimpleDice { count, sides: dice.sides, bonus: count as i32 * constitution }.to_string();
+            stat_block.hit_points.average = hit_dice_average(&formula)?.max(1);
+            stat_block.hit_points.formula = Some(formula);
+        }
+        None => stat_block.hit_points.average = target as u32,
+    }
+    let format = |hit_points: &HitPoints| match &hit_points.formula {
+        Some(formula) => format!("{} ({})", hit_points.average, formula),
+        None => hit_points.average.to_string(),
+    };
+    log.record("Hit points", format(&before), format(&stat_block.hit_points));
+    Ok(())
+}
+
+fn shift_armor_class(stat_block: &mut StatBlock, delta: i32, log: &mut Changelog) {
+    let before = stat_block.armor_class.value;
+    stat_block.armor_class.value = (before as i32 + delta).clamp(1, 30) as u8;
+    log.record("Armor class", before, stat_block.armor_class.value);
+}
+
+/// Shift attack bonuses and save DCs of actions and spellcasting
+fn shift_accuracy(stat_block: &mut StatBlock, attack_delta: i32, dc_delta: i32, log: &mut Changelog) {
+    for action in all_actions(stat_block) {
+        if let Some(attack) = &mut action.attack {
+            let before = attack.to_hit;
+            attack.to_hit += attack_delta;
+            log.record(&format!("{} attack bonus", action.name), signed(before), signed(attack.to_hit));
+        }
+        if let Some(save) = &mut action.saving_throw {
+            let before = save.dc;
+            save.dc = (save.dc as i32 + dc_delta).clamp(1, 30) as u8;
+            log.record(&format!("{} save DC", action.name), before, save.dc);
+        }
+    }
+    for spellcasting in &mut stat_block.spellcasting {
+        if let Some(attack_bonus) = &mut spellcasting.attack_bonus {
+            let before = *attack_bonus;
+            *attack_bonus += attack_delta;
+            log.record(&format!("{} attack bonus", spellcasting.name), signed(before), signed(*attack_bonus));
+        }
+        if let Some(save_dc) = &mut spellcasting.save_dc {
+            let before = *save_dc;
+            *save_dc = (*save_dc as i32 + dc_delta).clamp(1, 30) as u8;
+            log.record(&format!("{} save DC", spellcasting.name), before, *save_dc);
+        }
+    }
+}
+
+/// Scale the damage of every action, changing the number of dice and keeping
+/// the bonus. Damage that isn't written as "NdS+M" or a number is kept.
+fn scale_damage(stat_block: &mut StatBlock, ratio: f64, log: &mut Changelog) -> DmResult<()> {
+    for action in all_actions(stat_block) {
+        for damage in &mut action.damage {
+            let before = damage.dice.clone();
+            if let Some(dice) = SimpleDice::parse(&damage.dice) {
+                let target = dice.average() * ratio;
+                let count = ((target - dice.bonus as f64) / dice.die_average()).round().max(1.0);
+                if count > MAX_DICE as f64 {
+                    return Err(DmAssistantError::invalid_input(&format!(
+                        "Scaled {} damage of {} would need {} dice, more than the limit of {}", action.name, target, count, MAX_DICE)));
+                }
+                damage.dice = SimpleDice { count: count as u32, ..dice }.to_string();
+            } else if let Ok(fixed) = damage.dice.trim().parse::<u32>() {
+                damage.dice = ((fixed as f64 * ratio).round() as u32).max(1).to_string();
+            } else {
+                log.record(&format!("{} damage", action.name), &before, format!("{} (not scaled)", before));
+                continue;
+            }
+            log.record(&format!("{} damage", action.name), before, &damage.dice);
+        }
+    }
+    Ok(())
+}
+
+/// Shift the bonuses of proficient saves and skills to a new proficiency bonus
+fn shift_proficiency(stat_block: &mut StatBlock, delta: i32, log: &mut Changelog) {
+    for (ability, bonus) in stat_block.saving_throws.iter_mut() {
+        let before = *bonus;
+        *bonus += delta;
+        log.record(&format!("{:?} saving throw", ability), signed(before), signed(*bonus));
+    }
+    for (skill, bonus) in stat_block.skills.iter_mut() {
+        let before = *bonus;
+        *bonus += delta;
+        log.record(&format!("{:?} skill", skill), signed(before), signed(*bonus));
+    }
+}
+
+fn set_challenge_rating(stat_block: &mut StatBlock, cr: ChallengeRating, log: &mut Changelog) {
+    let before = stat_block.challenge_rating;
+    stat_block.challenge_rating = cr;
+    log.record("Challenge rating", before, cr);
+}
+
+/// Move the declared CR by as many steps as the changes moved the calculated one
+fn recalculate_challenge_rating(stat_block: &mut StatBlock, calculated_before: ChallengeRating, log: &mut Changelog) -> DmResult<()> {
+    let calculated = challenge_rating::calculate(stat_block)?.challenge_rating;
+    let steps = calculated.index() as i32 - calculated_before.index() as i32;
+    let index = (stat_block.challenge_rating.index() as i32 + steps).max(0) as usize;
+    set_challenge_rating(stat_block, ChallengeRating::from_index(index), log);
+    Ok(())
+}
+
+fn rename(stat_block: &mut StatBlock, name: String, log: &mut Changelog) {
+    let before = std::mem::replace(&mut stat_block.name, name);
+    log.record("Name", before, &stat_block.name);
+}
+
+fn retarget(stat_block: &mut StatBlock, target: ChallengeRating, log: &mut Changelog) -> DmResult<()> {
+    let from = statistics(stat_block.challenge_rating);
+    let to = statistics(target);
+
+    let hit_points_ratio = midpoint(to.min_hit_points, to.max_hit_points) / midpoint(from.min_hit_points, from.max_hit_points);
+    scale_hit_points(stat_block, hit_points_ratio, log)?;
+    shift_armor_class(stat_block, to.armor_class - from.armor_class, log);
+    shift_accuracy(stat_block, to.attack_bonus - from.attack_bonus, to.save_dc - from.save_dc, log);
+    // CR 0 deals up to 1 damage: scale from at least 1 to avoid blowing up
+    let damage_ratio = midpoint(to.min_damage, to.max_damage).max(1.0) / midpoint(from.min_damage, from.max_damage).max(1.0);
+    scale_damage(stat_block, damage_ratio, log)?;
+    shift_proficiency(stat_block, target.proficiency_bonus() - stat_block.challenge_rating.proficiency_bonus(), log);
+    set_challenge_rating(stat_block, target, log);
+    Ok(())
+}
+
+fn elite(stat_block: &mut StatBlock, log: &mut Changelog) -> DmResult<()> {
+    let calculated = challenge_rating::calculate(stat_block)?.challenge_rating;
+    scale_hit_points(stat_block, 1.5, log)?;
+    shift_armor_class(stat_block, 2, log);
+    shift_accuracy(stat_block, 2, 2, log);
+    if !has_trait(stat_block, "Legendary Resistance") {
+        add_trait(stat_block, "Legendary Resistance (1/Day)", "If the creature fails a saving throw, it can choose to succeed instead.", log);
+    }
+    recalculate_challenge_rating(stat_block, calculated, log)?;
+    rename(stat_block, format!("Elite {}", stat_block.name), log);
+    Ok(())
+}
+
+fn minion(stat_block: &mut StatBlock, log: &mut Changelog) -> DmResult<()> {
+    let calculated = challenge_rating::calculate(stat_block)?.challenge_rating;
+    let before = stat_block.hit_points.average;
+    stat_block.hit_points.average = 1;
+    stat_block.hit_points.formula = None;
+    log.record("Hit points", before, 1);
+
+    // Fixed damage, as printed before the dice
+    for action in all_actions(stat_block) {
+        for damage in &mut action.damage {
+            if let Some(dice) = SimpleDice::parse(&damage.dice) {
+                let before = std::mem::replace(&mut damage.dice, (dice.average().floor().max(1.0) as u32).to_string());
+                log.record(&format!("{} damage", action.name), before, &damage.dice);
+            }
+        }
+    }
+    if stat_block.legendary_actions.take().is_some() {
+        log.record("Legendary actions", "yes", "none");
+    }
+    stat_block.traits.retain(|feature| !feature.name.to_lowercase().starts_with("legendary resistance"));
+    add_trait(stat_block, "Minion", "If the minion takes damage from an attack or as the result of a failed saving throw, \
+        it dies. If it succeeds on a saving throw against an effect that deals half damage on a success, it takes no damage.", log);
+    recalculate_challenge_rating(stat_block, calculated, log)?;
+    rename(stat_block, format!("{} Minion", stat_block.name), log);
+    Ok(())
+}
+
+fn undead_variant(stat_block: &mut StatBlock, log: &mut Changelog) -> DmResult<()> {
+    let calculated = challenge_rating::calculate(stat_block)?.challenge_rating;
+    let before = stat_block.creature_type;
+    stat_block.creature_type = CreatureType::Undead;
+    log.record("Creature type", format!("{:?}", before), format!("{:?}", CreatureType::Undead));
+
+    if !stat_block.damage_immunities.iter().any(|d| d.damage_type == DamageType::Poison) {
+        stat_block.damage_resistances.retain(|d| d.damage_type != DamageType::Poison);
+        stat_block.damage_immunities.push(DamageDefense { damage_type: DamageType::Poison, condition: None });
+        log.record("Damage immunities", "", "poison");
+    }
+    let defended = |d: &DamageDefense| d.damage_type == DamageType::Necrotic;
+    if !stat_block.damage_resistances.iter().any(defended) && !stat_block.damage_immunities.iter().any(defended) {
+        stat_block.damage_resistances.push(DamageDefense { damage_type: DamageType::Necrotic, condition: None });
+        log.record("Damage resistances", "", "necrotic");
+    }
+    for condition in [Condition::Exhaustion, Condition::Poisoned] {
+        if !stat_block.condition_immunities.contains(&condition) {
+            stat_block.condition_immunities.push(condition);
+            log.record("Condition immunities", "", format!("{:?}", condition).to_lowercase());
+        }
+    }
+    let darkvision = stat_block.senses.darkvision.unwrap_or(0);
+    if darkvision < 60 {
+        stat_block.senses.darkvision = Some(60);
+        log.record("Darkvision", format!("{} ft.", darkvision), "60 ft.");
+    }
+    add_trait(stat_block, "Undead Nature", "The creature doesn't require air, food, drink, or sleep.", log);
+    recalculate_challenge_rating(stat_block, calculated, log)?;
+    Ok(())
+}
+
+fn adult(stat_block: &mut StatBlock, log: &mut Changelog) -> DmResult<()> {
+    let before = stat_block.size;
+    stat_block.size = match before {
+        Size::Tiny => Size::Small,
+        Size::Small => Size::Medium,
+        Size::Medium => Size::Large,
+        Size::Large => Size::Huge,
+        Size::Huge | Size::Gargantuan => Size::Gargantuan,
+    };
+    log.record("Size", format!("{:?}", before), format!("{:?}", stat_block.size));
+
+    for ability in Ability::ALL {
+        let increase = match ability {
+            Ability::Strength | Ability::Constitution => 4,
+            _ => 2,
+        };
+        let score = stat_block.ability_scores.get(ability);
+        let grown = (score + increase).min(30);
+        match ability {
+            Ability::Strength => stat_block.ability_scores.strength = grown,
+            Ability::Dexterity => stat_block.ability_scores.dexterity = grown,
+            Ability::Constitution => stat_block.ability_scores.constitution = grown,
+            Ability::Intelligence => stat_block.ability_scores.intelligence = grown,
+            Ability::Wisdom => stat_block.ability_scores.wisdom = grown,
+            Ability::Charisma => stat_block.ability_scores.charisma = grown,
+        }
+        log.record(&format!("{:?}", ability), score, grown);
+    }
+    for action in all_actions(stat_block) {
+        if let Some(reach) = action.attack.as_mut().and_then(|attack| attack.reach.as_mut()) {
+            let before = *reach;
+            *reach += 5;
+            log.record(&format!("{} reach", action.name), format!("{} ft.", before), format!("{} ft.", reach));
+        }
+    }
+    if let Some(blindsight) = &mut stat_block.senses.blindsight {
+        let before = *blindsight;
+        *blindsight *= 2;
+        log.record("Blindsight", format!("{} ft.", before), format!("{} ft.", blindsight));
+    }
+    if !has_trait(stat_block, "Legendary Resistance") {
+        add_trait(stat_block, "Legendary Resistance (3/Day)", "If the creature fails a saving throw, it can choose to succeed instead.", log);
+    }
+
+    let target = ChallengeRating::from_index(stat_block.challenge_rating.index() + 7);
+    retarget(stat_block, target, log)?;
+    let name = match stat_block.name.strip_prefix("Young ") {
+        Some(rest) => format!("Adult {}", rest),
+        None => format!("Adult {}", stat_block.name),
+    };
+    rename(stat_block, name, log);
+    Ok(())
+}
+
+/// Scaling and templating of monsters, to reuse a stat block at another difficulty
+pub struct TemplateService;
+
+impl TemplateService {
+    /// Apply templates to a stat block in order, recording every change
+    pub fn apply_templates(stat_block: &StatBlock, templates: &[MonsterTemplate]) -> DmResult<(StatBlock, Vec<TemplateChange>)> {
+        let mut stat_block = stat_block.clone();
+        let mut changes = Vec::new();
+        for template in templates {
+            let mut log = Changelog { template: Self::template_name(template), changes: &mut changes };
+            match *template {
+                MonsterTemplate::Retarget { challenge_rating } => retarget(&mut stat_block, challenge_rating, &mut log)?,
+                MonsterTemplate::Elite => elite(&mut stat_block, &mut log)?,
+                MonsterTemplate::Minion => minion(&mut stat_block, &mut log)?,
+                MonsterTemplate::UndeadVariant => undead_variant(&mut stat_block, &mut log)?,
+                MonsterTemplate::Adult => adult(&mut stat_block, &mut log)?,
+            }
+        }
+        MonsterStorageService::validate_stat_block(&stat_block)?;
+        Ok((stat_block, changes))
+    }
+
+    /// New monster made from another one with templates. It isn't saved, so
+    /// it can be reviewed and created like any other monster.
+    pub fn scale_monster(req: ScaleMonsterRequest) -> DmResult<ScaledMonster> {
+        if req.templates.is_empty() {
+            return Err(DmAssistantError::invalid_input("No template to apply"));
+        }
+        let (stat_block, changelog) = Self::apply_templates(&req.monster.stat_block, &req.templates)?;
+        let now = Utc::now();
+        let monster = Monster {
+            id: Uuid::new_v4(),
+            stat_block,
+            created_at: now,
+            updated_at: now,
+            ..req.monster
+        };
+        Ok(ScaledMonster { monster, changelog })
+    }
+
+    fn template_name(template: &MonsterTemplate) -> String {
+        match template {
+            MonsterTemplate::Retarget { challenge_rating } => format!("CR {}", challenge_rating),
+            MonsterTemplate::Elite => "Elite".to_string(),
+            MonsterTemplate::Minion => "Minion".to_string(),
+            MonsterTemplate::UndeadVariant => "Undead variant".to_string(),
+            MonsterTemplate::Adult => "Adult".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::common::Skill;
+    use crate::core::models::compendium::{CompendiumEntry, CompendiumKind};
+    use crate::core::models::monster::CreateMonsterRequest;
+    use crate::services::compendium::{CompendiumService, SRD_PACK};
+
+    fn srd_monster(key: &str) -> StatBlock {
+        match CompendiumService::get_entry(SRD_PACK, CompendiumKind::Monster, key).unwrap() {
+            CompendiumEntry::Monster(stat_block) => *stat_block,
+            _ => panic!("expected a monster"),
+        }
+    }
+
+    fn cr(s: &str) -> ChallengeRating {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_simple_dice() {
+        assert_eq!(SimpleDice::parse("2d6 + 3"), Some(SimpleDice { count: 2, sides: 6, bonus: 3 }));
+        assert_eq!(SimpleDice::parse("d8-1"), Some(SimpleDice { count: 1, sides: 8, bonus: -1 }));
+        assert_eq!(SimpleDice::parse("4d6kh3"), None);
+        assert_eq!(SimpleDice::parse("7"), None);
+        assert_eq!(SimpleDice { count: 3, sides: 10, bonus: -2 }.to_string(), "3d10-2");
+    }
+
+    #[test]
+    fn test_retarget_along_the_dmg_table() {
+        let goblin = srd_monster("goblin");
+        let (scaled, changes) = TemplateService::apply_templates(&goblin, &[
+            MonsterTemplate::Retarget { challenge_rating: cr("5") },
+        ]).unwrap();
+
+        assert_eq!(scaled.challenge_rating, cr("5"));
+        // 7 HP is 1/6 of the middle of CR 1/4, so about 1/6 of the middle of CR 5
+        assert_eq!(scaled.hit_points.formula.as_deref(), Some("7d6"));
+        assert_eq!(scaled.hit_points.average, 24);
+        // AC 13 -> 15, +3 -> +6, proficiency +2 -> +3
+        assert_eq!(scaled.armor_class.value, 17);
+        assert_eq!(scaled.actions[0].attack.as_ref().unwrap().to_hit, 7);
+        assert_eq!(scaled.skills[&Skill::Stealth], 7);
+        // 4-5 damage per round -> 33-38: the bonus stays, dice are added
+        assert_eq!(scaled.actions[0].damage[0].dice, "12d6+2");
+
+        assert!(changes.iter().all(|change| change.template == "CR 5"));
+        assert!(changes.iter().any(|change| change.field == "Scimitar damage" && change.before == "1d6+2"));
+        // Unchanged values aren't in the changelog
+        assert!(!changes.iter().any(|change| change.field == "Name"));
+    }
+
+    #[test]
+    fn test_hit_dice_beyond_the_dice_limit_are_rejected() {
+        let mut colossus = srd_monster("goblin");
+        colossus.challenge_rating = cr("0");
+        colossus.hit_points = HitPoints { average: 1050, formula: Some("300d6".to_string()) };
+
+        let err = TemplateService::apply_templates(&colossus, &[
+            MonsterTemplate::Retarget { challenge_rating: cr("30") },
+        ]).unwrap_err();
+        assert!(err.to_string().contains(&format!("limit of {}", MAX_DICE)));
+
+        // Up to the limit is fine
+        colossus.hit_points = HitPoints { average: 7, formula: Some("2d6".to_string()) };
+        assert!(TemplateService::apply_templates(&colossus, &[
+            MonsterTemplate::Retarget { challenge_rating: cr("30") },
+        ]).is_ok());
+    }
+
+    #[test]
+    fn test_damage_dice_beyond_the_dice_limit_are_rejected() {
+        let mut colossus = srd_monster("goblin");
+        colossus.challenge_rating = cr("0");
+        colossus.actions[0].damage[0].dice = "4d1".to_string();
+
+        let err = TemplateService::apply_templates(&colossus, &[
+            MonsterTemplate::Retarget { challenge_rating: cr("30") },
+        ]).unwrap_err();
+        assert!(err.to_string().contains("Scimitar damage"));
+        assert!(err.to_string().contains(&format!("limit of {}", MAX_DICE)));
+    }
+
+    #[test]
+    fn test_templates_compose() {
+        let goblin = srd_monster("goblin");
+        let (scaled, changes) = TemplateService::apply_templates(&goblin, &[
+            MonsterTemplate::Retarget { challenge_rating: cr("5") },
+            MonsterTemplate::Elite,
+            MonsterTemplate::UndeadVariant,
+        ]).unwrap();
+
+        assert_eq!(scaled.name, "Elite Goblin");
+        // Elite applies to the retargeted goblin
+        assert_eq!(scaled.armor_class.value, 19);
+        assert_eq!(scaled.hit_points.formula.as_deref(), Some("10d6"));
+        assert!(scaled.challenge_rating >= cr("5"));
+        assert_eq!(scaled.creature_type, CreatureType::Undead);
+        assert!(scaled.condition_immunities.contains(&Condition::Poisoned));
+        assert!(has_trait(&scaled, "Legendary Resistance"));
+
+        let templates: Vec<&str> = changes.iter().map(|change| change.template.as_str()).collect();
+        let first = |name: &str| templates.iter().position(|t| *t == name).unwrap();
+        assert!(first("CR 5") < first("Elite") && first("Elite") < first("Undead variant"));
+
+        let (minion, _) = TemplateService::apply_templates(&goblin, &[MonsterTemplate::Minion]).unwrap();
+        assert_eq!(minion.hit_points.average, 1);
+        assert_eq!(minion.actions[0].damage[0].dice, "5");
+        assert!(minion.challenge_rating <= goblin.challenge_rating);
+    }
+
+    #[test]
+    fn test_young_to_adult() {
+        let young = srd_monster("young-red-dragon");
+        let monster = Monster::new(CreateMonsterRequest {
+            campaign_id: Uuid::new_v4(),
+            stat_block: young.clone(),
+            notes: Some("Lairs in the volcano".to_string()),
+        });
+        let scaled = TemplateService::scale_monster(ScaleMonsterRequest {
+            monster: monster.clone(),
+            templates: vec![MonsterTemplate::Adult],
+        }).unwrap();
+
+        let adult = &scaled.monster.stat_block;
+        assert_ne!(scaled.monster.id, monster.id);
+        assert_eq!(scaled.monster.notes, monster.notes);
+        assert_eq!(adult.name, "Adult Red Dragon");
+        assert_eq!(adult.size, Size::Huge);
+        assert_eq!(adult.challenge_rating, cr("17"));
+        assert_eq!(adult.ability_scores.strength, 27);
+        assert_eq!(adult.actions[1].attack.as_ref().unwrap().reach, Some(15));
+        assert!(adult.hit_points.average > young.hit_points.average);
+        assert!(adult.actions[3].saving_throw.as_ref().unwrap().dc > 17);
+        assert!(has_trait(adult, "Legendary Resistance (3/Day)"));
+
+        assert!(TemplateService::scale_monster(ScaleMonsterRequest { monster, templates: Vec::new() }).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Per-campaign locking for read-modify-write cycles on campaign files.
+//!
+//! Within the process, a reentrant lock per campaign serializes storage
+//! operations (reentrant because some operations call others, e.g. deleting
+//! an NPC cleans up character relationships). Across processes, the outermost
+//! holder also takes an advisory lock on `locks/<campaign_id>.lock` in the app
+//! data directory, so a second app instance can't interleave its writes.
+
+use crate::utils::error::{DmAssistantError, DmResult};
+use crate::utils::file_system::{get_app_data_dir, ensure_dir_exists};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread::{self, ThreadId};
+use uuid::Uuid;
+
+/// Owner and depth of a campaign lock. An owner with a depth of 0 is still
+/// taking the file lock.
+#[derive(Default)]
+struct LockState {
+    owner: Option<ThreadId>,
+    depth: usize,
+    file: Option<File>, // Advisory file lock, held while depth > 0
+}
+
+#[derive(Default)]
+struct CampaignMutex {
+    state: Mutex<LockState>,
+    released: Condvar,
+}
+
+/// Lock of a campaign, with the number of guards and waiting threads using it
+struct RegistryEntry {
+    lock: Arc<CampaignMutex>,
+    users: usize,
+}
+
+fn registry() -> &'static Mutex<HashMap<Uuid, RegistryEntry>> {
+    static LOCKS: OnceLock<Mutex<HashMap<Uuid, RegistryEntry>>> = OnceLock::new();
+    LOCKS.get_or_init(Default::default)
+}
+
+/// Lock of a campaign, counting the caller as one of its users
+fn acquire_entry(campaign_id: &Uuid) -> Arc<CampaignMutex> {
+    let mut locks = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = locks.entry(*campaign_id)
+        .or_insert_with(|| RegistryEntry { lock: Arc::default(), users: 0 });
+    entry.users += 1;
+    entry.lock.clone()
+}
+
+/// Stop using the lock of a campaign, forgetting it once nobody holds or waits for it
+fn release_entry(campaign_id: &Uuid) {
+    let mut locks = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = locks.get_mut(campaign_id) {
+        entry.users -= 1;
+        if entry.users == 0 {
+            locks.remove(campaign_id);
+        }
+    }
+}
+
+/// Held lock on a campaign, released on drop
+pub struct CampaignLockGuard {
+    campaign_id: Uuid,
+    lock: Arc<CampaignMutex>,
+}
+
+/// Lock a campaign for the current thread, blocking until it's available.
+/// The same thread can lock a campaign again while holding it.
+pub fn lock_campaign(campaign_id: &Uuid) -> DmResult<CampaignLockGuard> {
+    let lock = acquire_entry(campaign_id);
+
+    let current = thread::current().id();
+    let mut state = lock.state.lock().unwrap_or_else(|e| e.into_inner());
+    while state.owner.is_some_and(|owner| owner != current) {
+        state = lock.released.wait(state).unwrap_or_else(|e| e.into_inner());
+    }
+
+    if state.depth == 0 {
+        // Outermost acquisition: also lock out other processes. The lock is
+        // claimed first and the file locked without holding the mutex, so
+        // other threads wait on the condition variable meanwhile.
+        state.owner = Some(current);
+        drop(state);
+        let file = lock_file(campaign_id);
+
+        state = lock.state.lock().unwrap_or_else(|e| e.into_inner());
+        match file {
+            Ok(file) => state.file = Some(file),
+            Err(e) => {
+                state.owner = None;
+                drop(state);
+                lock.released.notify_one();
+                release_entry(campaign_id);
+                return Err(e);
+            }
+        }
+    }
+    state.depth += 1;
+    drop(state);
+
+    Ok(CampaignLockGuard { campaign_id: *campaign_id, lock })
+}
+
+/// Open and exclusively lock the campaign lock file
+fn lock_file(campaign_id: &Uuid) -> DmResult<File> {
+    let locks_dir = get_app_data_dir()?.join("locks");
+    ensure_dir_exists(&locks_dir)?;
+
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(locks_dir.join(format!("{}.lock", campaign_id)))
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to open lock file: {}", e)))?;
+    file.lock()
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to lock campaign: {}", e)))?;
+
+    Ok(file)
+}
+
+impl Drop for CampaignLockGuard {
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.depth -= 1;
+        if state.depth == 0 {
+            // Closing the file releases the advisory lock
+            if let Some(file) = state.file.take() {
+                let _ = file.unlock();
+            }
+            state.owner = None;
+            drop(state);
+            self.lock.released.notify_one();
+        } else {
+            drop(state);
+        }
+        release_entry(&self.campaign_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::init_test_data_dir;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_lock_is_reentrant() {
+        init_test_data_dir();
+        let campaign_id = Uuid::new_v4();
+        let outer = lock_campaign(&campaign_id).unwrap();
+        let inner = lock_campaign(&campaign_id).unwrap();
+        drop(inner);
+        drop(outer);
+
+        // Released: another thread can take it
+        thread::spawn(move || lock_campaign(&campaign_id).map(|_| ()))
+            .join()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_lock_excludes_other_threads() {
+        init_test_data_dir();
+        let campaign_id = Uuid::new_v4();
+        let inside = Arc::new(AtomicUsize::new(0));
+        let max_inside = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let inside = inside.clone();
+            let max_inside = max_inside.clone();
+            thread::spawn(move || {
+                for _ in 0..20 {
+                    let _guard = lock_campaign(&campaign_id).unwrap();
+                    let now = inside.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_inside.fetch_max(now, Ordering::SeqCst);
+                    thread::yield_now();
+                    inside.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(max_inside.load(Ordering::SeqCst), 1);
+        // Nobody holds or waits for the lock anymore
+        assert!(!registry().lock().unwrap().contains_key(&campaign_id));
+    }
+
+    #[test]
+    fn test_released_locks_are_forgotten() {
+        init_test_data_dir();
+        let campaign_id = Uuid::new_v4();
+        let outer = lock_campaign(&campaign_id).unwrap();
+        let inner = lock_campaign(&campaign_id).unwrap();
+        assert_eq!(registry().lock().unwrap()[&campaign_id].users, 2);
+
+        drop(inner);
+        assert!(registry().lock().unwrap().contains_key(&campaign_id));
+        drop(outer);
+        assert!(!registry().lock().unwrap().contains_key(&campaign_id));
+    }
+}
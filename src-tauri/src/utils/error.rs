@@ -33,6 +33,8 @@ pub enum DmAssistantError {
     ItemError(String),
     /// Map-related errors
     MapError(String),
+    /// Dice notation errors, with the zero-based character offset of the problem
+    DiceError { message: String, position: usize },
     /// Authentication/Authorization errors
     AuthError(String),
 }
@@ -54,6 +56,7 @@ impl fmt::Display for DmAssistantError {
             DmAssistantError::QuestError(msg) => write!(f, "Quest Error: {}", msg),
             DmAssistantError::ItemError(msg) => write!(f, "Item Error: {}", msg),
             DmAssistantError::MapError(msg) => write!(f, "Map Error: {}", msg),
+            DmAssistantError::DiceError { message, position } => write!(f, "Dice Error: {} (at position {})", message, position),
             DmAssistantError::AuthError(msg) => write!(f, "Auth Error: {}", msg),
         }
     }
@@ -74,6 +77,13 @@ impl From<serde_json::Error> for DmAssistantError {
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for DmAssistantError {
+    fn from(error: rusqlite::Error) -> Self {
+        DmAssistantError::StorageError(format!("Database error: {}", error))
+    }
+}
+
 impl From<uuid::Error> for DmAssistantError {
     fn from(error: uuid::Error) -> Self {
         DmAssistantError::InvalidInput(format!("Invalid UUID: {}", error))
@@ -147,6 +157,11 @@ impl DmAssistantError {
         DmAssistantError::MapError(msg.to_string())
     }
 
+    /// Create a DiceError at the given character position
+    pub fn dice(msg: &str, position: usize) -> Self {
+        DmAssistantError::DiceError { message: msg.to_string(), position }
+    }
+
     /// Create an AuthError
     pub fn auth(msg: &str) -> Self {
         DmAssistantError::AuthError(msg.to_string())
@@ -179,6 +194,7 @@ impl DmAssistantError {
             DmAssistantError::QuestError(_) => "quest",
             DmAssistantError::ItemError(_) => "item",
             DmAssistantError::MapError(_) => "map",
+            DmAssistantError::DiceError { .. } => "dice",
             DmAssistantError::AuthError(_) => "auth",
         }
     }
@@ -210,6 +226,7 @@ impl DmAssistantError {
             DmAssistantError::QuestError(msg) => format!("Errore quest: {}", msg),
             DmAssistantError::ItemError(msg) => format!("Errore oggetto: {}", msg),
             DmAssistantError::MapError(msg) => format!("Errore mappa: {}", msg),
+            DmAssistantError::DiceError { message, position } => format!("Errore dadi: {} (posizione {})", message, position),
             DmAssistantError::AuthError(_) => "Errore di autorizzazione".to_string(),
         }
     }
@@ -231,6 +248,7 @@ impl DmAssistantError {
             DmAssistantError::QuestError(_) => true,
             DmAssistantError::ItemError(_) => true,
             DmAssistantError::MapError(_) => true,
+            DmAssistantError::DiceError { .. } => true,
             DmAssistantError::AuthError(_) => false,
         }
     }
@@ -263,6 +281,9 @@ macro_rules! dm_error {
     (campaign, $msg:expr) => {
         DmAssistantError::campaign($msg)
     };
+    (dice, $msg:expr, $pos:expr) => {
+        DmAssistantError::dice($msg, $pos)
+    };
 }
 
 /// Helper function to validate UUID strings
@@ -322,6 +343,14 @@ mod tests {
         assert!(DmAssistantError::invalid_input("test").is_recoverable());
     }
 
+    #[test]
+    fn test_dice_error() {
+        let error = DmAssistantError::dice("Expected number of sides", 2);
+        assert_eq!(error.to_string(), "Dice Error: Expected number of sides (at position 2)");
+        assert_eq!(error.category(), "dice");
+        assert!(error.is_recoverable());
+    }
+
     #[test]
     fn test_validate_uuid() {
         assert!(validate_uuid("invalid", "Character").is_err());
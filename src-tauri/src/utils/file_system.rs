@@ -1,14 +1,35 @@
 use crate::utils::error::{DmAssistantError, DmResult};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use serde::{Serialize, Deserialize};
 
-/// Get the application data directory
-pub fn get_app_data_dir() -> DmResult<PathBuf> {
+/// Extension appended to a file for the copy of its last good version
+pub const BACKUP_EXTENSION: &str = "bak";
+
+/// Prefix of the temporary files written by `save_json`
+const TEMP_FILE_PREFIX: &str = ".tmp-";
+
+/// Timestamped copies kept per file by `backup_file`
+pub const MAX_FILE_BACKUPS: usize = 5;
+
+/// Location of the application data: the local data directory of the user
+#[cfg(not(test))]
+fn app_data_location() -> DmResult<PathBuf> {
     let app_data = dirs::data_local_dir()
         .ok_or_else(|| DmAssistantError::storage("Could not determine app data directory"))?;
-    
-    let dm_assistant_dir = app_data.join("dm-assistant");
+    Ok(app_data.join("dm-assistant"))
+}
+
+/// Tests keep their data in a temporary directory instead
+#[cfg(test)]
+fn app_data_location() -> DmResult<PathBuf> {
+    Ok(crate::utils::test_support::test_data_dir().to_path_buf())
+}
+
+/// Get the application data directory
+pub fn get_app_data_dir() -> DmResult<PathBuf> {
+    let dm_assistant_dir = app_data_location()?;
     
     // Create directory if it doesn't exist
     if !dm_assistant_dir.exists() {
@@ -45,26 +66,60 @@ pub fn get_campaign_dir(campaign_id: &str) -> DmResult<PathBuf> {
     Ok(campaign_dir)
 }
 
-/// Save JSON data to file
+/// Save JSON data to file atomically.
+///
+/// Data is written to a temporary file in the same directory, flushed to disk
+/// and renamed over the target, so a crash leaves either the old or the new
+/// file, never a truncated one. The previous version is kept as `<file>.bak`.
 pub fn save_json<T: Serialize>(data: &T, file_path: &Path) -> DmResult<()> {
     let json_data = serde_json::to_string_pretty(data)
         .map_err(|e| DmAssistantError::json(&format!("Failed to serialize data: {}", e)))?;
     
     // Create parent directory if it doesn't exist
-    if let Some(parent) = file_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| DmAssistantError::storage(&format!("Failed to create directory: {}", e)))?;
-        }
-    }
+    let parent = match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    ensure_dir_exists(parent)?;
     
-    fs::write(file_path, json_data)
+    let mut temp_file = tempfile::Builder::new()
+        .prefix(TEMP_FILE_PREFIX)
+        .tempfile_in(parent)
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to create temporary file: {}", e)))?;
+    temp_file.write_all(json_data.as_bytes())
+        .and_then(|_| temp_file.as_file().sync_all())
         .map_err(|e| DmAssistantError::storage(&format!("Failed to write file: {}", e)))?;
     
+    // Keep the current version if it's valid, so a damaged file never
+    // replaces the last good backup
+    if is_valid_json(file_path) {
+        fs::copy(file_path, backup_path_for(file_path))
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to create backup: {}", e)))?;
+    }
+    
+    temp_file.persist(file_path)
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to write file: {}", e.error)))?;
+    
+    // Make the rename itself durable
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    
     Ok(())
 }
 
-/// Load JSON data from file
+/// Check if a file holds well-formed JSON, without building it in memory
+fn is_valid_json(file_path: &Path) -> bool {
+    fs::File::open(file_path)
+        .map(|file| serde_json::from_reader::<_, serde::de::IgnoredAny>(std::io::BufReader::new(file)).is_ok())
+        .unwrap_or(false)
+}
+
+/// Load JSON data from file.
+///
+/// If the file is damaged (empty, truncated or not JSON at all) the newest
+/// valid backup is loaded instead.
 pub fn load_json<T: for<'de> Deserialize<'de>>(file_path: &Path) -> DmResult<T> {
     if !file_path.exists() {
         return Err(DmAssistantError::not_found_msg(&format!("File not found: {}", file_path.display())));
@@ -73,10 +128,80 @@ pub fn load_json<T: for<'de> Deserialize<'de>>(file_path: &Path) -> DmResult<T>
     let json_data = fs::read_to_string(file_path)
         .map_err(|e| DmAssistantError::storage(&format!("Failed to read file: {}", e)))?;
     
-    let data = serde_json::from_str(&json_data)
-        .map_err(|e| DmAssistantError::json(&format!("Failed to deserialize data: {}", e)))?;
-    
-    Ok(data)
+    match serde_json::from_str(&json_data) {
+        Ok(data) => Ok(data),
+        // Valid JSON with the wrong shape isn't damage, don't hide it behind a backup
+        Err(e) if e.is_data() => Err(DmAssistantError::json(&format!("Failed to deserialize data: {}", e))),
+        Err(e) => {
+            for backup in find_backups(file_path) {
+                if let Ok(data) = read_json_value(&backup).and_then(|value| {
+                    serde_json::from_value(value).map_err(|e| DmAssistantError::json(&e.to_string()))
+                }) {
+                    eprintln!("{} is damaged ({}), loaded backup {}", file_path.display(), e, backup.display());
+                    return Ok(data);
+                }
+            }
+            Err(DmAssistantError::json(&format!("Failed to deserialize data: {} (no valid backup found)", e)))
+        }
+    }
+}
+
+/// Read a file as a JSON value, without any fallback
+fn read_json_value(file_path: &Path) -> DmResult<serde_json::Value> {
+    let json_data = fs::read_to_string(file_path)
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to read file: {}", e)))?;
+    serde_json::from_str(&json_data)
+        .map_err(|e| DmAssistantError::json(&format!("Failed to deserialize data: {}", e)))
+}
+
+/// Path of the copy of the last good version of a file
+pub fn backup_path_for(file_path: &Path) -> PathBuf {
+    let mut name = file_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(BACKUP_EXTENSION);
+    file_path.with_file_name(name)
+}
+
+/// Backups of a file, newest first: `<file>.bak` and the timestamped copies made by `backup_file`
+pub fn find_backups(file_path: &Path) -> Vec<PathBuf> {
+    let (Some(parent), Some(stem)) = (file_path.parent(), file_path.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let timestamped_prefix = format!("{}.backup_", stem);
+    let last_good = backup_path_for(file_path);
+
+    let mut backups: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(parent)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| {
+            *path == last_good
+                || path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&timestamped_prefix))
+        })
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .collect();
+
+    backups.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Relative path with `/` separators on every platform, as stored in archives
+pub fn portable_path(relative: &Path) -> String {
+    relative.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Check if a file is a leftover or backup of the storage layer rather than data
+pub fn is_storage_artifact(file_path: &Path) -> bool {
+    let name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    name.starts_with(TEMP_FILE_PREFIX)
+        || name.contains(".backup_")
+        || file_path.extension().is_some_and(|ext| ext == BACKUP_EXTENSION)
 }
 
 /// Check if file exists
@@ -106,15 +231,39 @@ pub fn backup_file(file_path: &Path) -> DmResult<PathBuf> {
         return Err(DmAssistantError::not_found_msg(&format!("File not found: {}", file_path.display())));
     }
     
-    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S%3f").to_string();
     let backup_path = file_path.with_extension(format!("backup_{}.json", timestamp));
     
     fs::copy(file_path, &backup_path)
         .map_err(|e| DmAssistantError::storage(&format!("Failed to create backup: {}", e)))?;
+    prune_file_backups(file_path)?;
     
     Ok(backup_path)
 }
 
+/// Delete all but the newest `MAX_FILE_BACKUPS` timestamped copies of a file
+fn prune_file_backups(file_path: &Path) -> DmResult<()> {
+    let (Some(parent), Some(stem)) = (file_path.parent(), file_path.file_stem().and_then(|s| s.to_str())) else {
+        return Ok(());
+    };
+    let prefix = format!("{}.backup_", stem);
+
+    // Timestamps sort chronologically by name
+    let mut copies: Vec<PathBuf> = fs::read_dir(parent)
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory: {}", e)))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix)))
+        .collect();
+    copies.sort();
+
+    let excess = copies.len().saturating_sub(MAX_FILE_BACKUPS);
+    for old in &copies[..excess] {
+        fs::remove_file(old)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to remove old backup: {}", e)))?;
+    }
+    Ok(())
+}
+
 /// List files in directory with specific extension
 pub fn list_files_with_extension(dir_path: &Path, extension: &str) -> DmResult<Vec<PathBuf>> {
     if !dir_exists(dir_path) {
@@ -142,6 +291,32 @@ pub fn list_files_with_extension(dir_path: &Path, extension: &str) -> DmResult<V
     Ok(files)
 }
 
+/// List every file under a directory, as paths relative to it
+pub fn list_files_recursive(dir_path: &Path) -> DmResult<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> DmResult<()> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory: {}", e)))?;
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory entry: {}", e)))?;
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.push(relative.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    if dir_exists(dir_path) {
+        walk(dir_path, dir_path, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
 /// Get file size in bytes
 pub fn get_file_size(file_path: &Path) -> DmResult<u64> {
     let metadata = fs::metadata(file_path)
@@ -159,6 +334,35 @@ pub fn ensure_dir_exists(dir_path: &Path) -> DmResult<()> {
     Ok(())
 }
 
+/// Recursively copy a directory, returning the number of files copied
+pub fn copy_dir_recursive(source: &Path, destination: &Path) -> DmResult<usize> {
+    if !dir_exists(source) {
+        return Err(DmAssistantError::not_found_msg(&format!("Directory not found: {}", source.display())));
+    }
+    ensure_dir_exists(destination)?;
+
+    let entries = fs::read_dir(source)
+        .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory: {}", e)))?;
+
+    let mut copied = 0;
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| DmAssistantError::storage(&format!("Failed to read directory entry: {}", e)))?;
+        let path = entry.path();
+        let target = destination.join(entry.file_name());
+
+        if path.is_dir() {
+            copied += copy_dir_recursive(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)
+                .map_err(|e| DmAssistantError::storage(&format!("Failed to copy file: {}", e)))?;
+            copied += 1;
+        }
+    }
+
+    Ok(copied)
+}
+
 /// File operation result for batch operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileOperationResult {
@@ -208,6 +412,105 @@ mod tests {
         assert_eq!(test_data, loaded_data);
     }
 
+    #[test]
+    fn test_save_keeps_last_good_version() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("characters.json");
+
+        save_json(&json!({ "version": 1 }), &file_path).unwrap();
+        assert!(!file_exists(&backup_path_for(&file_path)));
+        save_json(&json!({ "version": 2 }), &file_path).unwrap();
+
+        let backup: serde_json::Value = load_json(&backup_path_for(&file_path)).unwrap();
+        assert_eq!(backup["version"], 1);
+        // No temporary files are left behind
+        let leftovers = fs::read_dir(temp_dir.path()).unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with(TEMP_FILE_PREFIX))
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn test_truncated_file_falls_back_to_backup() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("characters.json");
+        save_json(&json!({ "version": 1, "party": ["Aria", "Borin"] }), &file_path).unwrap();
+        save_json(&json!({ "version": 2, "party": ["Aria", "Borin", "Cass"] }), &file_path).unwrap();
+
+        // Simulate a write interrupted halfway through
+        let content = fs::read_to_string(&file_path).unwrap();
+        fs::write(&file_path, &content[..content.len() / 2]).unwrap();
+        let loaded: serde_json::Value = load_json(&file_path).unwrap();
+        assert_eq!(loaded["version"], 1);
+
+        // Or one that never got to write anything
+        fs::write(&file_path, "").unwrap();
+        let loaded: serde_json::Value = load_json(&file_path).unwrap();
+        assert_eq!(loaded["version"], 1);
+
+        // Saving over a damaged file doesn't overwrite the good backup
+        fs::write(&file_path, "{ \"version\": 3, \"par").unwrap();
+        save_json(&json!({ "version": 4 }), &file_path).unwrap();
+        let backup: serde_json::Value = load_json(&backup_path_for(&file_path)).unwrap();
+        assert_eq!(backup["version"], 1);
+    }
+
+    #[test]
+    fn test_interrupted_write_leaves_original_intact() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("npcs.json");
+        save_json(&json!({ "version": 1 }), &file_path).unwrap();
+
+        // A crash while writing leaves a partial temporary file next to the target
+        fs::write(temp_dir.path().join(format!("{}crash", TEMP_FILE_PREFIX)), "{ \"vers").unwrap();
+        let loaded: serde_json::Value = load_json(&file_path).unwrap();
+        assert_eq!(loaded["version"], 1);
+        assert!(is_storage_artifact(&temp_dir.path().join(format!("{}crash", TEMP_FILE_PREFIX))));
+
+        // A failed serialization doesn't touch the file
+        let mut invalid = std::collections::HashMap::new();
+        invalid.insert(vec![1u8], 1);
+        assert!(save_json(&invalid, &file_path).is_err());
+        let loaded: serde_json::Value = load_json(&file_path).unwrap();
+        assert_eq!(loaded["version"], 1);
+    }
+
+    #[test]
+    fn test_damaged_file_without_backup_fails() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("quests.json");
+        fs::write(&file_path, "{ \"quests\": [").unwrap();
+        assert!(load_json::<serde_json::Value>(&file_path).is_err());
+
+        // Valid JSON of the wrong shape is reported, not replaced by a backup
+        fs::write(&file_path, "[1, 2, 3]").unwrap();
+        fs::write(backup_path_for(&file_path), "{ \"name\": \"old\" }").unwrap();
+        assert!(load_json::<std::collections::HashMap<String, String>>(&file_path).is_err());
+    }
+
+    #[test]
+    fn test_backup_file_keeps_newest_copies() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("notes.json");
+        save_json(&json!({ "version": 0 }), &file_path).unwrap();
+
+        let mut latest = PathBuf::new();
+        for version in 1..=MAX_FILE_BACKUPS + 2 {
+            save_json(&json!({ "version": version }), &file_path).unwrap();
+            latest = backup_file(&file_path).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let copies: Vec<PathBuf> = find_backups(&file_path)
+            .into_iter()
+            .filter(|path| *path != backup_path_for(&file_path))
+            .collect();
+        assert_eq!(copies.len(), MAX_FILE_BACKUPS);
+        assert!(copies.contains(&latest));
+        let newest: serde_json::Value = load_json(&latest).unwrap();
+        assert_eq!(newest["version"], MAX_FILE_BACKUPS + 2);
+    }
+
     #[test]
     fn test_file_exists() {
         let temp_dir = tempdir().unwrap();
@@ -247,6 +550,20 @@ mod tests {
         assert!(!deleted_again);
     }
 
+    #[test]
+    fn test_copy_dir_recursive() {
+        let temp_dir = tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        fs::create_dir_all(source.join("assets")).unwrap();
+        fs::write(source.join("campaign.json"), "{}").unwrap();
+        fs::write(source.join("assets").join("map.png"), "png").unwrap();
+
+        let destination = temp_dir.path().join("copy");
+        assert_eq!(copy_dir_recursive(&source, &destination).unwrap(), 2);
+        assert!(file_exists(&destination.join("campaign.json")));
+        assert!(file_exists(&destination.join("assets").join("map.png")));
+    }
+
     #[test]
     fn test_ensure_dir_exists() {
         let temp_dir = tempdir().unwrap();
@@ -1,3 +1,5 @@
+use serde_json::Value;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Generate a new UUID v4
@@ -31,6 +33,81 @@ pub fn to_short_display_str(id: &str) -> Result<String, uuid::Error> {
     Ok(to_short_display(&uuid))
 }
 
+/// Maps existing IDs to freshly generated ones, e.g. when duplicating a campaign
+#[derive(Debug, Clone, Default)]
+pub struct IdRemapper {
+    ids: HashMap<Uuid, Uuid>,
+}
+
+impl IdRemapper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the new ID for `old`, generating one the first time it's seen
+    pub fn remap(&mut self, old: Uuid) -> Uuid {
+        *self.ids.entry(old).or_insert_with(generate_id)
+    }
+
+    /// Map `old` to a known ID instead of a generated one
+    pub fn insert(&mut self, old: Uuid, new: Uuid) {
+        self.ids.insert(old, new);
+    }
+
+    /// Get the new ID for `old` if it has been remapped
+    pub fn get(&self, old: &Uuid) -> Option<Uuid> {
+        self.ids.get(old).copied()
+    }
+
+    /// Number of remapped IDs
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Register a new ID for every `"id"` field found in a JSON document
+    pub fn collect_ids(&mut self, value: &Value) {
+        match value {
+            Value::Object(map) => {
+                if let Some(id) = map.get("id").and_then(Value::as_str).and_then(|id| Uuid::parse_str(id).ok()) {
+                    self.remap(id);
+                }
+                map.values().for_each(|v| self.collect_ids(v));
+            }
+            Value::Array(items) => items.iter().for_each(|v| self.collect_ids(v)),
+            _ => {}
+        }
+    }
+
+    /// Replace every remapped ID in a JSON document, in values and object keys.
+    /// IDs that were never remapped (e.g. links to other campaigns) are left as-is.
+    pub fn rewrite(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => {
+                if let Some(new_id) = Uuid::parse_str(text).ok().and_then(|id| self.get(&id)) {
+                    *text = new_id.to_string();
+                }
+            }
+            Value::Object(map) => {
+                let entries = std::mem::take(map);
+                for (key, mut v) in entries {
+                    self.rewrite(&mut v);
+                    let key = match Uuid::parse_str(&key).ok().and_then(|id| self.get(&id)) {
+                        Some(new_id) => new_id.to_string(),
+                        None => key,
+                    };
+                    map.insert(key, v);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.rewrite(v)),
+            _ => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +145,35 @@ mod tests {
         assert_eq!(short, "550e8400");
     }
 
+    #[test]
+    fn test_id_remapper() {
+        let character_id = generate_id();
+        let npc_id = generate_id();
+        let external_id = generate_id();
+        let mut doc = serde_json::json!({
+            "characters": {
+                character_id.to_string(): {
+                    "id": character_id,
+                    "relationships": [{ "npcId": npc_id, "questId": external_id }]
+                }
+            },
+            "npcs": [{ "id": npc_id }]
+        });
+
+        let mut remapper = IdRemapper::new();
+        remapper.collect_ids(&doc);
+        assert_eq!(remapper.len(), 2);
+        remapper.rewrite(&mut doc);
+
+        let new_character = remapper.get(&character_id).unwrap().to_string();
+        let new_npc = remapper.get(&npc_id).unwrap().to_string();
+        let character = &doc["characters"][&new_character];
+        assert_eq!(character["id"], new_character.as_str());
+        assert_eq!(character["relationships"][0]["npcId"], new_npc.as_str());
+        assert_eq!(character["relationships"][0]["questId"], external_id.to_string().as_str());
+        assert_eq!(doc["npcs"][0]["id"], new_npc.as_str());
+    }
+
     #[test]
     fn test_to_short_display_str() {
         let short = to_short_display_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
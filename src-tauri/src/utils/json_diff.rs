@@ -0,0 +1,210 @@
+//! Structural differences between JSON documents.
+//!
+//! A diff only holds the parts of a document that changed, so it stays small
+//! for small edits of large entities, and it can be applied in either
+//! direction. Applying checks the document is in the state the diff starts
+//! from, so a diff is never applied over unrelated changes.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Difference between two JSON values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum ValueDiff {
+    /// Replaced as a whole
+    Replace { before: Value, after: Value },
+    /// Fields of an object changed, were added or removed
+    Object {
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        changed: BTreeMap<String, ValueDiff>,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        added: BTreeMap<String, Value>,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        removed: BTreeMap<String, Value>,
+    },
+    /// Run of items of an array replaced by others, e.g. an item appended
+    Splice { index: usize, removed: Vec<Value>, inserted: Vec<Value> },
+}
+
+/// Difference from `before` to `after`, None if they're equal
+pub fn diff(before: &Value, after: &Value) -> Option<ValueDiff> {
+    if before == after {
+        return None;
+    }
+
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut changed = BTreeMap::new();
+            let mut added = BTreeMap::new();
+            let mut removed = BTreeMap::new();
+            for (key, old_value) in old {
+                match new.get(key) {
+                    Some(new_value) => {
+                        if let Some(field_diff) = diff(old_value, new_value) {
+                            changed.insert(key.clone(), field_diff);
+                        }
+                    }
+                    None => {
+                        removed.insert(key.clone(), old_value.clone());
+                    }
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    added.insert(key.clone(), new_value.clone());
+                }
+            }
+            Some(ValueDiff::Object { changed, added, removed })
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+            let suffix = old[prefix..].iter().rev()
+                .zip(new[prefix..].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            Some(ValueDiff::Splice {
+                index: prefix,
+                removed: old[prefix..old.len() - suffix].to_vec(),
+                inserted: new[prefix..new.len() - suffix].to_vec(),
+            })
+        }
+        _ => Some(ValueDiff::Replace { before: before.clone(), after: after.clone() }),
+    }
+}
+
+impl ValueDiff {
+    /// Diff going from `after` back to `before`
+    pub fn reversed(&self) -> ValueDiff {
+        match self {
+            ValueDiff::Replace { before, after } => ValueDiff::Replace { before: after.clone(), after: before.clone() },
+            ValueDiff::Object { changed, added, removed } => ValueDiff::Object {
+                changed: changed.iter().map(|(key, d)| (key.clone(), d.reversed())).collect(),
+                added: removed.clone(),
+                removed: added.clone(),
+            },
+            ValueDiff::Splice { index, removed, inserted } => ValueDiff::Splice {
+                index: *index,
+                removed: inserted.clone(),
+                inserted: removed.clone(),
+            },
+        }
+    }
+
+    /// Apply the diff to a value in its `before` state.
+    /// Returns false if the value isn't in that state; it may then be partially changed.
+    pub fn apply(&self, value: &mut Value) -> bool {
+        match self {
+            ValueDiff::Replace { before, after } => {
+                if value != before {
+                    return false;
+                }
+                *value = after.clone();
+                true
+            }
+            ValueDiff::Object { changed, added, removed } => {
+                let Value::Object(map) = value else {
+                    return false;
+                };
+                Self::apply_fields(map, changed, added, removed)
+            }
+            ValueDiff::Splice { index, removed, inserted } => {
+                let Value::Array(items) = value else {
+                    return false;
+                };
+                let end = index + removed.len();
+                if end > items.len() || items[*index..end] != removed[..] {
+                    return false;
+                }
+                items.splice(*index..end, inserted.iter().cloned());
+                true
+            }
+        }
+    }
+
+    fn apply_fields(
+        map: &mut Map<String, Value>,
+        changed: &BTreeMap<String, ValueDiff>,
+        added: &BTreeMap<String, Value>,
+        removed: &BTreeMap<String, Value>,
+    ) -> bool {
+        for (key, field_diff) in changed {
+            if !map.get_mut(key).is_some_and(|field| field_diff.apply(field)) {
+                return false;
+            }
+        }
+        for (key, old_value) in removed {
+            if map.remove(key).as_ref() != Some(old_value) {
+                return false;
+            }
+        }
+        for (key, new_value) in added {
+            if map.insert(key.clone(), new_value.clone()).is_some() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_round_trip() {
+        let before = json!({
+            "name": "Brann",
+            "level": 3,
+            "notes": null,
+            "achievements": [{ "title": "Slew the wyrm" }, { "title": "Saved the mayor" }],
+            "stats": { "str": 16, "dex": 10 },
+            "retired": false,
+        });
+        let after = json!({
+            "name": "Brann",
+            "level": 4,
+            "notes": "Owes the guild a favour",
+            "achievements": [{ "title": "Slew the wyrm" }, { "title": "Saved the mayor" }, { "title": "Found the relic" }],
+            "stats": { "str": 16, "dex": 12, "con": 14 },
+        });
+
+        let d = diff(&before, &after).unwrap();
+        let mut value = before.clone();
+        assert!(d.apply(&mut value));
+        assert_eq!(value, after);
+        assert!(d.reversed().apply(&mut value));
+        assert_eq!(value, before);
+
+        // Only what changed is kept: the appended achievement, not the whole list
+        let ValueDiff::Object { changed, removed, .. } = &d else { panic!("expected an object diff") };
+        assert_eq!(changed["achievements"], ValueDiff::Splice {
+            index: 2,
+            removed: vec![],
+            inserted: vec![json!({ "title": "Found the relic" })],
+        });
+        assert!(!changed.contains_key("name"));
+        assert_eq!(removed["retired"], json!(false));
+
+        assert!(diff(&before, &before).is_none());
+    }
+
+    #[test]
+    fn test_apply_refuses_other_states() {
+        let before = json!({ "hp": [10, 12], "name": "Brann" });
+        let after = json!({ "hp": [10, 8], "name": "Brann" });
+        let d = diff(&before, &after).unwrap();
+
+        // Already applied
+        assert!(!d.apply(&mut after.clone()));
+        // Changed elsewhere in the meantime
+        assert!(!d.apply(&mut json!({ "hp": [10, 11], "name": "Brann" })));
+        assert!(!d.apply(&mut json!({ "name": "Brann" })));
+        // Unrelated changes don't matter
+        let mut renamed = json!({ "hp": [10, 12], "name": "Brann the Bold" });
+        assert!(d.apply(&mut renamed));
+        assert_eq!(renamed, json!({ "hp": [10, 8], "name": "Brann the Bold" }));
+    }
+}
@@ -1,3 +1,8 @@
 pub mod id_generator;
 pub mod file_system;
 pub mod error;
+pub mod campaign_lock;
+pub mod json_diff;
+
+#[cfg(test)]
+pub mod test_support;
@@ -0,0 +1,115 @@
+use crate::core::models::event::{CampaignReloadedEvent, EntityEvent};
+use crate::services::events::{ChangeEmitter, EventService, SubscriptionId};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use tempfile::TempDir;
+
+static TEST_DATA_DIR: OnceLock<TempDir> = OnceLock::new();
+
+/// Temporary directory used as the app data directory by all tests.
+/// Tests must use fresh campaign IDs so they don't interfere with each other.
+pub fn test_data_dir() -> &'static Path {
+    TEST_DATA_DIR.get_or_init(|| {
+        tempfile::Builder::new()
+            .prefix("dm-assistant-test")
+            .tempdir()
+            .expect("Failed to create test data directory")
+    }).path()
+}
+
+/// Create the app data directory of the tests
+pub fn init_test_data_dir() {
+    test_data_dir();
+}
+
+/// Create a campaign in the test data directory and return its ID
+pub fn create_test_campaign() -> uuid::Uuid {
+    use crate::core::models::campaign::{Campaign, CreateCampaignRequest};
+    use crate::services::campaign_storage::CampaignStorageService;
+
+    init_test_data_dir();
+    let campaign = Campaign::new(CreateCampaignRequest {
+        name: "Test Campaign".to_string(),
+        description: None,
+        setting: None,
+        ruleset: None,
+        party_level: None,
+        in_game_date: None,
+    });
+    CampaignStorageService::create_campaign(campaign)
+        .expect("Failed to create test campaign")
+        .id
+}
+
+/// Emitter keeping the events sent while it's subscribed. Tests run in
+/// parallel, so it can keep the events of one campaign only.
+pub struct MockEmitter {
+    campaign_id: Option<uuid::Uuid>,
+    events: Mutex<Vec<EntityEvent>>,
+    reloads: Mutex<Vec<CampaignReloadedEvent>>,
+}
+
+/// Subscription of a mock emitter, unsubscribed when dropped
+pub struct Subscription {
+    id: SubscriptionId,
+    pub emitter: Arc<MockEmitter>,
+}
+
+impl ChangeEmitter for MockEmitter {
+    fn emit(&self, event: &EntityEvent) {
+        if self.campaign_id.is_none_or(|id| id == event.campaign_id) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    fn emit_reloaded(&self, event: &CampaignReloadedEvent) {
+        if self.campaign_id.is_none_or(|id| id == event.campaign_id) {
+            self.reloads.lock().unwrap().push(event.clone());
+        }
+    }
+}
+
+impl MockEmitter {
+    /// Keep the events of one campaign
+    pub fn subscribe(campaign_id: uuid::Uuid) -> Subscription {
+        Self::subscribe_to(Some(campaign_id))
+    }
+
+    /// Keep the events of every campaign, for campaigns that don't exist yet
+    pub fn subscribe_all() -> Subscription {
+        Self::subscribe_to(None)
+    }
+
+    fn subscribe_to(campaign_id: Option<uuid::Uuid>) -> Subscription {
+        let emitter = Arc::new(MockEmitter { campaign_id, events: Mutex::new(Vec::new()), reloads: Mutex::new(Vec::new()) });
+        Subscription { id: EventService::subscribe(emitter.clone()), emitter }
+    }
+
+    /// Entity events received since the last call
+    pub fn take(&self) -> Vec<EntityEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+
+    /// Campaign reloads received since the last call
+    pub fn take_reloads(&self) -> Vec<CampaignReloadedEvent> {
+        std::mem::take(&mut *self.reloads.lock().unwrap())
+    }
+}
+
+impl Subscription {
+    /// Entity events received since the last call
+    pub fn take(&self) -> Vec<EntityEvent> {
+        self.emitter.take()
+    }
+
+    /// Campaign reloads received since the last call
+    pub fn take_reloads(&self) -> Vec<CampaignReloadedEvent> {
+        self.emitter.take_reloads()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        EventService::unsubscribe(self.id);
+    }
+}